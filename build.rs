@@ -1,8 +1,17 @@
 use cfg_aliases::cfg_aliases;
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(x11)");
+    println!("cargo:rustc-check-cfg=cfg(win32)");
+    println!("cargo:rustc-check-cfg=cfg(wayland)");
+    println!("cargo:rustc-check-cfg=cfg(headless)");
+    println!("cargo:rustc-check-cfg=cfg(raw_window_handle)");
+
     cfg_aliases! {
         x11: {all(unix, feature="x11")},
+        wayland: {all(unix, feature="wayland")},
         win32: {windows},
+        headless: {feature="headless"},
+        raw_window_handle: {feature="raw-window-handle"},
     }
 }
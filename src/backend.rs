@@ -0,0 +1,165 @@
+use std::{env, str::FromStr};
+
+use crate::{CoreStateType, Error};
+
+/// The name of the environment variable that is read when the [Backend] is [Auto](Backend::Auto)
+pub const BACKEND_ENV_VAR: &str = "WWIND_BACKEND";
+
+/// Which backend a [WWindInstance](crate::WWindInstance) should try first.
+///
+/// The preferred backend is tried first. If it isn't compiled in or fails to start, the other
+/// compiled-in backends are tried in the order of [CoreStateType::ALL], so for example X11 falls
+/// back to headless when the `headless` feature is enabled. The backend that ended up being
+/// used can be found with [backend_type](crate::WWindInitState::backend_type).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Uses the backend named by the `WWIND_BACKEND` environment variable, or the platform's
+    /// default one if it isn't set
+    #[default]
+    Auto,
+    X11,
+    Wayland,
+    Win32,
+    Headless,
+}
+
+impl FromStr for Backend {
+    type Err = Error;
+
+    /// Parses a backend name. Names are case insensitive, and the empty string is
+    /// [Auto](Backend::Auto).
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "" | "auto" => Ok(Backend::Auto),
+            "x11" => Ok(Backend::X11),
+            "wayland" => Ok(Backend::Wayland),
+            "win32" | "windows" => Ok(Backend::Win32),
+            "headless" => Ok(Backend::Headless),
+            _ => Err(Error::UnknownBackend(name.to_owned())),
+        }
+    }
+}
+
+impl Backend {
+    /// Gets the backend that is named in the `WWIND_BACKEND` environment variable
+    pub fn from_env() -> Result<Self, Error> {
+        match env::var(BACKEND_ENV_VAR) {
+            Ok(name) => name.parse(),
+            Err(env::VarError::NotPresent) => Ok(Backend::Auto),
+            Err(env::VarError::NotUnicode(name)) => {
+                Err(Error::UnknownBackend(name.to_string_lossy().into_owned()))
+            }
+        }
+    }
+
+    /// Gets the compiled-in backend that this refers to, if there is one
+    fn core_state_type(self) -> Option<CoreStateType> {
+        match self {
+            #[cfg(x11)]
+            Backend::X11 => Some(CoreStateType::X11),
+            #[cfg(wayland)]
+            Backend::Wayland => Some(CoreStateType::Wayland),
+            #[cfg(windows)]
+            Backend::Win32 => Some(CoreStateType::Win32),
+            #[cfg(headless)]
+            Backend::Headless => Some(CoreStateType::Headless),
+            _ => None,
+        }
+    }
+
+    /// Gets the backends to try, in order
+    pub(crate) fn candidates(self) -> Result<Vec<CoreStateType>, Error> {
+        let backend = match self {
+            Backend::Auto => Self::from_env()?,
+            backend => backend,
+        };
+
+        let mut candidates = Vec::with_capacity(CoreStateType::ALL.len());
+
+        candidates.extend(backend.core_state_type());
+        candidates.extend(
+            CoreStateType::ALL
+                .iter()
+                .filter(|&&state_type| Some(state_type) != backend.core_state_type()),
+        );
+
+        Ok(candidates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Backend;
+    use crate::{CoreStateType, Error};
+
+    const BACKENDS: [Backend; 4] = [
+        Backend::X11,
+        Backend::Wayland,
+        Backend::Win32,
+        Backend::Headless,
+    ];
+
+    #[test]
+    fn names_are_case_insensitive() {
+        assert_eq!("x11".parse::<Backend>().unwrap(), Backend::X11);
+        assert_eq!("X11".parse::<Backend>().unwrap(), Backend::X11);
+        assert_eq!("Wayland".parse::<Backend>().unwrap(), Backend::Wayland);
+        assert_eq!("WIN32".parse::<Backend>().unwrap(), Backend::Win32);
+        assert_eq!("hEaDlEsS".parse::<Backend>().unwrap(), Backend::Headless);
+        assert_eq!("AUTO".parse::<Backend>().unwrap(), Backend::Auto);
+    }
+
+    #[test]
+    fn aliases() {
+        assert_eq!("windows".parse::<Backend>().unwrap(), Backend::Win32);
+        assert_eq!("Windows".parse::<Backend>().unwrap(), Backend::Win32);
+        assert_eq!("".parse::<Backend>().unwrap(), Backend::Auto);
+    }
+
+    #[test]
+    fn unknown_names() {
+        for name in ["xorg", " x11", "wayland ", "win64"] {
+            match name.parse::<Backend>() {
+                Err(Error::UnknownBackend(unknown)) => assert_eq!(unknown, name),
+                result => panic!("`{name}` parsed as {result:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn preferred_backend_comes_first() {
+        for backend in BACKENDS {
+            let candidates = backend.candidates().unwrap();
+
+            let Some(preferred) = backend.core_state_type() else {
+                // Backends that aren't compiled in are skipped
+                assert_eq!(candidates, CoreStateType::ALL);
+                continue;
+            };
+
+            assert_eq!(candidates[0], preferred);
+
+            // The others keep their order
+            let others: Vec<_> = CoreStateType::ALL
+                .iter()
+                .copied()
+                .filter(|&state_type| state_type != preferred)
+                .collect();
+            assert_eq!(candidates[1..], others);
+        }
+    }
+
+    #[test]
+    fn candidates_have_no_duplicates() {
+        for backend in BACKENDS {
+            let candidates = backend.candidates().unwrap();
+            let unique: HashSet<_> = candidates.iter().collect();
+
+            assert_eq!(candidates.len(), CoreStateType::ALL.len());
+            assert_eq!(unique.len(), candidates.len());
+        }
+    }
+}
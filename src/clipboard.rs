@@ -0,0 +1,154 @@
+use std::marker::PhantomData;
+
+use crate::{core::CoreStateImplementation, state::CoreStateData, util::PhantomUnsend, Error};
+
+/// The MIME type that [set_text](Clipboard::set_text) stores text as. The backends translate it
+/// to the text formats of the platform, like `UTF8_STRING` on X11.
+pub const TEXT_MIME_TYPE: &str = "text/plain;charset=utf-8";
+
+/// A place that other programs can copy from and paste into
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Selection {
+    /// What is copied and pasted with Ctrl+C and Ctrl+V
+    #[default]
+    Clipboard,
+    /// The text that was last selected, which is pasted with the middle mouse button. Only X11
+    /// has this, so on the other backends it is only shared within the program.
+    Primary,
+}
+
+/// The formats that a selection is offered in, as MIME types and data
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SelectionContents(Vec<(String, Vec<u8>)>);
+
+impl SelectionContents {
+    pub fn new(formats: &[(&str, &[u8])]) -> Self {
+        Self(
+            formats
+                .iter()
+                .map(|&(mime_type, data)| (mime_type.to_owned(), data.to_vec()))
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, mime_type: &str) -> Option<&[u8]> {
+        self.0
+            .iter()
+            .find(|(format, _)| format == mime_type)
+            .map(|(_, data)| data.as_slice())
+    }
+
+    pub fn types(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|(mime_type, _)| mime_type.clone())
+            .collect()
+    }
+
+    #[cfg(any(x11, win32))]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.0
+            .iter()
+            .map(|(mime_type, data)| (mime_type.as_str(), data.as_slice()))
+    }
+}
+
+/// Copies to and pastes from the selections that are shared with other programs. Can be
+/// obtained with [clipboard](crate::WWindInitState::clipboard).
+///
+/// Getting a selection that another program owns waits for that program to send it, which
+/// takes at most a few seconds if it doesn't respond. On Wayland, the selections are only
+/// shared within the program for now.
+///
+/// # Example
+/// ```rust,no_run
+/// use wwind::{Selection, WWindInitState, WWindInstance};
+///
+/// WWindInstance::new(|state: &mut WWindInitState| {
+///     let mut clipboard = state.clipboard();
+///
+///     if let Some(text) = clipboard.get_text(Selection::Clipboard).unwrap() {
+///         println!("The clipboard has {text:?}");
+///     }
+///
+///     clipboard.set_text(Selection::Clipboard, "Hello world").unwrap();
+/// })
+/// .unwrap();
+/// ```
+pub struct Clipboard<'a> {
+    data: *mut CoreStateData,
+    _unsend: PhantomUnsend,
+    _phantom_data: PhantomData<&'a ()>,
+}
+
+impl<'a> Clipboard<'a> {
+    /// Puts text in a selection
+    pub fn set_text(&mut self, selection: Selection, text: &str) -> Result<(), Error> {
+        self.set_data(selection, &[(TEXT_MIME_TYPE, text.as_bytes())])
+    }
+
+    /// Puts data in a selection, in each of the given MIME types. Other programs pick the one
+    /// that suits them best. An empty slice clears the selection if this program owns it.
+    pub fn set_data(
+        &mut self,
+        selection: Selection,
+        formats: &[(&str, &[u8])],
+    ) -> Result<(), Error> {
+        self.get_data_mut()
+            .core_state
+            .set_selection(selection, SelectionContents::new(formats))?;
+
+        Ok(())
+    }
+
+    /// Gets the text in a selection, or `None` if it doesn't have any. Invalid UTF-8 is replaced
+    /// with U+FFFD.
+    pub fn get_text(&mut self, selection: Selection) -> Result<Option<String>, Error> {
+        let text = self.get_data(selection, TEXT_MIME_TYPE)?;
+
+        Ok(text.map(|text| String::from_utf8_lossy(&text).into_owned()))
+    }
+
+    /// Gets the data in a selection as `mime_type`, or `None` if it isn't available as that
+    pub fn get_data(
+        &mut self,
+        selection: Selection,
+        mime_type: &str,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let data = self
+            .get_data_mut()
+            .core_state
+            .get_selection(selection, mime_type)?;
+
+        Ok(data)
+    }
+
+    /// Gets the MIME types that a selection is available as. Text is reported as
+    /// [TEXT_MIME_TYPE].
+    pub fn get_types(&mut self, selection: Selection) -> Result<Vec<String>, Error> {
+        let types = self
+            .get_data_mut()
+            .core_state
+            .get_selection_types(selection)?;
+
+        Ok(types)
+    }
+}
+
+impl<'a> Clipboard<'a> {
+    pub(crate) fn from_parts(data: *mut CoreStateData) -> Self {
+        Self {
+            data,
+            _unsend: Default::default(),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    fn get_data_mut(&mut self) -> &mut CoreStateData {
+        unsafe { &mut *self.data }
+    }
+}
@@ -0,0 +1,66 @@
+//! A software rasterizer for the backends that draw into memory
+
+use crate::{Color, RectRegion};
+
+/// A pixel buffer that the software rasterizer can draw on
+pub trait Canvas {
+    fn width(&self) -> u16;
+
+    fn height(&self) -> u16;
+
+    /// Sets a pixel. `x` and `y` are always inside of the canvas.
+    fn put_pixel(&mut self, x: u16, y: u16, color: Color);
+
+    fn fill_rectangle(&mut self, rectangle: RectRegion, color: Color) {
+        let right_x = rectangle.get_right_x().min(self.width());
+        let bottom_y = rectangle.get_bottom_y().min(self.height());
+
+        for y in rectangle.y..bottom_y {
+            for x in rectangle.x..right_x {
+                self.put_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Draws a one pixel wide line with Bresenham's algorithm
+    fn draw_line(&mut self, x1: u16, y1: u16, x2: u16, y2: u16, color: Color) {
+        // Always drawn from the same end, because which of two equally close pixels is picked
+        // depends on the direction
+        let ((x1, y1), (x2, y2)) = if (x1, y1) <= (x2, y2) {
+            ((x1, y1), (x2, y2))
+        } else {
+            ((x2, y2), (x1, y1))
+        };
+
+        let (mut x, mut y) = (x1 as i32, y1 as i32);
+        let (x2, y2) = (x2 as i32, y2 as i32);
+
+        let dx = (x2 - x).abs();
+        let dy = -(y2 - y).abs();
+        let step_x = if x < x2 { 1 } else { -1 };
+        let step_y = if y < y2 { 1 } else { -1 };
+        let mut error = dx + dy;
+
+        loop {
+            if x < self.width() as i32 && y < self.height() as i32 {
+                self.put_pixel(x as u16, y as u16, color);
+            }
+
+            if x == x2 && y == y2 {
+                break;
+            }
+
+            let double_error = 2 * error;
+
+            if double_error >= dy {
+                error += dy;
+                x += step_x;
+            }
+
+            if double_error <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+}
@@ -1,14 +1,32 @@
 use crate::{
+    clipboard::{Selection, SelectionContents},
+    input::{KeyEvent, MouseButtonEvent, MouseMoveEvent, ScrollEvent},
     state::{CoreStateType, CORE_STATE_TYPE, STATE_CREATED},
-    Color, RectRegion,
+    Color, CursorIcon, DropEvent, DropPayload, Monitor, RectRegion, ResizeEdge, RgbaImage,
+    WindowBuilder, WindowStateFlags,
 };
-use std::{convert::Infallible, hash::Hash, sync::atomic};
+use std::{
+    convert::Infallible,
+    fmt,
+    hash::Hash,
+    ptr::{addr_of, addr_of_mut},
+    sync::atomic,
+    time::Duration,
+};
+
+#[cfg(raw_window_handle)]
+use raw_window_handle::{HandleError, RawDisplayHandle, RawWindowHandle};
 
 #[cfg(x11)]
 use super::x11rb::X11RbState;
 
 #[cfg(windows)]
-use super::win32::Win32State;
+use super::win32::{Win32Error, Win32State};
+
+#[cfg(headless)]
+use super::headless::HeadlessState;
+#[cfg(wayland)]
+use super::wayland::{WaylandState, WlError};
 
 pub trait CoreStateImplementation: Sized {
     /// The error that can occur when initializing the state
@@ -17,26 +35,119 @@ pub trait CoreStateImplementation: Sized {
     type Window: Sized + Copy;
     /// Something that you can draw on
     type DrawingContext: Sized + Copy;
+    /// Wakes up the event loop from another thread
+    type Waker: CoreWaker;
 
     /// ## Safety
     /// Should not be called while another CoreStateImplementation exists
     unsafe fn new() -> Result<Self, Self::Error>;
-    fn add_window(
+    fn add_window(&mut self, builder: &WindowBuilder) -> Result<Self::Window, Self::Error>;
+    fn set_window_title(&mut self, window: Self::Window, title: &str) -> Result<(), Self::Error>;
+    /// Lets things be dragged over and dropped on a window. This can't be undone.
+    fn accept_window_drops(&mut self, window: Self::Window);
+    fn set_window_position(
         &mut self,
+        window: Self::Window,
         x: i16,
         y: i16,
-        height: u16,
+    ) -> Result<(), Self::Error>;
+    /// Resizes the contents of a window. The change is reported with a Resize event.
+    fn set_window_size(
+        &mut self,
+        window: Self::Window,
         width: u16,
-        title: &str,
-    ) -> Result<Self::Window, Self::Error>;
-    fn set_window_title(&mut self, window: Self::Window, title: &str);
+        height: u16,
+    ) -> Result<(), Self::Error>;
+    fn set_window_visible(
+        &mut self,
+        window: Self::Window,
+        visible: bool,
+    ) -> Result<(), Self::Error>;
+    fn focus_window(&mut self, window: Self::Window) -> Result<(), Self::Error>;
+    /// Puts a window above the other windows
+    fn raise_window(&mut self, window: Self::Window) -> Result<(), Self::Error>;
+    /// Changes are reported with a StateChange event
+    fn set_window_fullscreen(
+        &mut self,
+        window: Self::Window,
+        fullscreen: bool,
+    ) -> Result<(), Self::Error>;
+    fn set_window_maximized(
+        &mut self,
+        window: Self::Window,
+        maximized: bool,
+    ) -> Result<(), Self::Error>;
+    fn minimize_window(&mut self, window: Self::Window) -> Result<(), Self::Error>;
+    fn set_window_decorations(
+        &mut self,
+        window: Self::Window,
+        decorations: bool,
+    ) -> Result<(), Self::Error>;
+    /// An empty slice removes the icon
+    fn set_window_icon(
+        &mut self,
+        window: Self::Window,
+        icons: &[RgbaImage],
+    ) -> Result<(), Self::Error>;
+    fn set_window_cursor(
+        &mut self,
+        window: Self::Window,
+        cursor: CursorIcon,
+    ) -> Result<(), Self::Error>;
+    /// The image isn't empty, and the hotspot is inside of it
+    fn set_window_custom_cursor(
+        &mut self,
+        window: Self::Window,
+        image: &RgbaImage,
+        hotspot: (u16, u16),
+    ) -> Result<(), Self::Error>;
+    /// Lets the window manager move the window, or resize it if `edge` is set, while the
+    /// pointer is held down
+    fn start_window_drag(
+        &mut self,
+        window: Self::Window,
+        edge: Option<ResizeEdge>,
+    ) -> Result<(), Self::Error>;
+    /// Starts dragging data out of a window while a mouse button is held. The end of the drag is
+    /// reported with [WWindCoreEvent::DragEnd].
+    fn start_data_drag(
+        &mut self,
+        window: Self::Window,
+        payloads: &[DropPayload],
+        image: Option<(&RgbaImage, (u16, u16))>,
+    ) -> Result<(), Self::Error>;
+    /// Takes ownership of a selection. Empty contents give up the selection if it is owned.
+    fn set_selection(
+        &mut self,
+        selection: Selection,
+        contents: SelectionContents,
+    ) -> Result<(), Self::Error>;
+    /// Gets the data in a selection, or `None` if it isn't available as `mime_type`
+    fn get_selection(
+        &mut self,
+        selection: Selection,
+        mime_type: &str,
+    ) -> Result<Option<Vec<u8>>, Self::Error>;
+    fn get_selection_types(&mut self, selection: Selection) -> Result<Vec<String>, Self::Error>;
+    fn get_monitors(&self) -> Result<Vec<Monitor>, Self::Error>;
     fn flush(&mut self) -> Result<(), Self::Error>;
     /// ## Safety
     /// The same window should not be destroyed twice
     unsafe fn destroy_window(&mut self, window: Self::Window);
-    unsafe fn wait_for_events(&mut self, on_event: &mut unsafe fn(WWindCoreEvent));
-
-    fn get_size(&self, window: Self::Window) -> (u16, u16);
+    fn get_waker(&self) -> Self::Waker;
+    /// Waits for an event and handles it. Returns early if nothing happened before `timeout`.
+    /// Returns whether an event was handled.
+    unsafe fn wait_for_events(
+        &mut self,
+        on_event: &mut unsafe fn(WWindCoreEvent),
+        timeout: Option<Duration>,
+    ) -> Result<bool, Self::Error>;
+    /// Gets the native handle of a window, for other libraries to draw into
+    #[cfg(raw_window_handle)]
+    fn raw_window_handle(&self, window: Self::Window) -> Result<RawWindowHandle, HandleError>;
+    /// Gets the native handle of the connection to the display server
+    #[cfg(raw_window_handle)]
+    fn raw_display_handle(&self) -> Result<RawDisplayHandle, HandleError>;
 
     // Drawing
     unsafe fn get_context(&mut self, window: Self::Window) -> Self::DrawingContext;
@@ -62,19 +173,55 @@ pub trait CoreStateImplementation: Sized {
     ) -> Result<(), Self::Error>;
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub enum WWindCoreEvent {
     CloseWindow(CoreWindowRef),
     Expose(CoreWindowRef, RectRegion),
-    Keydown(CoreWindowRef, u32),
+    /// The window was resized to the given width and height
+    Resize(CoreWindowRef, u16, u16),
+    /// The window was moved to the given position on the screen
+    Move(CoreWindowRef, i16, i16),
+    Keydown(CoreWindowRef, KeyEvent),
+    Keyup(CoreWindowRef, KeyEvent),
+    /// A character was typed. Dead keys and the keyboard layout have already been applied.
+    TextInput(CoreWindowRef, char),
+    MouseDown(CoreWindowRef, MouseButtonEvent),
+    MouseUp(CoreWindowRef, MouseButtonEvent),
+    MouseMove(CoreWindowRef, MouseMoveEvent),
+    Scroll(CoreWindowRef, ScrollEvent),
+    /// The window gained (`true`) or lost (`false`) keyboard focus
+    FocusChange(CoreWindowRef, bool),
+    PointerEnter(CoreWindowRef, MouseMoveEvent),
+    PointerLeave(CoreWindowRef, MouseMoveEvent),
+    /// The window manager maximized, minimized or fullscreened the window, or undid that
+    StateChange(CoreWindowRef, WindowStateFlags),
+    /// Something was dragged into the window or moved inside of it
+    DragOver(CoreWindowRef, DropEvent),
+    DragLeave(CoreWindowRef),
+    DragDrop(CoreWindowRef, DropEvent),
+    /// A drag that the window started is over. `None` if nothing was dropped.
+    DragEnd(CoreWindowRef, Option<crate::DragAction>),
+    /// A monitor was connected, disconnected or changed
+    MonitorsChanged,
+}
+
+/// Wakes up a waiting [CoreStateImplementation::wait_for_events] call
+pub trait CoreWaker: Clone + Send + Sync + 'static {
+    fn wake(&self);
 }
 
 /// An enumeration over all of the [CoreStateImplementation]s.
+// Only one of these exists and it is stored in a Box, so the size difference does not matter
+#[allow(clippy::large_enum_variant)]
 pub(crate) enum CoreStateEnum {
     #[cfg(x11)]
     X11(X11RbState),
     #[cfg(windows)]
     Win32(Win32State),
+    #[cfg(headless)]
+    Headless(HeadlessState),
+    #[cfg(wayland)]
+    Wayland(WaylandState),
 }
 
 /// Represents a reference to a window from any [CoreStateImplementation].
@@ -84,6 +231,24 @@ pub union CoreWindowRef {
     x11: <X11RbState as CoreStateImplementation>::Window,
     #[cfg(windows)]
     win32: <Win32State as CoreStateImplementation>::Window,
+    #[cfg(headless)]
+    headless: <HeadlessState as CoreStateImplementation>::Window,
+    #[cfg(wayland)]
+    wayland: <WaylandState as CoreStateImplementation>::Window,
+}
+
+#[cfg(headless)]
+impl From<<HeadlessState as CoreStateImplementation>::Window> for CoreWindowRef {
+    fn from(headless: <HeadlessState as CoreStateImplementation>::Window) -> Self {
+        CoreWindowRef { headless }
+    }
+}
+
+#[cfg(wayland)]
+impl From<<WaylandState as CoreStateImplementation>::Window> for CoreWindowRef {
+    fn from(wayland: <WaylandState as CoreStateImplementation>::Window) -> Self {
+        CoreWindowRef { wayland }
+    }
 }
 
 #[cfg(windows)]
@@ -109,6 +274,14 @@ impl CoreWindowRef {
     pub unsafe fn win32(self) -> <Win32State as CoreStateImplementation>::Window {
         self.win32
     }
+    #[cfg(headless)]
+    pub unsafe fn headless(self) -> <HeadlessState as CoreStateImplementation>::Window {
+        self.headless
+    }
+    #[cfg(wayland)]
+    pub unsafe fn wayland(self) -> <WaylandState as CoreStateImplementation>::Window {
+        self.wayland
+    }
 }
 
 impl PartialEq for CoreWindowRef {
@@ -117,13 +290,17 @@ impl PartialEq for CoreWindowRef {
             if !STATE_CREATED.load(atomic::Ordering::Acquire) {
                 panic!("PartialEq called with no loaded state");
             }
-            let state_type = CORE_STATE_TYPE.assume_init_ref();
+            let state_type = (*addr_of!(CORE_STATE_TYPE)).assume_init_ref();
 
             match state_type {
                 #[cfg(x11)]
                 CoreStateType::X11 => self.x11() == other.x11(),
                 #[cfg(windows)]
                 CoreStateType::Win32 => self.win32 == other.win32(),
+                #[cfg(headless)]
+                CoreStateType::Headless => self.headless() == other.headless(),
+                #[cfg(wayland)]
+                CoreStateType::Wayland => self.wayland() == other.wayland(),
             }
         }
     }
@@ -135,10 +312,15 @@ pub enum DrawingContextEnum {
     X11(<X11RbState as CoreStateImplementation>::DrawingContext),
     #[cfg(windows)]
     Win32(<Win32State as CoreStateImplementation>::DrawingContext),
+    #[cfg(headless)]
+    Headless(<HeadlessState as CoreStateImplementation>::DrawingContext),
+    #[cfg(wayland)]
+    Wayland(<WaylandState as CoreStateImplementation>::DrawingContext),
 }
 
 impl DrawingContextEnum {
     #[cfg(x11)]
+    #[allow(irrefutable_let_patterns)]
     unsafe fn x11(self) -> <X11RbState as CoreStateImplementation>::DrawingContext {
         if let Self::X11(context) = self {
             context
@@ -147,6 +329,7 @@ impl DrawingContextEnum {
         }
     }
     #[cfg(windows)]
+    #[allow(irrefutable_let_patterns)]
     unsafe fn win32(self) -> <Win32State as CoreStateImplementation>::DrawingContext {
         if let Self::Win32(context) = self {
             context
@@ -154,6 +337,38 @@ impl DrawingContextEnum {
             panic!()
         }
     }
+    #[cfg(headless)]
+    #[allow(irrefutable_let_patterns)]
+    unsafe fn headless(self) -> <HeadlessState as CoreStateImplementation>::DrawingContext {
+        if let Self::Headless(context) = self {
+            context
+        } else {
+            panic!()
+        }
+    }
+    #[cfg(wayland)]
+    #[allow(irrefutable_let_patterns)]
+    unsafe fn wayland(self) -> <WaylandState as CoreStateImplementation>::DrawingContext {
+        if let Self::Wayland(context) = self {
+            context
+        } else {
+            panic!()
+        }
+    }
+}
+
+#[cfg(headless)]
+impl From<<HeadlessState as CoreStateImplementation>::DrawingContext> for DrawingContextEnum {
+    fn from(value: <HeadlessState as CoreStateImplementation>::DrawingContext) -> Self {
+        Self::Headless(value)
+    }
+}
+
+#[cfg(wayland)]
+impl From<<WaylandState as CoreStateImplementation>::DrawingContext> for DrawingContextEnum {
+    fn from(value: <WaylandState as CoreStateImplementation>::DrawingContext) -> Self {
+        Self::Wayland(value)
+    }
 }
 
 #[cfg(windows)]
@@ -170,6 +385,33 @@ impl From<<X11RbState as CoreStateImplementation>::DrawingContext> for DrawingCo
     }
 }
 
+#[derive(Clone)]
+pub enum WakerEnum {
+    #[cfg(x11)]
+    X11(<X11RbState as CoreStateImplementation>::Waker),
+    #[cfg(windows)]
+    Win32(<Win32State as CoreStateImplementation>::Waker),
+    #[cfg(headless)]
+    Headless(<HeadlessState as CoreStateImplementation>::Waker),
+    #[cfg(wayland)]
+    Wayland(<WaylandState as CoreStateImplementation>::Waker),
+}
+
+impl CoreWaker for WakerEnum {
+    fn wake(&self) {
+        match self {
+            #[cfg(x11)]
+            WakerEnum::X11(waker) => waker.wake(),
+            #[cfg(windows)]
+            WakerEnum::Win32(waker) => waker.wake(),
+            #[cfg(headless)]
+            WakerEnum::Headless(waker) => waker.wake(),
+            #[cfg(wayland)]
+            WakerEnum::Wayland(waker) => waker.wake(),
+        }
+    }
+}
+
 impl Eq for CoreWindowRef {}
 
 impl Hash for CoreWindowRef {
@@ -179,13 +421,17 @@ impl Hash for CoreWindowRef {
                 panic!("Hash called with no loaded state");
             }
 
-            let state_type = CORE_STATE_TYPE.assume_init_ref();
+            let state_type = (*addr_of!(CORE_STATE_TYPE)).assume_init_ref();
 
             match state_type {
                 #[cfg(x11)]
                 CoreStateType::X11 => self.x11().hash(state),
                 #[cfg(windows)]
                 CoreStateType::Win32 => self.win32().hash(state),
+                #[cfg(headless)]
+                CoreStateType::Headless => self.headless().hash(state),
+                #[cfg(wayland)]
+                CoreStateType::Wayland => self.wayland().hash(state),
             }
         }
     }
@@ -193,8 +439,31 @@ impl Hash for CoreWindowRef {
 
 #[derive(Debug)]
 pub enum CoreError {
+    /// None of the backends that were tried are compiled in
+    NoBackend,
+    /// The backend can't do what was asked. Becomes [Error::Unsupported](crate::Error::Unsupported).
+    Unsupported,
     #[cfg(x11)]
     RbError(<X11RbState as CoreStateImplementation>::Error),
+    #[cfg(wayland)]
+    WlError(<WaylandState as CoreStateImplementation>::Error),
+    #[cfg(windows)]
+    Win32Error(<Win32State as CoreStateImplementation>::Error),
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            CoreError::NoBackend => f.write_str("no backend is available"),
+            CoreError::Unsupported => f.write_str("the backend doesn't support this"),
+            #[cfg(x11)]
+            CoreError::RbError(ref err) => err.fmt(f),
+            #[cfg(wayland)]
+            CoreError::WlError(ref err) => err.fmt(f),
+            #[cfg(windows)]
+            CoreError::Win32Error(ref err) => err.fmt(f),
+        }
+    }
 }
 
 impl From<Infallible> for CoreError {
@@ -210,6 +479,89 @@ impl From<<X11RbState as CoreStateImplementation>::Error> for CoreError {
     }
 }
 
+#[cfg(wayland)]
+impl From<<WaylandState as CoreStateImplementation>::Error> for CoreError {
+    fn from(value: <WaylandState as CoreStateImplementation>::Error) -> Self {
+        match value {
+            WlError::Unsupported => CoreError::Unsupported,
+            value => CoreError::WlError(value),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl From<<Win32State as CoreStateImplementation>::Error> for CoreError {
+    fn from(value: <Win32State as CoreStateImplementation>::Error) -> Self {
+        match value {
+            Win32Error::Unsupported => CoreError::Unsupported,
+            value => CoreError::Win32Error(value),
+        }
+    }
+}
+
+impl CoreStateEnum {
+    /// Tries to create each backend in `backends` in order and returns the first one that
+    /// works. If every backend fails, the error from the first one is returned.
+    /// ## Safety
+    /// Should not be called while another CoreStateImplementation exists
+    pub unsafe fn load(backends: &[CoreStateType]) -> Result<Self, CoreError> {
+        let mut first_error = None;
+
+        for &state_type in backends {
+            match Self::new_with_type(state_type) {
+                Ok(state) => return Ok(state),
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        Err(first_error.unwrap_or(CoreError::NoBackend))
+    }
+
+    /// ## Safety
+    /// Should not be called while another CoreStateImplementation exists
+    unsafe fn new_with_type(state_type: CoreStateType) -> Result<Self, CoreError> {
+        let state = match state_type {
+            #[cfg(x11)]
+            CoreStateType::X11 => CoreStateEnum::X11(X11RbState::new()?),
+            #[cfg(windows)]
+            CoreStateType::Win32 => CoreStateEnum::Win32(Win32State::new()?),
+            #[cfg(headless)]
+            CoreStateType::Headless => CoreStateEnum::Headless(HeadlessState::new()?),
+            #[cfg(wayland)]
+            CoreStateType::Wayland => CoreStateEnum::Wayland(WaylandState::new()?),
+        };
+
+        (*addr_of_mut!(CORE_STATE_TYPE)).write(state_type);
+
+        Ok(state)
+    }
+
+    pub fn get_type(&self) -> CoreStateType {
+        match self {
+            #[cfg(x11)]
+            CoreStateEnum::X11(_) => CoreStateType::X11,
+            #[cfg(windows)]
+            CoreStateEnum::Win32(_) => CoreStateType::Win32,
+            #[cfg(headless)]
+            CoreStateEnum::Headless(_) => CoreStateType::Headless,
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(_) => CoreStateType::Wayland,
+        }
+    }
+
+    #[cfg(headless)]
+    pub fn headless_handle(&self) -> Option<super::HeadlessHandle> {
+        #[allow(irrefutable_let_patterns)]
+        if let CoreStateEnum::Headless(state) = self {
+            Some(state.get_handle())
+        } else {
+            None
+        }
+    }
+}
+
 // Boilerplate that I intend to eventually automate with a macro
 
 impl CoreStateImplementation for CoreStateEnum {
@@ -219,71 +571,351 @@ impl CoreStateImplementation for CoreStateEnum {
 
     type DrawingContext = DrawingContextEnum;
 
+    type Waker = WakerEnum;
+
     unsafe fn new() -> Result<Self, Self::Error> {
-        #[cfg(windows)]
-        {
-            CORE_STATE_TYPE.write(CoreStateType::Win32);
+        Self::load(CoreStateType::ALL)
+    }
 
-            let win32_state = unsafe { Win32State::new().unwrap() };
+    fn add_window(&mut self, builder: &WindowBuilder) -> Result<Self::Window, Self::Error> {
+        let window = match self {
+            #[cfg(x11)]
+            CoreStateEnum::X11(x11_state) => x11_state.add_window(builder)?.into(),
+            #[cfg(windows)]
+            CoreStateEnum::Win32(win32_state) => win32_state.add_window(builder)?.into(),
+            #[cfg(headless)]
+            CoreStateEnum::Headless(headless_state) => headless_state.add_window(builder)?.into(),
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(wayland_state) => wayland_state.add_window(builder)?.into(),
+        };
+        Ok(window)
+    }
 
-            let core_state = CoreStateEnum::Win32(win32_state);
+    fn set_window_title(&mut self, window: Self::Window, title: &str) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.set_window_title(window.x11(), title)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.set_window_title(window.win32(), title)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.set_window_title(window.headless(), title)?,
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.set_window_title(window.wayland(), title)?,
+            }
+        }
+        Ok(())
+    }
 
-            Ok(core_state)
+    fn accept_window_drops(&mut self, window: Self::Window) {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.accept_window_drops(window.x11()),
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.accept_window_drops(window.win32()),
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.accept_window_drops(window.headless()),
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.accept_window_drops(window.wayland()),
+            }
         }
+    }
 
-        #[cfg(x11)]
-        {
-            let err = {
-                match X11RbState::new() {
-                    Ok(state) => {
-                        CORE_STATE_TYPE.write(CoreStateType::X11);
+    fn set_window_position(
+        &mut self,
+        window: Self::Window,
+        x: i16,
+        y: i16,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.set_window_position(window.x11(), x, y)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.set_window_position(window.win32(), x, y)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.set_window_position(window.headless(), x, y)?,
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.set_window_position(window.wayland(), x, y)?,
+            }
+        }
+        Ok(())
+    }
 
-                        let state = CoreStateEnum::X11(state);
+    fn set_window_size(
+        &mut self,
+        window: Self::Window,
+        width: u16,
+        height: u16,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.set_window_size(window.x11(), width, height)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.set_window_size(window.win32(), width, height)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => {
+                    s.set_window_size(window.headless(), width, height)?
+                }
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.set_window_size(window.wayland(), width, height)?,
+            }
+        }
+        Ok(())
+    }
 
-                        return Ok(state);
-                    }
-                    Err(err) => err,
+    fn set_window_visible(
+        &mut self,
+        window: Self::Window,
+        visible: bool,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.set_window_visible(window.x11(), visible)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.set_window_visible(window.win32(), visible)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.set_window_visible(window.headless(), visible)?,
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.set_window_visible(window.wayland(), visible)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn focus_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.focus_window(window.x11())?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.focus_window(window.win32())?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.focus_window(window.headless())?,
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.focus_window(window.wayland())?,
+            }
+        }
+        Ok(())
+    }
+
+    fn raise_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.raise_window(window.x11())?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.raise_window(window.win32())?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.raise_window(window.headless())?,
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.raise_window(window.wayland())?,
+            }
+        }
+        Ok(())
+    }
+
+    fn set_window_fullscreen(
+        &mut self,
+        window: Self::Window,
+        fullscreen: bool,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.set_window_fullscreen(window.x11(), fullscreen)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.set_window_fullscreen(window.win32(), fullscreen)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => {
+                    s.set_window_fullscreen(window.headless(), fullscreen)?
                 }
-            };
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => {
+                    s.set_window_fullscreen(window.wayland(), fullscreen)?
+                }
+            }
+        }
+        Ok(())
+    }
 
-            Err(err.into())
+    fn set_window_maximized(
+        &mut self,
+        window: Self::Window,
+        maximized: bool,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.set_window_maximized(window.x11(), maximized)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.set_window_maximized(window.win32(), maximized)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => {
+                    s.set_window_maximized(window.headless(), maximized)?
+                }
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.set_window_maximized(window.wayland(), maximized)?,
+            }
         }
+        Ok(())
     }
 
-    fn add_window(
+    fn minimize_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.minimize_window(window.x11())?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.minimize_window(window.win32())?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.minimize_window(window.headless())?,
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.minimize_window(window.wayland())?,
+            }
+        }
+        Ok(())
+    }
+
+    fn set_window_decorations(
         &mut self,
-        x: i16,
-        y: i16,
-        height: u16,
-        width: u16,
-        title: &str,
-    ) -> Result<Self::Window, Self::Error> {
-        let window = match self {
-            #[cfg(x11)]
-            CoreStateEnum::X11(x11_state) => {
-                x11_state.add_window(x, y, height, width, title)?.into()
+        window: Self::Window,
+        decorations: bool,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.set_window_decorations(window.x11(), decorations)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.set_window_decorations(window.win32(), decorations)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => {
+                    s.set_window_decorations(window.headless(), decorations)?
+                }
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => {
+                    s.set_window_decorations(window.wayland(), decorations)?
+                }
             }
-            #[cfg(windows)]
-            CoreStateEnum::Win32(win32_state) => {
-                win32_state.add_window(x, y, height, width, title)?.into()
+        }
+        Ok(())
+    }
+
+    fn start_window_drag(
+        &mut self,
+        window: Self::Window,
+        edge: Option<ResizeEdge>,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.start_window_drag(window.x11(), edge)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.start_window_drag(window.win32(), edge)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.start_window_drag(window.headless(), edge)?,
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.start_window_drag(window.wayland(), edge)?,
             }
-        };
-        Ok(window)
+        }
+        Ok(())
     }
 
-    fn set_window_title(&mut self, window: Self::Window, title: &str) {
+    fn start_data_drag(
+        &mut self,
+        window: Self::Window,
+        payloads: &[DropPayload],
+        image: Option<(&RgbaImage, (u16, u16))>,
+    ) -> Result<(), Self::Error> {
         unsafe {
             match self {
                 #[cfg(x11)]
-                CoreStateEnum::X11(x11_state) => {
-                    x11_state.set_window_title(Self::Window::x11(window), title)
+                CoreStateEnum::X11(s) => s.start_data_drag(window.x11(), payloads, image)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.start_data_drag(window.win32(), payloads, image)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => {
+                    s.start_data_drag(window.headless(), payloads, image)?
+                }
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => {
+                    s.start_data_drag(window.wayland(), payloads, image)?
                 }
+            }
+        }
+        Ok(())
+    }
+
+    fn set_window_icon(
+        &mut self,
+        window: Self::Window,
+        icons: &[RgbaImage],
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.set_window_icon(window.x11(), icons)?,
                 #[cfg(windows)]
-                CoreStateEnum::Win32(win32_state) => {
-                    win32_state.set_window_title(Self::Window::win32(window), title)
+                CoreStateEnum::Win32(s) => s.set_window_icon(window.win32(), icons)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.set_window_icon(window.headless(), icons)?,
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.set_window_icon(window.wayland(), icons)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn set_window_cursor(
+        &mut self,
+        window: Self::Window,
+        cursor: CursorIcon,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.set_window_cursor(window.x11(), cursor)?,
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.set_window_cursor(window.win32(), cursor)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.set_window_cursor(window.headless(), cursor)?,
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.set_window_cursor(window.wayland(), cursor)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn set_window_custom_cursor(
+        &mut self,
+        window: Self::Window,
+        image: &RgbaImage,
+        hotspot: (u16, u16),
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => {
+                    s.set_window_custom_cursor(window.x11(), image, hotspot)?
+                }
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => {
+                    s.set_window_custom_cursor(window.win32(), image, hotspot)?
+                }
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => {
+                    s.set_window_custom_cursor(window.headless(), image, hotspot)?
+                }
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => {
+                    s.set_window_custom_cursor(window.wayland(), image, hotspot)?
                 }
             }
         }
+        Ok(())
     }
 
     fn draw_line(
@@ -302,6 +934,14 @@ impl CoreStateImplementation for CoreStateEnum {
                 CoreStateEnum::Win32(s) => {
                     Ok(s.draw_line(drawing_context.win32(), x1, y1, x2, y2)?)
                 }
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => {
+                    Ok(s.draw_line(drawing_context.headless(), x1, y1, x2, y2)?)
+                }
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => {
+                    Ok(s.draw_line(drawing_context.wayland(), x1, y1, x2, y2)?)
+                }
             }
         }
     }
@@ -319,6 +959,14 @@ impl CoreStateImplementation for CoreStateEnum {
                 CoreStateEnum::Win32(s) => {
                     Ok(s.draw_rectangle(drawing_context.win32(), rectangle)?)
                 }
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => {
+                    Ok(s.draw_rectangle(drawing_context.headless(), rectangle)?)
+                }
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => {
+                    Ok(s.draw_rectangle(drawing_context.wayland(), rectangle)?)
+                }
             }
         }
     }
@@ -329,16 +977,107 @@ impl CoreStateImplementation for CoreStateEnum {
             CoreStateEnum::X11(s) => s.destroy_window(CoreWindowRef::x11(window)),
             #[cfg(windows)]
             CoreStateEnum::Win32(s) => s.destroy_window(CoreWindowRef::win32(window)),
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => s.destroy_window(CoreWindowRef::headless(window)),
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => s.destroy_window(CoreWindowRef::wayland(window)),
         }
     }
 
-    unsafe fn wait_for_events(&mut self, on_event: &mut unsafe fn(WWindCoreEvent)) {
+    fn get_waker(&self) -> Self::Waker {
+        match self {
+            #[cfg(x11)]
+            CoreStateEnum::X11(s) => WakerEnum::X11(s.get_waker()),
+            #[cfg(windows)]
+            CoreStateEnum::Win32(s) => WakerEnum::Win32(s.get_waker()),
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => WakerEnum::Headless(s.get_waker()),
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => WakerEnum::Wayland(s.get_waker()),
+        }
+    }
+
+    unsafe fn wait_for_events(
+        &mut self,
+        on_event: &mut unsafe fn(WWindCoreEvent),
+        timeout: Option<Duration>,
+    ) -> Result<bool, Self::Error> {
+        let handled = match self {
+            #[cfg(x11)]
+            CoreStateEnum::X11(s) => s.wait_for_events(on_event, timeout)?,
+            #[cfg(windows)]
+            CoreStateEnum::Win32(s) => s.wait_for_events(on_event, timeout)?,
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => s.wait_for_events(on_event, timeout)?,
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => s.wait_for_events(on_event, timeout)?,
+        };
+
+        Ok(handled)
+    }
+
+    fn set_selection(
+        &mut self,
+        selection: Selection,
+        contents: SelectionContents,
+    ) -> Result<(), Self::Error> {
         match self {
             #[cfg(x11)]
-            CoreStateEnum::X11(s) => s.wait_for_events(on_event),
+            CoreStateEnum::X11(s) => s.set_selection(selection, contents)?,
             #[cfg(windows)]
-            CoreStateEnum::Win32(s) => s.wait_for_events(on_event),
+            CoreStateEnum::Win32(s) => s.set_selection(selection, contents)?,
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => s.set_selection(selection, contents)?,
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => s.set_selection(selection, contents)?,
         }
+        Ok(())
+    }
+
+    fn get_selection(
+        &mut self,
+        selection: Selection,
+        mime_type: &str,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        let data = match self {
+            #[cfg(x11)]
+            CoreStateEnum::X11(s) => s.get_selection(selection, mime_type)?,
+            #[cfg(windows)]
+            CoreStateEnum::Win32(s) => s.get_selection(selection, mime_type)?,
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => s.get_selection(selection, mime_type)?,
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => s.get_selection(selection, mime_type)?,
+        };
+        Ok(data)
+    }
+
+    fn get_selection_types(&mut self, selection: Selection) -> Result<Vec<String>, Self::Error> {
+        let types = match self {
+            #[cfg(x11)]
+            CoreStateEnum::X11(s) => s.get_selection_types(selection)?,
+            #[cfg(windows)]
+            CoreStateEnum::Win32(s) => s.get_selection_types(selection)?,
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => s.get_selection_types(selection)?,
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => s.get_selection_types(selection)?,
+        };
+        Ok(types)
+    }
+
+    fn get_monitors(&self) -> Result<Vec<Monitor>, Self::Error> {
+        let monitors = match self {
+            #[cfg(x11)]
+            CoreStateEnum::X11(s) => s.get_monitors()?,
+            #[cfg(windows)]
+            CoreStateEnum::Win32(s) => s.get_monitors()?,
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => s.get_monitors()?,
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => s.get_monitors()?,
+        };
+        Ok(monitors)
     }
 
     fn flush(&mut self) -> Result<(), Self::Error> {
@@ -347,6 +1086,10 @@ impl CoreStateImplementation for CoreStateEnum {
             CoreStateEnum::X11(s) => s.flush()?,
             #[cfg(windows)]
             CoreStateEnum::Win32(s) => s.flush()?,
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => s.flush()?,
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => s.flush()?,
         }
         Ok(())
     }
@@ -362,28 +1105,57 @@ impl CoreStateImplementation for CoreStateEnum {
                 CoreStateEnum::Win32(s) => s.set_draw_color(drawing_context.win32(), color)?,
                 #[cfg(x11)]
                 CoreStateEnum::X11(s) => s.set_draw_color(drawing_context.x11(), color)?,
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => {
+                    s.set_draw_color(drawing_context.headless(), color)?
+                }
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.set_draw_color(drawing_context.wayland(), color)?,
             }
         }
         Ok(())
     }
 
+    #[cfg(raw_window_handle)]
+    fn raw_window_handle(&self, window: Self::Window) -> Result<RawWindowHandle, HandleError> {
+        unsafe {
+            match self {
+                #[cfg(x11)]
+                CoreStateEnum::X11(s) => s.raw_window_handle(window.x11()),
+                #[cfg(windows)]
+                CoreStateEnum::Win32(s) => s.raw_window_handle(window.win32()),
+                #[cfg(headless)]
+                CoreStateEnum::Headless(s) => s.raw_window_handle(window.headless()),
+                #[cfg(wayland)]
+                CoreStateEnum::Wayland(s) => s.raw_window_handle(window.wayland()),
+            }
+        }
+    }
+
+    #[cfg(raw_window_handle)]
+    fn raw_display_handle(&self) -> Result<RawDisplayHandle, HandleError> {
+        match self {
+            #[cfg(x11)]
+            CoreStateEnum::X11(s) => s.raw_display_handle(),
+            #[cfg(windows)]
+            CoreStateEnum::Win32(s) => s.raw_display_handle(),
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => s.raw_display_handle(),
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => s.raw_display_handle(),
+        }
+    }
+
     unsafe fn get_context(&mut self, window: Self::Window) -> Self::DrawingContext {
         match self {
             #[cfg(windows)]
             CoreStateEnum::Win32(s) => s.get_context(window.win32()).into(),
             #[cfg(x11)]
             CoreStateEnum::X11(s) => s.get_context(window.x11()).into(),
-        }
-    }
-
-    fn get_size(&self, window: Self::Window) -> (u16, u16) {
-        unsafe {
-            match self {
-                #[cfg(windows)]
-                CoreStateEnum::Win32(s) => s.get_size(window.win32()),
-                #[cfg(x11)]
-                CoreStateEnum::X11(s) => s.get_size(window.x11()),
-            }
+            #[cfg(headless)]
+            CoreStateEnum::Headless(s) => s.get_context(window.headless()).into(),
+            #[cfg(wayland)]
+            CoreStateEnum::Wayland(s) => s.get_context(window.wayland()).into(),
         }
     }
 }
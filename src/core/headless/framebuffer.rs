@@ -0,0 +1,85 @@
+use crate::{core::canvas::Canvas, Color};
+
+/// The contents of a headless window as 8-bit RGBA pixels, row by row. Pixels that haven't been
+/// drawn to are transparent black.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Framebuffer {
+    width: u16,
+    height: u16,
+    pixels: Vec<u8>,
+}
+
+impl Framebuffer {
+    pub(super) fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Gets the raw RGBA data
+    pub fn data(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Gets the color of a pixel, or `None` if it is outside of the framebuffer or hasn't been
+    /// drawn to
+    pub fn get_pixel(&self, x: u16, y: u16) -> Option<Color> {
+        let index = self.get_index(x, y)?;
+        let [red, green, blue, alpha] = self.pixels[index..index + 4] else {
+            unreachable!()
+        };
+
+        (alpha != 0).then_some(Color { red, green, blue })
+    }
+
+    /// Changes the size of the framebuffer. The part that is still inside of it is kept.
+    pub(super) fn resize(&mut self, width: u16, height: u16) {
+        let mut resized = Self::new(width, height);
+
+        let row_length = width.min(self.width) as usize * 4;
+
+        for y in 0..height.min(self.height) {
+            let old_start = y as usize * self.width as usize * 4;
+            let new_start = y as usize * width as usize * 4;
+
+            resized.pixels[new_start..new_start + row_length]
+                .copy_from_slice(&self.pixels[old_start..old_start + row_length]);
+        }
+
+        *self = resized;
+    }
+
+    fn get_index(&self, x: u16, y: u16) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some((y as usize * self.width as usize + x as usize) * 4)
+    }
+}
+
+impl Canvas for Framebuffer {
+    fn width(&self) -> u16 {
+        self.width
+    }
+
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn put_pixel(&mut self, x: u16, y: u16, color: Color) {
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+
+        self.pixels[index..index + 4].copy_from_slice(&[color.red, color.green, color.blue, 255]);
+    }
+}
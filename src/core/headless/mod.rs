@@ -0,0 +1,801 @@
+//! A backend that keeps every window in memory. Nothing is shown on screen; instead, events
+//! are injected and framebuffers are inspected through a [HeadlessHandle].
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    convert::Infallible,
+    rc::Rc,
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    clipboard::{Selection, SelectionContents},
+    input::{KeyEvent, MouseButtonEvent, MouseMoveEvent, ScrollEvent},
+    Color, CursorIcon, DragAction, DropEvent, DropPayload, Monitor, RectRegion, ResizeEdge,
+    RgbaImage, WindowBuilder, WindowState, WindowStateFlags,
+};
+#[cfg(raw_window_handle)]
+use raw_window_handle::{HandleError, RawDisplayHandle, RawWindowHandle};
+
+use super::{
+    canvas::Canvas, core_state_implementation::WWindCoreEvent, CoreStateImplementation, CoreWaker,
+};
+
+mod framebuffer;
+
+pub use framebuffer::Framebuffer;
+
+/// Identifies a window of the headless backend
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HeadlessWindowId(u32);
+
+/// An event that can be injected with [HeadlessHandle::send_event]. These are delivered to the
+/// same handlers as the events of the other backends.
+#[derive(Clone, Debug)]
+pub enum HeadlessEvent {
+    /// The user asked to close the window
+    Close,
+    /// Part of the window needs to be redrawn
+    Expose(RectRegion),
+    /// The window was resized. Its framebuffer is resized as well.
+    Resize(u16, u16),
+    /// The window was moved to the given position on the screen
+    Move(i16, i16),
+    Keydown(KeyEvent),
+    Keyup(KeyEvent),
+    TextInput(char),
+    MouseDown(MouseButtonEvent),
+    MouseUp(MouseButtonEvent),
+    MouseMove(MouseMoveEvent),
+    Scroll(ScrollEvent),
+    FocusChange(bool),
+    PointerEnter(MouseMoveEvent),
+    PointerLeave(MouseMoveEvent),
+    /// The window manager maximized, minimized or fullscreened the window, or undid that
+    StateChange(WindowStateFlags),
+    /// Something is dragged over the window. Only delivered if the window accepts drops.
+    DragOver(DropEvent),
+    /// What was dragged over the window left it without being dropped
+    DragLeave,
+    /// Something was dropped on the window. Only delivered if the window accepts drops.
+    Drop(DropEvent),
+    /// The drag that the window started is over, and the target did `action` with it
+    DragEnd(Option<DragAction>),
+}
+
+/// A drag that was started with [start_drag](crate::Window::start_drag). It lasts until a
+/// [HeadlessEvent::DragEnd] is sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeadlessDrag {
+    pub window: HeadlessWindowId,
+    pub payloads: Vec<DropPayload>,
+    /// The image and hotspot given to
+    /// [start_drag_with_image](crate::Window::start_drag_with_image)
+    pub image: Option<(RgbaImage, (u16, u16))>,
+}
+
+/// The mouse cursor of a headless window
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HeadlessCursor {
+    Icon(CursorIcon),
+    Custom {
+        image: RgbaImage,
+        hotspot: (u16, u16),
+    },
+}
+
+struct HeadlessWindow {
+    title: String,
+    visible: bool,
+    decorations: bool,
+    icons: Vec<RgbaImage>,
+    cursor: HeadlessCursor,
+    state: WindowStateFlags,
+    accepts_drops: bool,
+    framebuffer: Framebuffer,
+}
+
+/// The part of the state that is shared with [HeadlessHandle]s
+#[derive(Default)]
+struct Shared {
+    windows: HashMap<HeadlessWindowId, HeadlessWindow>,
+    /// Windows in the order that they were created
+    window_order: Vec<HeadlessWindowId>,
+    events: VecDeque<(HeadlessWindowId, HeadlessEvent)>,
+    next_window_id: u32,
+    /// The window with keyboard focus
+    focus: Option<HeadlessWindowId>,
+    selections: HashMap<Selection, SelectionContents>,
+    drag: Option<HeadlessDrag>,
+    monitors: Vec<Monitor>,
+    /// Whether the monitors were set since the event loop last ran
+    monitors_changed: bool,
+}
+
+impl Shared {
+    fn set_selection(&mut self, selection: Selection, contents: SelectionContents) {
+        if contents.is_empty() {
+            self.selections.remove(&selection);
+        } else {
+            self.selections.insert(selection, contents);
+        }
+    }
+}
+
+/// Lets tests control the headless backend. Can be obtained with
+/// [headless_handle](crate::WWindInitState::headless_handle).
+///
+/// # Example
+/// ```rust
+/// use wwind::{Color, HeadlessEvent, PumpStatus, RectRegion, WWindInitState, WWindInstance};
+/// use std::time::Duration;
+///
+/// let mut instance = WWindInstance::new_headless(|state: &mut WWindInitState| {
+///     let mut window = state.add_window(0, 0, 10, 10, "test").unwrap();
+///
+///     window.on_redraw(|_, window, region| {
+///         let mut context = window.get_drawing_context();
+///
+///         context.set_draw_color(Color::from_hex(0xff0000)).unwrap();
+///         context.draw_rectangle(region).unwrap();
+///     });
+/// })
+/// .unwrap();
+///
+/// // Runs on_init and draws the window for the first time
+/// instance.pump_events(Some(Duration::ZERO)).unwrap();
+///
+/// let handle = instance.headless_handle().unwrap();
+/// let window = handle.windows()[0];
+///
+/// let pixel = handle.framebuffer(window).unwrap().get_pixel(5, 5).unwrap();
+/// assert_eq!((pixel.red, pixel.green, pixel.blue), (255, 0, 0));
+///
+/// handle.send_event(window, HeadlessEvent::Close);
+/// let status = instance.pump_events(Some(Duration::ZERO)).unwrap();
+/// assert_eq!(status, PumpStatus::Exit);
+/// ```
+#[derive(Clone)]
+pub struct HeadlessHandle {
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl HeadlessHandle {
+    /// Gets the windows that currently exist, in the order that they were created in
+    pub fn windows(&self) -> Vec<HeadlessWindowId> {
+        self.shared.borrow().window_order.clone()
+    }
+
+    /// Queues an event for a window. It is handled the next time the event loop runs.
+    pub fn send_event(&self, window: HeadlessWindowId, event: HeadlessEvent) {
+        self.shared.borrow_mut().events.push_back((window, event));
+    }
+
+    /// Gets a copy of what has been drawn on a window
+    pub fn framebuffer(&self, window: HeadlessWindowId) -> Option<Framebuffer> {
+        Some(
+            self.shared
+                .borrow()
+                .windows
+                .get(&window)?
+                .framebuffer
+                .clone(),
+        )
+    }
+
+    pub fn title(&self, window: HeadlessWindowId) -> Option<String> {
+        Some(self.shared.borrow().windows.get(&window)?.title.clone())
+    }
+
+    /// Gets whether a window would be shown on screen
+    pub fn is_visible(&self, window: HeadlessWindowId) -> Option<bool> {
+        Some(self.shared.borrow().windows.get(&window)?.visible)
+    }
+
+    /// Gets whether a window would have a title bar and border
+    pub fn has_decorations(&self, window: HeadlessWindowId) -> Option<bool> {
+        Some(self.shared.borrow().windows.get(&window)?.decorations)
+    }
+
+    /// Gets the icons that were set with [set_icon](crate::Window::set_icon)
+    pub fn icons(&self, window: HeadlessWindowId) -> Option<Vec<RgbaImage>> {
+        Some(self.shared.borrow().windows.get(&window)?.icons.clone())
+    }
+
+    pub fn cursor(&self, window: HeadlessWindowId) -> Option<HeadlessCursor> {
+        Some(self.shared.borrow().windows.get(&window)?.cursor.clone())
+    }
+
+    /// Gets whether a window is maximized, minimized or fullscreen. This is updated as soon as
+    /// it is requested, before the window is told about it.
+    pub fn state(&self, window: HeadlessWindowId) -> Option<WindowStateFlags> {
+        Some(self.shared.borrow().windows.get(&window)?.state)
+    }
+
+    /// Gets whether [on_drop](crate::Window::on_drop) or one of the other drag callbacks was
+    /// set on a window
+    pub fn accepts_drops(&self, window: HeadlessWindowId) -> Option<bool> {
+        Some(self.shared.borrow().windows.get(&window)?.accepts_drops)
+    }
+
+    /// Gets the window that has keyboard focus
+    pub fn focused_window(&self) -> Option<HeadlessWindowId> {
+        self.shared.borrow().focus
+    }
+
+    /// Gets the drag that is in progress
+    pub fn drag(&self) -> Option<HeadlessDrag> {
+        self.shared.borrow().drag.clone()
+    }
+
+    /// Acts like monitors being connected, disconnected or changed. There are none until this
+    /// is called.
+    pub fn set_monitors(&self, monitors: &[Monitor]) {
+        let mut shared = self.shared.borrow_mut();
+
+        shared.monitors = monitors.to_vec();
+        shared.monitors_changed = true;
+    }
+
+    /// Gets the data that was put in a selection as `mime_type`
+    pub fn selection(&self, selection: Selection, mime_type: &str) -> Option<Vec<u8>> {
+        let shared = self.shared.borrow();
+
+        Some(shared.selections.get(&selection)?.get(mime_type)?.to_vec())
+    }
+
+    /// Acts like another program putting data in a selection. An empty slice clears it.
+    pub fn set_selection(&self, selection: Selection, formats: &[(&str, &[u8])]) {
+        let contents = SelectionContents::new(formats);
+
+        self.shared.borrow_mut().set_selection(selection, contents);
+    }
+}
+
+/// Wakes up the headless event loop from other threads
+#[derive(Clone, Default)]
+pub struct HeadlessWaker(Arc<(Mutex<bool>, Condvar)>);
+
+impl CoreWaker for HeadlessWaker {
+    fn wake(&self) {
+        let (woken, condvar) = &*self.0;
+
+        *woken.lock().unwrap_or_else(|err| err.into_inner()) = true;
+        condvar.notify_one();
+    }
+}
+
+impl HeadlessWaker {
+    /// Blocks until [wake](CoreWaker::wake) is called or `timeout` runs out
+    fn wait(&self, timeout: Option<Duration>) {
+        let (woken, condvar) = &*self.0;
+
+        let woken = woken.lock().unwrap_or_else(|err| err.into_inner());
+
+        let mut woken = match timeout {
+            Some(timeout) => {
+                condvar
+                    .wait_timeout_while(woken, timeout, |woken| !*woken)
+                    .unwrap_or_else(|err| err.into_inner())
+                    .0
+            }
+            None => condvar
+                .wait_while(woken, |woken| !*woken)
+                .unwrap_or_else(|err| err.into_inner()),
+        };
+
+        *woken = false;
+    }
+}
+
+pub struct HeadlessState {
+    shared: Rc<RefCell<Shared>>,
+    waker: HeadlessWaker,
+    draw_color: Color,
+}
+
+impl HeadlessState {
+    pub fn get_handle(&self) -> HeadlessHandle {
+        HeadlessHandle {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl HeadlessState {
+    /// Acts like a window manager that allows every state change. Like a real one, it restores
+    /// a minimized window when it is asked to change anything else.
+    fn change_state(
+        &mut self,
+        window: HeadlessWindowId,
+        change: impl FnOnce(&mut WindowStateFlags),
+    ) {
+        let mut shared = self.shared.borrow_mut();
+
+        let Some(headless_window) = shared.windows.get_mut(&window) else {
+            return;
+        };
+
+        headless_window.state.remove(WindowStateFlags::MINIMIZED);
+        change(&mut headless_window.state);
+        let state = headless_window.state;
+
+        shared
+            .events
+            .push_back((window, HeadlessEvent::StateChange(state)));
+    }
+}
+
+impl CoreStateImplementation for HeadlessState {
+    type Error = Infallible;
+    type Window = HeadlessWindowId;
+    type DrawingContext = HeadlessWindowId;
+    type Waker = HeadlessWaker;
+
+    unsafe fn new() -> Result<Self, Self::Error> {
+        Ok(Self {
+            shared: Default::default(),
+            waker: Default::default(),
+            draw_color: Color::from_hex(0),
+        })
+    }
+
+    fn set_window_position(
+        &mut self,
+        window: Self::Window,
+        x: i16,
+        y: i16,
+    ) -> Result<(), Self::Error> {
+        // Act like a window manager that allows everything
+        self.shared
+            .borrow_mut()
+            .events
+            .push_back((window, HeadlessEvent::Move(x, y)));
+
+        Ok(())
+    }
+
+    fn set_window_size(
+        &mut self,
+        window: Self::Window,
+        width: u16,
+        height: u16,
+    ) -> Result<(), Self::Error> {
+        self.shared
+            .borrow_mut()
+            .events
+            .push_back((window, HeadlessEvent::Resize(width, height)));
+
+        Ok(())
+    }
+
+    fn set_window_visible(
+        &mut self,
+        window: Self::Window,
+        visible: bool,
+    ) -> Result<(), Self::Error> {
+        let mut shared = self.shared.borrow_mut();
+
+        let Some(headless_window) = shared.windows.get_mut(&window) else {
+            return Ok(());
+        };
+
+        let shown = visible && !headless_window.visible;
+        headless_window.visible = visible;
+
+        if shown {
+            let region = RectRegion {
+                x: 0,
+                y: 0,
+                width: headless_window.framebuffer.width(),
+                height: headless_window.framebuffer.height(),
+            };
+
+            shared
+                .events
+                .push_back((window, HeadlessEvent::Expose(region)));
+        }
+
+        Ok(())
+    }
+
+    fn focus_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        let mut shared = self.shared.borrow_mut();
+
+        if shared.focus == Some(window) {
+            return Ok(());
+        }
+
+        if let Some(old_focus) = shared.focus.replace(window) {
+            shared
+                .events
+                .push_back((old_focus, HeadlessEvent::FocusChange(false)));
+        }
+
+        shared
+            .events
+            .push_back((window, HeadlessEvent::FocusChange(true)));
+
+        Ok(())
+    }
+
+    /// There is no stacking order without a screen
+    fn raise_window(&mut self, _window: Self::Window) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_window_fullscreen(
+        &mut self,
+        window: Self::Window,
+        fullscreen: bool,
+    ) -> Result<(), Self::Error> {
+        self.change_state(window, |state| {
+            state.set(WindowStateFlags::FULLSCREEN, fullscreen)
+        });
+
+        Ok(())
+    }
+
+    fn set_window_maximized(
+        &mut self,
+        window: Self::Window,
+        maximized: bool,
+    ) -> Result<(), Self::Error> {
+        self.change_state(window, |state| {
+            state.set(WindowStateFlags::MAXIMIZED, maximized)
+        });
+
+        Ok(())
+    }
+
+    fn minimize_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        self.change_state(window, |state| state.insert(WindowStateFlags::MINIMIZED));
+
+        Ok(())
+    }
+
+    fn set_window_decorations(
+        &mut self,
+        window: Self::Window,
+        decorations: bool,
+    ) -> Result<(), Self::Error> {
+        if let Some(window) = self.shared.borrow_mut().windows.get_mut(&window) {
+            window.decorations = decorations;
+        }
+
+        Ok(())
+    }
+
+    fn set_window_icon(
+        &mut self,
+        window: Self::Window,
+        icons: &[RgbaImage],
+    ) -> Result<(), Self::Error> {
+        if let Some(window) = self.shared.borrow_mut().windows.get_mut(&window) {
+            window.icons = icons.to_vec();
+        }
+
+        Ok(())
+    }
+
+    fn set_window_cursor(
+        &mut self,
+        window: Self::Window,
+        cursor: CursorIcon,
+    ) -> Result<(), Self::Error> {
+        if let Some(window) = self.shared.borrow_mut().windows.get_mut(&window) {
+            window.cursor = HeadlessCursor::Icon(cursor);
+        }
+
+        Ok(())
+    }
+
+    fn set_window_custom_cursor(
+        &mut self,
+        window: Self::Window,
+        image: &RgbaImage,
+        hotspot: (u16, u16),
+    ) -> Result<(), Self::Error> {
+        if let Some(window) = self.shared.borrow_mut().windows.get_mut(&window) {
+            window.cursor = HeadlessCursor::Custom {
+                image: image.clone(),
+                hotspot,
+            };
+        }
+
+        Ok(())
+    }
+
+    /// There is no pointer to follow
+    fn start_window_drag(
+        &mut self,
+        _window: Self::Window,
+        _edge: Option<ResizeEdge>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn start_data_drag(
+        &mut self,
+        window: Self::Window,
+        payloads: &[DropPayload],
+        image: Option<(&RgbaImage, (u16, u16))>,
+    ) -> Result<(), Self::Error> {
+        self.shared.borrow_mut().drag = Some(HeadlessDrag {
+            window,
+            payloads: payloads.to_vec(),
+            image: image.map(|(image, hotspot)| (image.clone(), hotspot)),
+        });
+
+        Ok(())
+    }
+
+    fn add_window(&mut self, builder: &WindowBuilder) -> Result<Self::Window, Self::Error> {
+        let mut shared = self.shared.borrow_mut();
+        let (width, height) = builder.clamped_size();
+
+        let window = HeadlessWindowId(shared.next_window_id);
+        shared.next_window_id += 1;
+
+        let state = match builder.state {
+            WindowState::Normal => WindowStateFlags::NONE,
+            WindowState::Maximized => WindowStateFlags::MAXIMIZED,
+            WindowState::Minimized => WindowStateFlags::MINIMIZED,
+            WindowState::Fullscreen => WindowStateFlags::FULLSCREEN,
+        };
+
+        shared.windows.insert(
+            window,
+            HeadlessWindow {
+                title: builder.title.clone(),
+                visible: builder.visible,
+                decorations: builder.decorations,
+                icons: Vec::new(),
+                cursor: HeadlessCursor::Icon(CursorIcon::Default),
+                state,
+                accepts_drops: false,
+                framebuffer: Framebuffer::new(width, height),
+            },
+        );
+        shared.window_order.push(window);
+
+        if !state.is_empty() {
+            shared
+                .events
+                .push_back((window, HeadlessEvent::StateChange(state)));
+        }
+
+        if !builder.visible {
+            return Ok(window);
+        }
+
+        // Like a real window system, ask for the new window to be drawn once it is shown
+        let region = RectRegion {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        shared
+            .events
+            .push_back((window, HeadlessEvent::Expose(region)));
+
+        Ok(window)
+    }
+
+    fn set_window_title(&mut self, window: Self::Window, title: &str) -> Result<(), Self::Error> {
+        if let Some(window) = self.shared.borrow_mut().windows.get_mut(&window) {
+            window.title = title.to_owned();
+        }
+
+        Ok(())
+    }
+
+    fn accept_window_drops(&mut self, window: Self::Window) {
+        if let Some(window) = self.shared.borrow_mut().windows.get_mut(&window) {
+            window.accepts_drops = true;
+        }
+    }
+
+    fn set_selection(
+        &mut self,
+        selection: Selection,
+        contents: SelectionContents,
+    ) -> Result<(), Self::Error> {
+        self.shared.borrow_mut().set_selection(selection, contents);
+
+        Ok(())
+    }
+
+    fn get_selection(
+        &mut self,
+        selection: Selection,
+        mime_type: &str,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        let shared = self.shared.borrow();
+
+        Ok(shared
+            .selections
+            .get(&selection)
+            .and_then(|contents| contents.get(mime_type))
+            .map(<[u8]>::to_vec))
+    }
+
+    fn get_selection_types(&mut self, selection: Selection) -> Result<Vec<String>, Self::Error> {
+        let shared = self.shared.borrow();
+
+        Ok(shared
+            .selections
+            .get(&selection)
+            .map(SelectionContents::types)
+            .unwrap_or_default())
+    }
+
+    fn get_monitors(&self) -> Result<Vec<Monitor>, Self::Error> {
+        Ok(self.shared.borrow().monitors.clone())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    unsafe fn destroy_window(&mut self, window: Self::Window) {
+        let mut shared = self.shared.borrow_mut();
+
+        shared.windows.remove(&window);
+        shared.window_order.retain(|&id| id != window);
+        if shared.focus == Some(window) {
+            shared.focus = None;
+        }
+        shared.events.retain(|&(id, _)| id != window);
+    }
+
+    fn get_waker(&self) -> Self::Waker {
+        self.waker.clone()
+    }
+
+    unsafe fn wait_for_events(
+        &mut self,
+        on_event: &mut unsafe fn(WWindCoreEvent),
+        timeout: Option<Duration>,
+    ) -> Result<bool, Self::Error> {
+        if std::mem::take(&mut self.shared.borrow_mut().monitors_changed) {
+            on_event(WWindCoreEvent::MonitorsChanged);
+            return Ok(true);
+        }
+
+        // Events can only be injected from this thread, so if there are none, only a waker
+        // can end the wait
+        let Some((window, event)) = self.shared.borrow_mut().events.pop_front() else {
+            self.waker.wait(timeout);
+            return Ok(false);
+        };
+
+        let Some(accepts_drops) = self
+            .shared
+            .borrow()
+            .windows
+            .get(&window)
+            .map(|window| window.accepts_drops)
+        else {
+            return Ok(true);
+        };
+
+        let window_ref = window.into();
+
+        let core_event = match event {
+            HeadlessEvent::Close => WWindCoreEvent::CloseWindow(window_ref),
+            HeadlessEvent::Expose(region) => WWindCoreEvent::Expose(window_ref, region),
+            HeadlessEvent::Resize(width, height) => {
+                if let Some(window) = self.shared.borrow_mut().windows.get_mut(&window) {
+                    window.framebuffer.resize(width, height);
+                }
+
+                WWindCoreEvent::Resize(window_ref, width, height)
+            }
+            HeadlessEvent::Move(x, y) => WWindCoreEvent::Move(window_ref, x, y),
+            HeadlessEvent::Keydown(event) => WWindCoreEvent::Keydown(window_ref, event),
+            HeadlessEvent::Keyup(event) => WWindCoreEvent::Keyup(window_ref, event),
+            HeadlessEvent::TextInput(character) => WWindCoreEvent::TextInput(window_ref, character),
+            HeadlessEvent::MouseDown(event) => WWindCoreEvent::MouseDown(window_ref, event),
+            HeadlessEvent::MouseUp(event) => WWindCoreEvent::MouseUp(window_ref, event),
+            HeadlessEvent::MouseMove(event) => WWindCoreEvent::MouseMove(window_ref, event),
+            HeadlessEvent::Scroll(event) => WWindCoreEvent::Scroll(window_ref, event),
+            HeadlessEvent::FocusChange(focused) => {
+                let mut shared = self.shared.borrow_mut();
+
+                if focused {
+                    shared.focus = Some(window);
+                } else if shared.focus == Some(window) {
+                    shared.focus = None;
+                }
+
+                WWindCoreEvent::FocusChange(window_ref, focused)
+            }
+            HeadlessEvent::PointerEnter(event) => WWindCoreEvent::PointerEnter(window_ref, event),
+            HeadlessEvent::PointerLeave(event) => WWindCoreEvent::PointerLeave(window_ref, event),
+            HeadlessEvent::StateChange(state) => {
+                if let Some(window) = self.shared.borrow_mut().windows.get_mut(&window) {
+                    window.state = state;
+                }
+
+                WWindCoreEvent::StateChange(window_ref, state)
+            }
+            HeadlessEvent::DragOver(_) | HeadlessEvent::DragLeave | HeadlessEvent::Drop(_)
+                if !accepts_drops =>
+            {
+                return Ok(true);
+            }
+            HeadlessEvent::DragOver(event) => WWindCoreEvent::DragOver(window_ref, event),
+            HeadlessEvent::DragLeave => WWindCoreEvent::DragLeave(window_ref),
+            HeadlessEvent::Drop(event) => WWindCoreEvent::DragDrop(window_ref, event),
+            HeadlessEvent::DragEnd(action) => {
+                self.shared.borrow_mut().drag = None;
+
+                WWindCoreEvent::DragEnd(window_ref, action)
+            }
+        };
+
+        on_event(core_event);
+
+        Ok(true)
+    }
+
+    /// Headless windows only exist in memory, so there is nothing to hand out
+    #[cfg(raw_window_handle)]
+    fn raw_window_handle(&self, _window: Self::Window) -> Result<RawWindowHandle, HandleError> {
+        Err(HandleError::NotSupported)
+    }
+
+    #[cfg(raw_window_handle)]
+    fn raw_display_handle(&self) -> Result<RawDisplayHandle, HandleError> {
+        Err(HandleError::NotSupported)
+    }
+
+    unsafe fn get_context(&mut self, window: Self::Window) -> Self::DrawingContext {
+        window
+    }
+
+    fn draw_line(
+        &mut self,
+        drawing_context: Self::DrawingContext,
+        x1: u16,
+        y1: u16,
+        x2: u16,
+        y2: u16,
+    ) -> Result<(), Self::Error> {
+        if let Some(window) = self.shared.borrow_mut().windows.get_mut(&drawing_context) {
+            window
+                .framebuffer
+                .draw_line(x1, y1, x2, y2, self.draw_color);
+        }
+
+        Ok(())
+    }
+
+    fn draw_rectangle(
+        &mut self,
+        drawing_context: Self::DrawingContext,
+        rectangle: RectRegion,
+    ) -> Result<(), Self::Error> {
+        if let Some(window) = self.shared.borrow_mut().windows.get_mut(&drawing_context) {
+            window
+                .framebuffer
+                .fill_rectangle(rectangle, self.draw_color);
+        }
+
+        Ok(())
+    }
+
+    fn set_draw_color(
+        &mut self,
+        _context: Self::DrawingContext,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        self.draw_color = color;
+
+        Ok(())
+    }
+}
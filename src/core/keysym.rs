@@ -0,0 +1,335 @@
+use crate::input::Key;
+
+use super::keysym_tables::{DEAD_KEY_COMPOSITIONS, LEGACY_KEYSYMS};
+
+/// Converts the keysym that a key produces without any modifiers into a [Key]
+pub fn keysym_to_key(keysym: u32) -> Key {
+    const LETTERS: [Key; 26] = [
+        Key::A,
+        Key::B,
+        Key::C,
+        Key::D,
+        Key::E,
+        Key::F,
+        Key::G,
+        Key::H,
+        Key::I,
+        Key::J,
+        Key::K,
+        Key::L,
+        Key::M,
+        Key::N,
+        Key::O,
+        Key::P,
+        Key::Q,
+        Key::R,
+        Key::S,
+        Key::T,
+        Key::U,
+        Key::V,
+        Key::W,
+        Key::X,
+        Key::Y,
+        Key::Z,
+    ];
+    const DIGITS: [Key; 10] = [
+        Key::Digit0,
+        Key::Digit1,
+        Key::Digit2,
+        Key::Digit3,
+        Key::Digit4,
+        Key::Digit5,
+        Key::Digit6,
+        Key::Digit7,
+        Key::Digit8,
+        Key::Digit9,
+    ];
+    const NUMPAD_DIGITS: [Key; 10] = [
+        Key::Numpad0,
+        Key::Numpad1,
+        Key::Numpad2,
+        Key::Numpad3,
+        Key::Numpad4,
+        Key::Numpad5,
+        Key::Numpad6,
+        Key::Numpad7,
+        Key::Numpad8,
+        Key::Numpad9,
+    ];
+    const FUNCTION_KEYS: [Key; 24] = [
+        Key::F1,
+        Key::F2,
+        Key::F3,
+        Key::F4,
+        Key::F5,
+        Key::F6,
+        Key::F7,
+        Key::F8,
+        Key::F9,
+        Key::F10,
+        Key::F11,
+        Key::F12,
+        Key::F13,
+        Key::F14,
+        Key::F15,
+        Key::F16,
+        Key::F17,
+        Key::F18,
+        Key::F19,
+        Key::F20,
+        Key::F21,
+        Key::F22,
+        Key::F23,
+        Key::F24,
+    ];
+
+    match keysym {
+        0x61..=0x7a => LETTERS[(keysym - 0x61) as usize],
+        0x41..=0x5a => LETTERS[(keysym - 0x41) as usize],
+        0x30..=0x39 => DIGITS[(keysym - 0x30) as usize],
+        0xffb0..=0xffb9 => NUMPAD_DIGITS[(keysym - 0xffb0) as usize],
+        0xffbe..=0xffd5 => FUNCTION_KEYS[(keysym - 0xffbe) as usize],
+
+        0x20 => Key::Space,
+        0x27 => Key::Apostrophe,
+        0x2c => Key::Comma,
+        0x2d => Key::Minus,
+        0x2e => Key::Period,
+        0x2f => Key::Slash,
+        0x3b => Key::Semicolon,
+        0x3d => Key::Equals,
+        0x5b => Key::LeftBracket,
+        0x5c => Key::Backslash,
+        0x5d => Key::RightBracket,
+        0x60 => Key::Grave,
+
+        0xff08 => Key::Backspace,
+        0xff09 | 0xfe20 => Key::Tab,
+        0xff0d => Key::Enter,
+        0xff13 => Key::Pause,
+        0xff14 => Key::ScrollLock,
+        0xff1b => Key::Escape,
+        0xff50 => Key::Home,
+        0xff51 => Key::Left,
+        0xff52 => Key::Up,
+        0xff53 => Key::Right,
+        0xff54 => Key::Down,
+        0xff55 => Key::PageUp,
+        0xff56 => Key::PageDown,
+        0xff57 => Key::End,
+        0xff61 => Key::PrintScreen,
+        0xff63 => Key::Insert,
+        0xff67 => Key::Menu,
+        0xff7f => Key::NumLock,
+        0xffff => Key::Delete,
+
+        // Keypad keys with num lock off
+        0xff8d => Key::NumpadEnter,
+        0xff95 => Key::Numpad7,
+        0xff96 => Key::Numpad4,
+        0xff97 => Key::Numpad8,
+        0xff98 => Key::Numpad6,
+        0xff99 => Key::Numpad2,
+        0xff9a => Key::Numpad9,
+        0xff9b => Key::Numpad3,
+        0xff9c => Key::Numpad1,
+        0xff9d => Key::Numpad5,
+        0xff9e => Key::Numpad0,
+        0xff9f => Key::NumpadDecimal,
+        0xffaa => Key::NumpadMultiply,
+        0xffab => Key::NumpadAdd,
+        0xffad => Key::NumpadSubtract,
+        0xffae => Key::NumpadDecimal,
+        0xffaf => Key::NumpadDivide,
+
+        0xffe1 => Key::LeftShift,
+        0xffe2 => Key::RightShift,
+        0xffe3 => Key::LeftControl,
+        0xffe4 => Key::RightControl,
+        0xffe5 => Key::CapsLock,
+        0xffe7 | 0xffe9 => Key::LeftAlt,
+        0xffe8 | 0xffea | 0xfe03 => Key::RightAlt,
+        0xffeb => Key::LeftSuper,
+        0xffec => Key::RightSuper,
+
+        _ => Key::Unknown,
+    }
+}
+
+/// Gets the character that a keysym types. Returns `None` for control characters.
+pub fn keysym_to_char(keysym: u32) -> Option<char> {
+    let code_point = match keysym {
+        0x20..=0x7e | 0xa0..=0xff => keysym,
+        // Keysyms that directly encode a Unicode code point
+        0x0100_0000..=0x0110_ffff => keysym - 0x0100_0000,
+
+        0xff80 => ' ' as u32,
+        0xffaa => '*' as u32,
+        0xffab => '+' as u32,
+        0xffac => ',' as u32,
+        0xffad => '-' as u32,
+        0xffae => '.' as u32,
+        0xffaf => '/' as u32,
+        0xffb0..=0xffb9 => '0' as u32 + (keysym - 0xffb0),
+        0xffbd => '=' as u32,
+
+        _ => {
+            let keysym = u16::try_from(keysym).ok()?;
+            let index = LEGACY_KEYSYMS
+                .binary_search_by_key(&keysym, |&(keysym, _)| keysym)
+                .ok()?;
+
+            LEGACY_KEYSYMS[index].1 as u32
+        }
+    };
+
+    char::from_u32(code_point).filter(|c| !c.is_control())
+}
+
+/// Combines dead keys with the key that follows them
+#[derive(Default)]
+pub struct Composer {
+    pending_dead_key: Option<u32>,
+}
+
+impl Composer {
+    /// Feeds the keysym of a pressed key into the composer and returns the characters that
+    /// should be typed. A dead key that doesn't combine with the next key is typed on its own
+    /// before it.
+    pub fn feed(&mut self, keysym: u32) -> impl Iterator<Item = char> {
+        let is_dead_key = (0xfe50..=0xfe8f).contains(&keysym);
+
+        let character = keysym_to_char(keysym);
+
+        let characters = if !is_dead_key && character.is_none() {
+            // Modifiers and other keys that don't type anything shouldn't cancel a dead key
+            [None, None]
+        } else {
+            match self.pending_dead_key.take() {
+                Some(dead_key) => match compose(dead_key, keysym) {
+                    Some(composed) => [Some(composed), None],
+                    // Pressing a dead key twice types it
+                    None if keysym == dead_key => [get_spacing_character(dead_key), None],
+                    None if is_dead_key => {
+                        self.pending_dead_key = Some(keysym);
+                        [get_spacing_character(dead_key), None]
+                    }
+                    None => [get_spacing_character(dead_key), character],
+                },
+                None if is_dead_key => {
+                    self.pending_dead_key = Some(keysym);
+                    [None, None]
+                }
+                None => [character, None],
+            }
+        };
+
+        characters.into_iter().flatten()
+    }
+}
+
+/// Gets the character that a dead key types on its own, like `´` for `dead_acute`
+fn get_spacing_character(dead_key: u32) -> Option<char> {
+    // Pressing the dead key twice gives the spacing accent. Some dead keys only have a
+    // composition with space, which gives the accent or a similar ASCII character.
+    compose(dead_key, dead_key).or_else(|| compose(dead_key, ' ' as u32))
+}
+
+fn compose(dead_key: u32, keysym: u32) -> Option<char> {
+    let dead_key = u16::try_from(dead_key).ok()?;
+
+    DEAD_KEY_COMPOSITIONS
+        .binary_search_by_key(&(dead_key, keysym), |&(dead_key, keysym, _)| {
+            (dead_key, keysym)
+        })
+        .ok()
+        .map(|index| DEAD_KEY_COMPOSITIONS[index].2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keysym_to_char, Composer, DEAD_KEY_COMPOSITIONS, LEGACY_KEYSYMS};
+
+    const DEAD_GRAVE: u32 = 0xfe50;
+    const DEAD_ACUTE: u32 = 0xfe51;
+    const DEAD_CARON: u32 = 0xfe5a;
+    const SHIFT_L: u32 = 0xffe1;
+
+    fn feed(composer: &mut Composer, keysym: u32) -> String {
+        composer.feed(keysym).collect()
+    }
+
+    #[test]
+    fn tables_are_sorted() {
+        // Looked up with binary searches
+        assert!(LEGACY_KEYSYMS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(DEAD_KEY_COMPOSITIONS
+            .windows(2)
+            .all(|pair| (pair[0].0, pair[0].1) < (pair[1].0, pair[1].1)));
+    }
+
+    #[test]
+    fn characters() {
+        assert_eq!(keysym_to_char('a' as u32), Some('a'));
+        assert_eq!(keysym_to_char(0xe9), Some('é'));
+        // A legacy keysym, Cyrillic_ef
+        assert_eq!(keysym_to_char(0x6c6), Some('ф'));
+        assert_eq!(keysym_to_char(0x0100_20ac), Some('€'));
+        // Keypad 7
+        assert_eq!(keysym_to_char(0xffb7), Some('7'));
+        // Return and Shift_L
+        assert_eq!(keysym_to_char(0xff0d), None);
+        assert_eq!(keysym_to_char(SHIFT_L), None);
+    }
+
+    #[test]
+    fn dead_keys_combine_with_the_next_key() {
+        let mut composer = Composer::default();
+
+        assert_eq!(feed(&mut composer, DEAD_ACUTE), "");
+        assert_eq!(feed(&mut composer, 'e' as u32), "é");
+        assert_eq!(feed(&mut composer, 'e' as u32), "e");
+    }
+
+    #[test]
+    fn modifiers_dont_cancel_dead_keys() {
+        let mut composer = Composer::default();
+
+        feed(&mut composer, DEAD_ACUTE);
+        assert_eq!(feed(&mut composer, SHIFT_L), "");
+        assert_eq!(feed(&mut composer, 'E' as u32), "É");
+    }
+
+    #[test]
+    fn failed_compositions_type_the_dead_key_first() {
+        let mut composer = Composer::default();
+
+        assert_eq!(feed(&mut composer, DEAD_ACUTE), "");
+        assert_eq!(feed(&mut composer, 'q' as u32), "´q");
+        assert_eq!(feed(&mut composer, 'q' as u32), "q");
+    }
+
+    #[test]
+    fn double_dead_keys_type_the_accent() {
+        let mut composer = Composer::default();
+
+        feed(&mut composer, DEAD_ACUTE);
+        assert_eq!(feed(&mut composer, DEAD_ACUTE), "´");
+
+        // Caron only has a composition with space
+        feed(&mut composer, DEAD_CARON);
+        assert_eq!(feed(&mut composer, DEAD_CARON), "ˇ");
+
+        // Nothing is left pending
+        assert_eq!(feed(&mut composer, 'e' as u32), "e");
+    }
+
+    #[test]
+    fn a_different_dead_key_replaces_the_pending_one() {
+        let mut composer = Composer::default();
+
+        feed(&mut composer, DEAD_CARON);
+        assert_eq!(feed(&mut composer, DEAD_GRAVE), "ˇ");
+        assert_eq!(feed(&mut composer, 'a' as u32), "à");
+    }
+}
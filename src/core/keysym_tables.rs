@@ -0,0 +1,1988 @@
+//! Lookup tables for turning keysyms into text.
+//!
+//! Generated from `X11/keysymdef.h` and the `en_US.UTF-8` Compose file of libX11.
+
+/// Keysyms outside of the Latin-1 and Unicode ranges, paired with the Unicode code point
+/// they produce. Sorted by keysym.
+pub static LEGACY_KEYSYMS: [(u16, u16); 762] = [
+    (0x01a1, 0x0104),
+    (0x01a2, 0x02d8),
+    (0x01a3, 0x0141),
+    (0x01a5, 0x013d),
+    (0x01a6, 0x015a),
+    (0x01a9, 0x0160),
+    (0x01aa, 0x015e),
+    (0x01ab, 0x0164),
+    (0x01ac, 0x0179),
+    (0x01ae, 0x017d),
+    (0x01af, 0x017b),
+    (0x01b1, 0x0105),
+    (0x01b2, 0x02db),
+    (0x01b3, 0x0142),
+    (0x01b5, 0x013e),
+    (0x01b6, 0x015b),
+    (0x01b7, 0x02c7),
+    (0x01b9, 0x0161),
+    (0x01ba, 0x015f),
+    (0x01bb, 0x0165),
+    (0x01bc, 0x017a),
+    (0x01bd, 0x02dd),
+    (0x01be, 0x017e),
+    (0x01bf, 0x017c),
+    (0x01c0, 0x0154),
+    (0x01c3, 0x0102),
+    (0x01c5, 0x0139),
+    (0x01c6, 0x0106),
+    (0x01c8, 0x010c),
+    (0x01ca, 0x0118),
+    (0x01cc, 0x011a),
+    (0x01cf, 0x010e),
+    (0x01d0, 0x0110),
+    (0x01d1, 0x0143),
+    (0x01d2, 0x0147),
+    (0x01d5, 0x0150),
+    (0x01d8, 0x0158),
+    (0x01d9, 0x016e),
+    (0x01db, 0x0170),
+    (0x01de, 0x0162),
+    (0x01e0, 0x0155),
+    (0x01e3, 0x0103),
+    (0x01e5, 0x013a),
+    (0x01e6, 0x0107),
+    (0x01e8, 0x010d),
+    (0x01ea, 0x0119),
+    (0x01ec, 0x011b),
+    (0x01ef, 0x010f),
+    (0x01f0, 0x0111),
+    (0x01f1, 0x0144),
+    (0x01f2, 0x0148),
+    (0x01f5, 0x0151),
+    (0x01f8, 0x0159),
+    (0x01f9, 0x016f),
+    (0x01fb, 0x0171),
+    (0x01fe, 0x0163),
+    (0x01ff, 0x02d9),
+    (0x02a1, 0x0126),
+    (0x02a6, 0x0124),
+    (0x02a9, 0x0130),
+    (0x02ab, 0x011e),
+    (0x02ac, 0x0134),
+    (0x02b1, 0x0127),
+    (0x02b6, 0x0125),
+    (0x02b9, 0x0131),
+    (0x02bb, 0x011f),
+    (0x02bc, 0x0135),
+    (0x02c5, 0x010a),
+    (0x02c6, 0x0108),
+    (0x02d5, 0x0120),
+    (0x02d8, 0x011c),
+    (0x02dd, 0x016c),
+    (0x02de, 0x015c),
+    (0x02e5, 0x010b),
+    (0x02e6, 0x0109),
+    (0x02f5, 0x0121),
+    (0x02f8, 0x011d),
+    (0x02fd, 0x016d),
+    (0x02fe, 0x015d),
+    (0x03a2, 0x0138),
+    (0x03a3, 0x0156),
+    (0x03a5, 0x0128),
+    (0x03a6, 0x013b),
+    (0x03aa, 0x0112),
+    (0x03ab, 0x0122),
+    (0x03ac, 0x0166),
+    (0x03b3, 0x0157),
+    (0x03b5, 0x0129),
+    (0x03b6, 0x013c),
+    (0x03ba, 0x0113),
+    (0x03bb, 0x0123),
+    (0x03bc, 0x0167),
+    (0x03bd, 0x014a),
+    (0x03bf, 0x014b),
+    (0x03c0, 0x0100),
+    (0x03c7, 0x012e),
+    (0x03cc, 0x0116),
+    (0x03cf, 0x012a),
+    (0x03d1, 0x0145),
+    (0x03d2, 0x014c),
+    (0x03d3, 0x0136),
+    (0x03d9, 0x0172),
+    (0x03dd, 0x0168),
+    (0x03de, 0x016a),
+    (0x03e0, 0x0101),
+    (0x03e7, 0x012f),
+    (0x03ec, 0x0117),
+    (0x03ef, 0x012b),
+    (0x03f1, 0x0146),
+    (0x03f2, 0x014d),
+    (0x03f3, 0x0137),
+    (0x03f9, 0x0173),
+    (0x03fd, 0x0169),
+    (0x03fe, 0x016b),
+    (0x047e, 0x203e),
+    (0x04a1, 0x3002),
+    (0x04a2, 0x300c),
+    (0x04a3, 0x300d),
+    (0x04a4, 0x3001),
+    (0x04a5, 0x30fb),
+    (0x04a6, 0x30f2),
+    (0x04a7, 0x30a1),
+    (0x04a8, 0x30a3),
+    (0x04a9, 0x30a5),
+    (0x04aa, 0x30a7),
+    (0x04ab, 0x30a9),
+    (0x04ac, 0x30e3),
+    (0x04ad, 0x30e5),
+    (0x04ae, 0x30e7),
+    (0x04af, 0x30c3),
+    (0x04b0, 0x30fc),
+    (0x04b1, 0x30a2),
+    (0x04b2, 0x30a4),
+    (0x04b3, 0x30a6),
+    (0x04b4, 0x30a8),
+    (0x04b5, 0x30aa),
+    (0x04b6, 0x30ab),
+    (0x04b7, 0x30ad),
+    (0x04b8, 0x30af),
+    (0x04b9, 0x30b1),
+    (0x04ba, 0x30b3),
+    (0x04bb, 0x30b5),
+    (0x04bc, 0x30b7),
+    (0x04bd, 0x30b9),
+    (0x04be, 0x30bb),
+    (0x04bf, 0x30bd),
+    (0x04c0, 0x30bf),
+    (0x04c1, 0x30c1),
+    (0x04c2, 0x30c4),
+    (0x04c3, 0x30c6),
+    (0x04c4, 0x30c8),
+    (0x04c5, 0x30ca),
+    (0x04c6, 0x30cb),
+    (0x04c7, 0x30cc),
+    (0x04c8, 0x30cd),
+    (0x04c9, 0x30ce),
+    (0x04ca, 0x30cf),
+    (0x04cb, 0x30d2),
+    (0x04cc, 0x30d5),
+    (0x04cd, 0x30d8),
+    (0x04ce, 0x30db),
+    (0x04cf, 0x30de),
+    (0x04d0, 0x30df),
+    (0x04d1, 0x30e0),
+    (0x04d2, 0x30e1),
+    (0x04d3, 0x30e2),
+    (0x04d4, 0x30e4),
+    (0x04d5, 0x30e6),
+    (0x04d6, 0x30e8),
+    (0x04d7, 0x30e9),
+    (0x04d8, 0x30ea),
+    (0x04d9, 0x30eb),
+    (0x04da, 0x30ec),
+    (0x04db, 0x30ed),
+    (0x04dc, 0x30ef),
+    (0x04dd, 0x30f3),
+    (0x04de, 0x309b),
+    (0x04df, 0x309c),
+    (0x05ac, 0x060c),
+    (0x05bb, 0x061b),
+    (0x05bf, 0x061f),
+    (0x05c1, 0x0621),
+    (0x05c2, 0x0622),
+    (0x05c3, 0x0623),
+    (0x05c4, 0x0624),
+    (0x05c5, 0x0625),
+    (0x05c6, 0x0626),
+    (0x05c7, 0x0627),
+    (0x05c8, 0x0628),
+    (0x05c9, 0x0629),
+    (0x05ca, 0x062a),
+    (0x05cb, 0x062b),
+    (0x05cc, 0x062c),
+    (0x05cd, 0x062d),
+    (0x05ce, 0x062e),
+    (0x05cf, 0x062f),
+    (0x05d0, 0x0630),
+    (0x05d1, 0x0631),
+    (0x05d2, 0x0632),
+    (0x05d3, 0x0633),
+    (0x05d4, 0x0634),
+    (0x05d5, 0x0635),
+    (0x05d6, 0x0636),
+    (0x05d7, 0x0637),
+    (0x05d8, 0x0638),
+    (0x05d9, 0x0639),
+    (0x05da, 0x063a),
+    (0x05e0, 0x0640),
+    (0x05e1, 0x0641),
+    (0x05e2, 0x0642),
+    (0x05e3, 0x0643),
+    (0x05e4, 0x0644),
+    (0x05e5, 0x0645),
+    (0x05e6, 0x0646),
+    (0x05e7, 0x0647),
+    (0x05e8, 0x0648),
+    (0x05e9, 0x0649),
+    (0x05ea, 0x064a),
+    (0x05eb, 0x064b),
+    (0x05ec, 0x064c),
+    (0x05ed, 0x064d),
+    (0x05ee, 0x064e),
+    (0x05ef, 0x064f),
+    (0x05f0, 0x0650),
+    (0x05f1, 0x0651),
+    (0x05f2, 0x0652),
+    (0x06a1, 0x0452),
+    (0x06a2, 0x0453),
+    (0x06a3, 0x0451),
+    (0x06a4, 0x0454),
+    (0x06a5, 0x0455),
+    (0x06a6, 0x0456),
+    (0x06a7, 0x0457),
+    (0x06a8, 0x0458),
+    (0x06a9, 0x0459),
+    (0x06aa, 0x045a),
+    (0x06ab, 0x045b),
+    (0x06ac, 0x045c),
+    (0x06ad, 0x0491),
+    (0x06ae, 0x045e),
+    (0x06af, 0x045f),
+    (0x06b0, 0x2116),
+    (0x06b1, 0x0402),
+    (0x06b2, 0x0403),
+    (0x06b3, 0x0401),
+    (0x06b4, 0x0404),
+    (0x06b5, 0x0405),
+    (0x06b6, 0x0406),
+    (0x06b7, 0x0407),
+    (0x06b8, 0x0408),
+    (0x06b9, 0x0409),
+    (0x06ba, 0x040a),
+    (0x06bb, 0x040b),
+    (0x06bc, 0x040c),
+    (0x06bd, 0x0490),
+    (0x06be, 0x040e),
+    (0x06bf, 0x040f),
+    (0x06c0, 0x044e),
+    (0x06c1, 0x0430),
+    (0x06c2, 0x0431),
+    (0x06c3, 0x0446),
+    (0x06c4, 0x0434),
+    (0x06c5, 0x0435),
+    (0x06c6, 0x0444),
+    (0x06c7, 0x0433),
+    (0x06c8, 0x0445),
+    (0x06c9, 0x0438),
+    (0x06ca, 0x0439),
+    (0x06cb, 0x043a),
+    (0x06cc, 0x043b),
+    (0x06cd, 0x043c),
+    (0x06ce, 0x043d),
+    (0x06cf, 0x043e),
+    (0x06d0, 0x043f),
+    (0x06d1, 0x044f),
+    (0x06d2, 0x0440),
+    (0x06d3, 0x0441),
+    (0x06d4, 0x0442),
+    (0x06d5, 0x0443),
+    (0x06d6, 0x0436),
+    (0x06d7, 0x0432),
+    (0x06d8, 0x044c),
+    (0x06d9, 0x044b),
+    (0x06da, 0x0437),
+    (0x06db, 0x0448),
+    (0x06dc, 0x044d),
+    (0x06dd, 0x0449),
+    (0x06de, 0x0447),
+    (0x06df, 0x044a),
+    (0x06e0, 0x042e),
+    (0x06e1, 0x0410),
+    (0x06e2, 0x0411),
+    (0x06e3, 0x0426),
+    (0x06e4, 0x0414),
+    (0x06e5, 0x0415),
+    (0x06e6, 0x0424),
+    (0x06e7, 0x0413),
+    (0x06e8, 0x0425),
+    (0x06e9, 0x0418),
+    (0x06ea, 0x0419),
+    (0x06eb, 0x041a),
+    (0x06ec, 0x041b),
+    (0x06ed, 0x041c),
+    (0x06ee, 0x041d),
+    (0x06ef, 0x041e),
+    (0x06f0, 0x041f),
+    (0x06f1, 0x042f),
+    (0x06f2, 0x0420),
+    (0x06f3, 0x0421),
+    (0x06f4, 0x0422),
+    (0x06f5, 0x0423),
+    (0x06f6, 0x0416),
+    (0x06f7, 0x0412),
+    (0x06f8, 0x042c),
+    (0x06f9, 0x042b),
+    (0x06fa, 0x0417),
+    (0x06fb, 0x0428),
+    (0x06fc, 0x042d),
+    (0x06fd, 0x0429),
+    (0x06fe, 0x0427),
+    (0x06ff, 0x042a),
+    (0x07a1, 0x0386),
+    (0x07a2, 0x0388),
+    (0x07a3, 0x0389),
+    (0x07a4, 0x038a),
+    (0x07a5, 0x03aa),
+    (0x07a7, 0x038c),
+    (0x07a8, 0x038e),
+    (0x07a9, 0x03ab),
+    (0x07ab, 0x038f),
+    (0x07ae, 0x0385),
+    (0x07af, 0x2015),
+    (0x07b1, 0x03ac),
+    (0x07b2, 0x03ad),
+    (0x07b3, 0x03ae),
+    (0x07b4, 0x03af),
+    (0x07b5, 0x03ca),
+    (0x07b6, 0x0390),
+    (0x07b7, 0x03cc),
+    (0x07b8, 0x03cd),
+    (0x07b9, 0x03cb),
+    (0x07ba, 0x03b0),
+    (0x07bb, 0x03ce),
+    (0x07c1, 0x0391),
+    (0x07c2, 0x0392),
+    (0x07c3, 0x0393),
+    (0x07c4, 0x0394),
+    (0x07c5, 0x0395),
+    (0x07c6, 0x0396),
+    (0x07c7, 0x0397),
+    (0x07c8, 0x0398),
+    (0x07c9, 0x0399),
+    (0x07ca, 0x039a),
+    (0x07cb, 0x039b),
+    (0x07cc, 0x039c),
+    (0x07cd, 0x039d),
+    (0x07ce, 0x039e),
+    (0x07cf, 0x039f),
+    (0x07d0, 0x03a0),
+    (0x07d1, 0x03a1),
+    (0x07d2, 0x03a3),
+    (0x07d4, 0x03a4),
+    (0x07d5, 0x03a5),
+    (0x07d6, 0x03a6),
+    (0x07d7, 0x03a7),
+    (0x07d8, 0x03a8),
+    (0x07d9, 0x03a9),
+    (0x07e1, 0x03b1),
+    (0x07e2, 0x03b2),
+    (0x07e3, 0x03b3),
+    (0x07e4, 0x03b4),
+    (0x07e5, 0x03b5),
+    (0x07e6, 0x03b6),
+    (0x07e7, 0x03b7),
+    (0x07e8, 0x03b8),
+    (0x07e9, 0x03b9),
+    (0x07ea, 0x03ba),
+    (0x07eb, 0x03bb),
+    (0x07ec, 0x03bc),
+    (0x07ed, 0x03bd),
+    (0x07ee, 0x03be),
+    (0x07ef, 0x03bf),
+    (0x07f0, 0x03c0),
+    (0x07f1, 0x03c1),
+    (0x07f2, 0x03c3),
+    (0x07f3, 0x03c2),
+    (0x07f4, 0x03c4),
+    (0x07f5, 0x03c5),
+    (0x07f6, 0x03c6),
+    (0x07f7, 0x03c7),
+    (0x07f8, 0x03c8),
+    (0x07f9, 0x03c9),
+    (0x08a1, 0x23b7),
+    (0x08a2, 0x250c),
+    (0x08a3, 0x2500),
+    (0x08a4, 0x2320),
+    (0x08a5, 0x2321),
+    (0x08a6, 0x2502),
+    (0x08a7, 0x23a1),
+    (0x08a8, 0x23a3),
+    (0x08a9, 0x23a4),
+    (0x08aa, 0x23a6),
+    (0x08ab, 0x239b),
+    (0x08ac, 0x239d),
+    (0x08ad, 0x239e),
+    (0x08ae, 0x23a0),
+    (0x08af, 0x23a8),
+    (0x08b0, 0x23ac),
+    (0x08bc, 0x2264),
+    (0x08bd, 0x2260),
+    (0x08be, 0x2265),
+    (0x08bf, 0x222b),
+    (0x08c0, 0x2234),
+    (0x08c1, 0x221d),
+    (0x08c2, 0x221e),
+    (0x08c5, 0x2207),
+    (0x08c8, 0x223c),
+    (0x08c9, 0x2243),
+    (0x08cd, 0x21d4),
+    (0x08ce, 0x21d2),
+    (0x08cf, 0x2261),
+    (0x08d6, 0x221a),
+    (0x08da, 0x2282),
+    (0x08db, 0x2283),
+    (0x08dc, 0x2229),
+    (0x08dd, 0x222a),
+    (0x08de, 0x2227),
+    (0x08df, 0x2228),
+    (0x08ef, 0x2202),
+    (0x08f6, 0x0192),
+    (0x08fb, 0x2190),
+    (0x08fc, 0x2191),
+    (0x08fd, 0x2192),
+    (0x08fe, 0x2193),
+    (0x09e0, 0x25c6),
+    (0x09e1, 0x2592),
+    (0x09e2, 0x2409),
+    (0x09e3, 0x240c),
+    (0x09e4, 0x240d),
+    (0x09e5, 0x240a),
+    (0x09e8, 0x2424),
+    (0x09e9, 0x240b),
+    (0x09ea, 0x2518),
+    (0x09eb, 0x2510),
+    (0x09ec, 0x250c),
+    (0x09ed, 0x2514),
+    (0x09ee, 0x253c),
+    (0x09ef, 0x23ba),
+    (0x09f0, 0x23bb),
+    (0x09f1, 0x2500),
+    (0x09f2, 0x23bc),
+    (0x09f3, 0x23bd),
+    (0x09f4, 0x251c),
+    (0x09f5, 0x2524),
+    (0x09f6, 0x2534),
+    (0x09f7, 0x252c),
+    (0x09f8, 0x2502),
+    (0x0aa1, 0x2003),
+    (0x0aa2, 0x2002),
+    (0x0aa3, 0x2004),
+    (0x0aa4, 0x2005),
+    (0x0aa5, 0x2007),
+    (0x0aa6, 0x2008),
+    (0x0aa7, 0x2009),
+    (0x0aa8, 0x200a),
+    (0x0aa9, 0x2014),
+    (0x0aaa, 0x2013),
+    (0x0aac, 0x2423),
+    (0x0aae, 0x2026),
+    (0x0aaf, 0x2025),
+    (0x0ab0, 0x2153),
+    (0x0ab1, 0x2154),
+    (0x0ab2, 0x2155),
+    (0x0ab3, 0x2156),
+    (0x0ab4, 0x2157),
+    (0x0ab5, 0x2158),
+    (0x0ab6, 0x2159),
+    (0x0ab7, 0x215a),
+    (0x0ab8, 0x2105),
+    (0x0abb, 0x2012),
+    (0x0abc, 0x2329),
+    (0x0abd, 0x002e),
+    (0x0abe, 0x232a),
+    (0x0ac3, 0x215b),
+    (0x0ac4, 0x215c),
+    (0x0ac5, 0x215d),
+    (0x0ac6, 0x215e),
+    (0x0ac9, 0x2122),
+    (0x0aca, 0x2613),
+    (0x0acc, 0x25c1),
+    (0x0acd, 0x25b7),
+    (0x0ace, 0x25cb),
+    (0x0acf, 0x25af),
+    (0x0ad0, 0x2018),
+    (0x0ad1, 0x2019),
+    (0x0ad2, 0x201c),
+    (0x0ad3, 0x201d),
+    (0x0ad4, 0x211e),
+    (0x0ad5, 0x2030),
+    (0x0ad6, 0x2032),
+    (0x0ad7, 0x2033),
+    (0x0ad9, 0x271d),
+    (0x0adb, 0x25ac),
+    (0x0adc, 0x25c0),
+    (0x0add, 0x25b6),
+    (0x0ade, 0x25cf),
+    (0x0adf, 0x25ae),
+    (0x0ae0, 0x25e6),
+    (0x0ae1, 0x25ab),
+    (0x0ae2, 0x25ad),
+    (0x0ae3, 0x25b3),
+    (0x0ae4, 0x25bd),
+    (0x0ae5, 0x2606),
+    (0x0ae6, 0x2022),
+    (0x0ae7, 0x25aa),
+    (0x0ae8, 0x25b2),
+    (0x0ae9, 0x25bc),
+    (0x0aea, 0x261c),
+    (0x0aeb, 0x261e),
+    (0x0aec, 0x2663),
+    (0x0aed, 0x2666),
+    (0x0aee, 0x2665),
+    (0x0af0, 0x2720),
+    (0x0af1, 0x2020),
+    (0x0af2, 0x2021),
+    (0x0af3, 0x2713),
+    (0x0af4, 0x2717),
+    (0x0af5, 0x266f),
+    (0x0af6, 0x266d),
+    (0x0af7, 0x2642),
+    (0x0af8, 0x2640),
+    (0x0af9, 0x260e),
+    (0x0afa, 0x2315),
+    (0x0afb, 0x2117),
+    (0x0afc, 0x2038),
+    (0x0afd, 0x201a),
+    (0x0afe, 0x201e),
+    (0x0ba3, 0x003c),
+    (0x0ba6, 0x003e),
+    (0x0ba8, 0x2228),
+    (0x0ba9, 0x2227),
+    (0x0bc0, 0x00af),
+    (0x0bc2, 0x22a4),
+    (0x0bc3, 0x2229),
+    (0x0bc4, 0x230a),
+    (0x0bc6, 0x005f),
+    (0x0bca, 0x2218),
+    (0x0bcc, 0x2395),
+    (0x0bce, 0x22a5),
+    (0x0bcf, 0x25cb),
+    (0x0bd3, 0x2308),
+    (0x0bd6, 0x222a),
+    (0x0bd8, 0x2283),
+    (0x0bda, 0x2282),
+    (0x0bdc, 0x22a3),
+    (0x0bfc, 0x22a2),
+    (0x0cdf, 0x2017),
+    (0x0ce0, 0x05d0),
+    (0x0ce1, 0x05d1),
+    (0x0ce2, 0x05d2),
+    (0x0ce3, 0x05d3),
+    (0x0ce4, 0x05d4),
+    (0x0ce5, 0x05d5),
+    (0x0ce6, 0x05d6),
+    (0x0ce7, 0x05d7),
+    (0x0ce8, 0x05d8),
+    (0x0ce9, 0x05d9),
+    (0x0cea, 0x05da),
+    (0x0ceb, 0x05db),
+    (0x0cec, 0x05dc),
+    (0x0ced, 0x05dd),
+    (0x0cee, 0x05de),
+    (0x0cef, 0x05df),
+    (0x0cf0, 0x05e0),
+    (0x0cf1, 0x05e1),
+    (0x0cf2, 0x05e2),
+    (0x0cf3, 0x05e3),
+    (0x0cf4, 0x05e4),
+    (0x0cf5, 0x05e5),
+    (0x0cf6, 0x05e6),
+    (0x0cf7, 0x05e7),
+    (0x0cf8, 0x05e8),
+    (0x0cf9, 0x05e9),
+    (0x0cfa, 0x05ea),
+    (0x0da1, 0x0e01),
+    (0x0da2, 0x0e02),
+    (0x0da3, 0x0e03),
+    (0x0da4, 0x0e04),
+    (0x0da5, 0x0e05),
+    (0x0da6, 0x0e06),
+    (0x0da7, 0x0e07),
+    (0x0da8, 0x0e08),
+    (0x0da9, 0x0e09),
+    (0x0daa, 0x0e0a),
+    (0x0dab, 0x0e0b),
+    (0x0dac, 0x0e0c),
+    (0x0dad, 0x0e0d),
+    (0x0dae, 0x0e0e),
+    (0x0daf, 0x0e0f),
+    (0x0db0, 0x0e10),
+    (0x0db1, 0x0e11),
+    (0x0db2, 0x0e12),
+    (0x0db3, 0x0e13),
+    (0x0db4, 0x0e14),
+    (0x0db5, 0x0e15),
+    (0x0db6, 0x0e16),
+    (0x0db7, 0x0e17),
+    (0x0db8, 0x0e18),
+    (0x0db9, 0x0e19),
+    (0x0dba, 0x0e1a),
+    (0x0dbb, 0x0e1b),
+    (0x0dbc, 0x0e1c),
+    (0x0dbd, 0x0e1d),
+    (0x0dbe, 0x0e1e),
+    (0x0dbf, 0x0e1f),
+    (0x0dc0, 0x0e20),
+    (0x0dc1, 0x0e21),
+    (0x0dc2, 0x0e22),
+    (0x0dc3, 0x0e23),
+    (0x0dc4, 0x0e24),
+    (0x0dc5, 0x0e25),
+    (0x0dc6, 0x0e26),
+    (0x0dc7, 0x0e27),
+    (0x0dc8, 0x0e28),
+    (0x0dc9, 0x0e29),
+    (0x0dca, 0x0e2a),
+    (0x0dcb, 0x0e2b),
+    (0x0dcc, 0x0e2c),
+    (0x0dcd, 0x0e2d),
+    (0x0dce, 0x0e2e),
+    (0x0dcf, 0x0e2f),
+    (0x0dd0, 0x0e30),
+    (0x0dd1, 0x0e31),
+    (0x0dd2, 0x0e32),
+    (0x0dd3, 0x0e33),
+    (0x0dd4, 0x0e34),
+    (0x0dd5, 0x0e35),
+    (0x0dd6, 0x0e36),
+    (0x0dd7, 0x0e37),
+    (0x0dd8, 0x0e38),
+    (0x0dd9, 0x0e39),
+    (0x0dda, 0x0e3a),
+    (0x0ddf, 0x0e3f),
+    (0x0de0, 0x0e40),
+    (0x0de1, 0x0e41),
+    (0x0de2, 0x0e42),
+    (0x0de3, 0x0e43),
+    (0x0de4, 0x0e44),
+    (0x0de5, 0x0e45),
+    (0x0de6, 0x0e46),
+    (0x0de7, 0x0e47),
+    (0x0de8, 0x0e48),
+    (0x0de9, 0x0e49),
+    (0x0dea, 0x0e4a),
+    (0x0deb, 0x0e4b),
+    (0x0dec, 0x0e4c),
+    (0x0ded, 0x0e4d),
+    (0x0df0, 0x0e50),
+    (0x0df1, 0x0e51),
+    (0x0df2, 0x0e52),
+    (0x0df3, 0x0e53),
+    (0x0df4, 0x0e54),
+    (0x0df5, 0x0e55),
+    (0x0df6, 0x0e56),
+    (0x0df7, 0x0e57),
+    (0x0df8, 0x0e58),
+    (0x0df9, 0x0e59),
+    (0x0ea1, 0x3131),
+    (0x0ea2, 0x3132),
+    (0x0ea3, 0x3133),
+    (0x0ea4, 0x3134),
+    (0x0ea5, 0x3135),
+    (0x0ea6, 0x3136),
+    (0x0ea7, 0x3137),
+    (0x0ea8, 0x3138),
+    (0x0ea9, 0x3139),
+    (0x0eaa, 0x313a),
+    (0x0eab, 0x313b),
+    (0x0eac, 0x313c),
+    (0x0ead, 0x313d),
+    (0x0eae, 0x313e),
+    (0x0eaf, 0x313f),
+    (0x0eb0, 0x3140),
+    (0x0eb1, 0x3141),
+    (0x0eb2, 0x3142),
+    (0x0eb3, 0x3143),
+    (0x0eb4, 0x3144),
+    (0x0eb5, 0x3145),
+    (0x0eb6, 0x3146),
+    (0x0eb7, 0x3147),
+    (0x0eb8, 0x3148),
+    (0x0eb9, 0x3149),
+    (0x0eba, 0x314a),
+    (0x0ebb, 0x314b),
+    (0x0ebc, 0x314c),
+    (0x0ebd, 0x314d),
+    (0x0ebe, 0x314e),
+    (0x0ebf, 0x314f),
+    (0x0ec0, 0x3150),
+    (0x0ec1, 0x3151),
+    (0x0ec2, 0x3152),
+    (0x0ec3, 0x3153),
+    (0x0ec4, 0x3154),
+    (0x0ec5, 0x3155),
+    (0x0ec6, 0x3156),
+    (0x0ec7, 0x3157),
+    (0x0ec8, 0x3158),
+    (0x0ec9, 0x3159),
+    (0x0eca, 0x315a),
+    (0x0ecb, 0x315b),
+    (0x0ecc, 0x315c),
+    (0x0ecd, 0x315d),
+    (0x0ece, 0x315e),
+    (0x0ecf, 0x315f),
+    (0x0ed0, 0x3160),
+    (0x0ed1, 0x3161),
+    (0x0ed2, 0x3162),
+    (0x0ed3, 0x3163),
+    (0x0ed4, 0x11a8),
+    (0x0ed5, 0x11a9),
+    (0x0ed6, 0x11aa),
+    (0x0ed7, 0x11ab),
+    (0x0ed8, 0x11ac),
+    (0x0ed9, 0x11ad),
+    (0x0eda, 0x11ae),
+    (0x0edb, 0x11af),
+    (0x0edc, 0x11b0),
+    (0x0edd, 0x11b1),
+    (0x0ede, 0x11b2),
+    (0x0edf, 0x11b3),
+    (0x0ee0, 0x11b4),
+    (0x0ee1, 0x11b5),
+    (0x0ee2, 0x11b6),
+    (0x0ee3, 0x11b7),
+    (0x0ee4, 0x11b8),
+    (0x0ee5, 0x11b9),
+    (0x0ee6, 0x11ba),
+    (0x0ee7, 0x11bb),
+    (0x0ee8, 0x11bc),
+    (0x0ee9, 0x11bd),
+    (0x0eea, 0x11be),
+    (0x0eeb, 0x11bf),
+    (0x0eec, 0x11c0),
+    (0x0eed, 0x11c1),
+    (0x0eee, 0x11c2),
+    (0x0eef, 0x316d),
+    (0x0ef0, 0x3171),
+    (0x0ef1, 0x3178),
+    (0x0ef2, 0x317f),
+    (0x0ef3, 0x3181),
+    (0x0ef4, 0x3184),
+    (0x0ef5, 0x3186),
+    (0x0ef6, 0x318d),
+    (0x0ef7, 0x318e),
+    (0x0ef8, 0x11eb),
+    (0x0ef9, 0x11f0),
+    (0x0efa, 0x11f9),
+    (0x0eff, 0x20a9),
+    (0x13bc, 0x0152),
+    (0x13bd, 0x0153),
+    (0x13be, 0x0178),
+    (0x20ac, 0x20ac),
+];
+
+/// Dead keysyms and the keysym that follows them, paired with the character that they
+/// combine into. Sorted by dead keysym and then by the following keysym.
+pub static DEAD_KEY_COMPOSITIONS: [(u16, u32, char); 1213] = [
+    (0xfe50, 0x20, '`'),
+    (0xfe50, 0x41, 'À'),
+    (0xfe50, 0x45, 'È'),
+    (0xfe50, 0x49, 'Ì'),
+    (0xfe50, 0x4e, 'Ǹ'),
+    (0xfe50, 0x4f, 'Ò'),
+    (0xfe50, 0x55, 'Ù'),
+    (0xfe50, 0x56, 'Ǜ'),
+    (0xfe50, 0x57, 'Ẁ'),
+    (0xfe50, 0x59, 'Ỳ'),
+    (0xfe50, 0x61, 'à'),
+    (0xfe50, 0x65, 'è'),
+    (0xfe50, 0x69, 'ì'),
+    (0xfe50, 0x6e, 'ǹ'),
+    (0xfe50, 0x6f, 'ò'),
+    (0xfe50, 0x75, 'ù'),
+    (0xfe50, 0x76, 'ǜ'),
+    (0xfe50, 0x77, 'ẁ'),
+    (0xfe50, 0x79, 'ỳ'),
+    (0xfe50, 0xa0, '\u{300}'),
+    (0xfe50, 0xc2, 'Ầ'),
+    (0xfe50, 0xca, 'Ề'),
+    (0xfe50, 0xd4, 'Ồ'),
+    (0xfe50, 0xdc, 'Ǜ'),
+    (0xfe50, 0xe2, 'ầ'),
+    (0xfe50, 0xea, 'ề'),
+    (0xfe50, 0xf4, 'ồ'),
+    (0xfe50, 0xfc, 'ǜ'),
+    (0xfe50, 0x1c3, 'Ằ'),
+    (0xfe50, 0x1e3, 'ằ'),
+    (0xfe50, 0x3aa, 'Ḕ'),
+    (0xfe50, 0x3ba, 'ḕ'),
+    (0xfe50, 0x3d2, 'Ṑ'),
+    (0xfe50, 0x3f2, 'ṑ'),
+    (0xfe50, 0x6c5, 'ѐ'),
+    (0xfe50, 0x6c9, 'ѝ'),
+    (0xfe50, 0x6e5, 'Ѐ'),
+    (0xfe50, 0x6e9, 'Ѝ'),
+    (0xfe50, 0x7b5, 'ῒ'),
+    (0xfe50, 0x7b9, 'ῢ'),
+    (0xfe50, 0x7c1, 'Ὰ'),
+    (0xfe50, 0x7c5, 'Ὲ'),
+    (0xfe50, 0x7c7, 'Ὴ'),
+    (0xfe50, 0x7c9, 'Ὶ'),
+    (0xfe50, 0x7cf, 'Ὸ'),
+    (0xfe50, 0x7d5, 'Ὺ'),
+    (0xfe50, 0x7d9, 'Ὼ'),
+    (0xfe50, 0x7e1, 'ὰ'),
+    (0xfe50, 0x7e5, 'ὲ'),
+    (0xfe50, 0x7e7, 'ὴ'),
+    (0xfe50, 0x7e9, 'ὶ'),
+    (0xfe50, 0x7ef, 'ὸ'),
+    (0xfe50, 0x7f5, 'ὺ'),
+    (0xfe50, 0x7f9, 'ὼ'),
+    (0xfe50, 0xfe50, '`'),
+    (0xfe50, 0x10001a0, 'Ờ'),
+    (0xfe50, 0x10001a1, 'ờ'),
+    (0xfe50, 0x10001af, 'Ừ'),
+    (0xfe50, 0x10001b0, 'ừ'),
+    (0xfe50, 0x1001f00, 'ἂ'),
+    (0xfe50, 0x1001f01, 'ἃ'),
+    (0xfe50, 0x1001f08, 'Ἂ'),
+    (0xfe50, 0x1001f09, 'Ἃ'),
+    (0xfe50, 0x1001f10, 'ἒ'),
+    (0xfe50, 0x1001f11, 'ἓ'),
+    (0xfe50, 0x1001f18, 'Ἒ'),
+    (0xfe50, 0x1001f19, 'Ἓ'),
+    (0xfe50, 0x1001f20, 'ἢ'),
+    (0xfe50, 0x1001f21, 'ἣ'),
+    (0xfe50, 0x1001f28, 'Ἢ'),
+    (0xfe50, 0x1001f29, 'Ἣ'),
+    (0xfe50, 0x1001f30, 'ἲ'),
+    (0xfe50, 0x1001f31, 'ἳ'),
+    (0xfe50, 0x1001f38, 'Ἲ'),
+    (0xfe50, 0x1001f39, 'Ἳ'),
+    (0xfe50, 0x1001f40, 'ὂ'),
+    (0xfe50, 0x1001f41, 'ὃ'),
+    (0xfe50, 0x1001f48, 'Ὂ'),
+    (0xfe50, 0x1001f49, 'Ὃ'),
+    (0xfe50, 0x1001f50, 'ὒ'),
+    (0xfe50, 0x1001f51, 'ὓ'),
+    (0xfe50, 0x1001f59, 'Ὓ'),
+    (0xfe50, 0x1001f60, 'ὢ'),
+    (0xfe50, 0x1001f61, 'ὣ'),
+    (0xfe50, 0x1001f68, 'Ὢ'),
+    (0xfe50, 0x1001f69, 'Ὣ'),
+    (0xfe51, 0x20, '\''),
+    (0xfe51, 0x41, 'Á'),
+    (0xfe51, 0x43, 'Ć'),
+    (0xfe51, 0x45, 'É'),
+    (0xfe51, 0x47, 'Ǵ'),
+    (0xfe51, 0x49, 'Í'),
+    (0xfe51, 0x4b, 'Ḱ'),
+    (0xfe51, 0x4c, 'Ĺ'),
+    (0xfe51, 0x4d, 'Ḿ'),
+    (0xfe51, 0x4e, 'Ń'),
+    (0xfe51, 0x4f, 'Ó'),
+    (0xfe51, 0x50, 'Ṕ'),
+    (0xfe51, 0x52, 'Ŕ'),
+    (0xfe51, 0x53, 'Ś'),
+    (0xfe51, 0x55, 'Ú'),
+    (0xfe51, 0x56, 'Ǘ'),
+    (0xfe51, 0x57, 'Ẃ'),
+    (0xfe51, 0x59, 'Ý'),
+    (0xfe51, 0x5a, 'Ź'),
+    (0xfe51, 0x61, 'á'),
+    (0xfe51, 0x63, 'ć'),
+    (0xfe51, 0x65, 'é'),
+    (0xfe51, 0x67, 'ǵ'),
+    (0xfe51, 0x69, 'í'),
+    (0xfe51, 0x6b, 'ḱ'),
+    (0xfe51, 0x6c, 'ĺ'),
+    (0xfe51, 0x6d, 'ḿ'),
+    (0xfe51, 0x6e, 'ń'),
+    (0xfe51, 0x6f, 'ó'),
+    (0xfe51, 0x70, 'ṕ'),
+    (0xfe51, 0x72, 'ŕ'),
+    (0xfe51, 0x73, 'ś'),
+    (0xfe51, 0x75, 'ú'),
+    (0xfe51, 0x76, 'ǘ'),
+    (0xfe51, 0x77, 'ẃ'),
+    (0xfe51, 0x79, 'ý'),
+    (0xfe51, 0x7a, 'ź'),
+    (0xfe51, 0xa0, '\u{301}'),
+    (0xfe51, 0xc2, 'Ấ'),
+    (0xfe51, 0xc5, 'Ǻ'),
+    (0xfe51, 0xc6, 'Ǽ'),
+    (0xfe51, 0xc7, 'Ḉ'),
+    (0xfe51, 0xca, 'Ế'),
+    (0xfe51, 0xcf, 'Ḯ'),
+    (0xfe51, 0xd4, 'Ố'),
+    (0xfe51, 0xd5, 'Ṍ'),
+    (0xfe51, 0xd8, 'Ǿ'),
+    (0xfe51, 0xdc, 'Ǘ'),
+    (0xfe51, 0xe2, 'ấ'),
+    (0xfe51, 0xe5, 'ǻ'),
+    (0xfe51, 0xe6, 'ǽ'),
+    (0xfe51, 0xe7, 'ḉ'),
+    (0xfe51, 0xea, 'ế'),
+    (0xfe51, 0xef, 'ḯ'),
+    (0xfe51, 0xf4, 'ố'),
+    (0xfe51, 0xf5, 'ṍ'),
+    (0xfe51, 0xf8, 'ǿ'),
+    (0xfe51, 0xfc, 'ǘ'),
+    (0xfe51, 0x1c3, 'Ắ'),
+    (0xfe51, 0x1e3, 'ắ'),
+    (0xfe51, 0x3aa, 'Ḗ'),
+    (0xfe51, 0x3ba, 'ḗ'),
+    (0xfe51, 0x3d2, 'Ṓ'),
+    (0xfe51, 0x3dd, 'Ṹ'),
+    (0xfe51, 0x3f2, 'ṓ'),
+    (0xfe51, 0x3fd, 'ṹ'),
+    (0xfe51, 0x6c7, 'ѓ'),
+    (0xfe51, 0x6cb, 'ќ'),
+    (0xfe51, 0x6e7, 'Ѓ'),
+    (0xfe51, 0x6eb, 'Ќ'),
+    (0xfe51, 0x7b5, 'ΐ'),
+    (0xfe51, 0x7b9, 'ΰ'),
+    (0xfe51, 0x7c1, 'Ά'),
+    (0xfe51, 0x7c5, 'Έ'),
+    (0xfe51, 0x7c7, 'Ή'),
+    (0xfe51, 0x7c9, 'Ί'),
+    (0xfe51, 0x7cf, 'Ό'),
+    (0xfe51, 0x7d5, 'Ύ'),
+    (0xfe51, 0x7d9, 'Ώ'),
+    (0xfe51, 0x7e1, 'ά'),
+    (0xfe51, 0x7e5, 'έ'),
+    (0xfe51, 0x7e7, 'ή'),
+    (0xfe51, 0x7e9, 'ί'),
+    (0xfe51, 0x7ef, 'ό'),
+    (0xfe51, 0x7f5, 'ύ'),
+    (0xfe51, 0x7f9, 'ώ'),
+    (0xfe51, 0xfe51, '´'),
+    (0xfe51, 0x10001a0, 'Ớ'),
+    (0xfe51, 0x10001a1, 'ớ'),
+    (0xfe51, 0x10001af, 'Ứ'),
+    (0xfe51, 0x10001b0, 'ứ'),
+    (0xfe51, 0x1001f00, 'ἄ'),
+    (0xfe51, 0x1001f01, 'ἅ'),
+    (0xfe51, 0x1001f08, 'Ἄ'),
+    (0xfe51, 0x1001f09, 'Ἅ'),
+    (0xfe51, 0x1001f10, 'ἔ'),
+    (0xfe51, 0x1001f11, 'ἕ'),
+    (0xfe51, 0x1001f18, 'Ἔ'),
+    (0xfe51, 0x1001f19, 'Ἕ'),
+    (0xfe51, 0x1001f20, 'ἤ'),
+    (0xfe51, 0x1001f21, 'ἥ'),
+    (0xfe51, 0x1001f28, 'Ἤ'),
+    (0xfe51, 0x1001f29, 'Ἥ'),
+    (0xfe51, 0x1001f30, 'ἴ'),
+    (0xfe51, 0x1001f31, 'ἵ'),
+    (0xfe51, 0x1001f38, 'Ἴ'),
+    (0xfe51, 0x1001f39, 'Ἵ'),
+    (0xfe51, 0x1001f40, 'ὄ'),
+    (0xfe51, 0x1001f41, 'ὅ'),
+    (0xfe51, 0x1001f48, 'Ὄ'),
+    (0xfe51, 0x1001f49, 'Ὅ'),
+    (0xfe51, 0x1001f50, 'ὔ'),
+    (0xfe51, 0x1001f51, 'ὕ'),
+    (0xfe51, 0x1001f59, 'Ὕ'),
+    (0xfe51, 0x1001f60, 'ὤ'),
+    (0xfe51, 0x1001f61, 'ὥ'),
+    (0xfe51, 0x1001f68, 'Ὤ'),
+    (0xfe51, 0x1001f69, 'Ὥ'),
+    (0xfe52, 0x20, '^'),
+    (0xfe52, 0x28, '⁽'),
+    (0xfe52, 0x29, '⁾'),
+    (0xfe52, 0x2b, '⁺'),
+    (0xfe52, 0x2d, '⁻'),
+    (0xfe52, 0x2e, '·'),
+    (0xfe52, 0x30, '⁰'),
+    (0xfe52, 0x31, '¹'),
+    (0xfe52, 0x32, '²'),
+    (0xfe52, 0x33, '³'),
+    (0xfe52, 0x34, '⁴'),
+    (0xfe52, 0x35, '⁵'),
+    (0xfe52, 0x36, '⁶'),
+    (0xfe52, 0x37, '⁷'),
+    (0xfe52, 0x38, '⁸'),
+    (0xfe52, 0x39, '⁹'),
+    (0xfe52, 0x3d, '⁼'),
+    (0xfe52, 0x41, 'Â'),
+    (0xfe52, 0x43, 'Ĉ'),
+    (0xfe52, 0x45, 'Ê'),
+    (0xfe52, 0x47, 'Ĝ'),
+    (0xfe52, 0x48, 'Ĥ'),
+    (0xfe52, 0x49, 'Î'),
+    (0xfe52, 0x4a, 'Ĵ'),
+    (0xfe52, 0x4f, 'Ô'),
+    (0xfe52, 0x53, 'Ŝ'),
+    (0xfe52, 0x55, 'Û'),
+    (0xfe52, 0x57, 'Ŵ'),
+    (0xfe52, 0x59, 'Ŷ'),
+    (0xfe52, 0x5a, 'Ẑ'),
+    (0xfe52, 0x61, 'â'),
+    (0xfe52, 0x63, 'ĉ'),
+    (0xfe52, 0x65, 'ê'),
+    (0xfe52, 0x67, 'ĝ'),
+    (0xfe52, 0x68, 'ĥ'),
+    (0xfe52, 0x69, 'î'),
+    (0xfe52, 0x6a, 'ĵ'),
+    (0xfe52, 0x6f, 'ô'),
+    (0xfe52, 0x73, 'ŝ'),
+    (0xfe52, 0x75, 'û'),
+    (0xfe52, 0x77, 'ŵ'),
+    (0xfe52, 0x79, 'ŷ'),
+    (0xfe52, 0x7a, 'ẑ'),
+    (0xfe52, 0xa0, '\u{302}'),
+    (0xfe52, 0xc0, 'Ầ'),
+    (0xfe52, 0xc1, 'Ấ'),
+    (0xfe52, 0xc3, 'Ẫ'),
+    (0xfe52, 0xc8, 'Ề'),
+    (0xfe52, 0xc9, 'Ế'),
+    (0xfe52, 0xd2, 'Ồ'),
+    (0xfe52, 0xd3, 'Ố'),
+    (0xfe52, 0xd5, 'Ỗ'),
+    (0xfe52, 0xe0, 'ầ'),
+    (0xfe52, 0xe1, 'ấ'),
+    (0xfe52, 0xe3, 'ẫ'),
+    (0xfe52, 0xe8, 'ề'),
+    (0xfe52, 0xe9, 'ế'),
+    (0xfe52, 0xf2, 'ồ'),
+    (0xfe52, 0xf3, 'ố'),
+    (0xfe52, 0xf5, 'ỗ'),
+    (0xfe52, 0xfe52, '^'),
+    (0xfe52, 0xffab, '⁺'),
+    (0xfe52, 0xffb0, '⁰'),
+    (0xfe52, 0xffb1, '¹'),
+    (0xfe52, 0xffb2, '²'),
+    (0xfe52, 0xffb3, '³'),
+    (0xfe52, 0xffb4, '⁴'),
+    (0xfe52, 0xffb5, '⁵'),
+    (0xfe52, 0xffb6, '⁶'),
+    (0xfe52, 0xffb7, '⁷'),
+    (0xfe52, 0xffb8, '⁸'),
+    (0xfe52, 0xffb9, '⁹'),
+    (0xfe52, 0xffbd, '⁼'),
+    (0xfe52, 0x1001ea0, 'Ậ'),
+    (0xfe52, 0x1001ea1, 'ậ'),
+    (0xfe52, 0x1001eb8, 'Ệ'),
+    (0xfe52, 0x1001eb9, 'ệ'),
+    (0xfe52, 0x1001ebc, 'Ễ'),
+    (0xfe52, 0x1001ebd, 'ễ'),
+    (0xfe52, 0x1001ecc, 'Ộ'),
+    (0xfe52, 0x1001ecd, 'ộ'),
+    (0xfe52, 0x1002212, '⁻'),
+    (0xfe52, 0x1004e00, '㆒'),
+    (0xfe52, 0x1004e01, '㆜'),
+    (0xfe52, 0x1004e09, '㆔'),
+    (0xfe52, 0x1004e0a, '㆖'),
+    (0xfe52, 0x1004e0b, '㆘'),
+    (0xfe52, 0x1004e19, '㆛'),
+    (0xfe52, 0x1004e2d, '㆗'),
+    (0xfe52, 0x1004e59, '㆚'),
+    (0xfe52, 0x1004e8c, '㆓'),
+    (0xfe52, 0x1004eba, '㆟'),
+    (0xfe52, 0x10056db, '㆕'),
+    (0xfe52, 0x1005730, '㆞'),
+    (0xfe52, 0x1005929, '㆝'),
+    (0xfe52, 0x1007532, '㆙'),
+    (0xfe53, 0x20, '~'),
+    (0xfe53, 0x3c, '≲'),
+    (0xfe53, 0x3d, '≃'),
+    (0xfe53, 0x3e, '≳'),
+    (0xfe53, 0x41, 'Ã'),
+    (0xfe53, 0x45, 'Ẽ'),
+    (0xfe53, 0x49, 'Ĩ'),
+    (0xfe53, 0x4e, 'Ñ'),
+    (0xfe53, 0x4f, 'Õ'),
+    (0xfe53, 0x55, 'Ũ'),
+    (0xfe53, 0x56, 'Ṽ'),
+    (0xfe53, 0x59, 'Ỹ'),
+    (0xfe53, 0x61, 'ã'),
+    (0xfe53, 0x65, 'ẽ'),
+    (0xfe53, 0x69, 'ĩ'),
+    (0xfe53, 0x6e, 'ñ'),
+    (0xfe53, 0x6f, 'õ'),
+    (0xfe53, 0x75, 'ũ'),
+    (0xfe53, 0x76, 'ṽ'),
+    (0xfe53, 0x79, 'ỹ'),
+    (0xfe53, 0xa0, '\u{303}'),
+    (0xfe53, 0xc2, 'Ẫ'),
+    (0xfe53, 0xca, 'Ễ'),
+    (0xfe53, 0xd3, 'Ṍ'),
+    (0xfe53, 0xd4, 'Ỗ'),
+    (0xfe53, 0xd6, 'Ṏ'),
+    (0xfe53, 0xda, 'Ṹ'),
+    (0xfe53, 0xe2, 'ẫ'),
+    (0xfe53, 0xea, 'ễ'),
+    (0xfe53, 0xf3, 'ṍ'),
+    (0xfe53, 0xf4, 'ỗ'),
+    (0xfe53, 0xf6, 'ṏ'),
+    (0xfe53, 0xfa, 'ṹ'),
+    (0xfe53, 0x1c3, 'Ẵ'),
+    (0xfe53, 0x1e3, 'ẵ'),
+    (0xfe53, 0x7b5, 'ῗ'),
+    (0xfe53, 0x7b9, 'ῧ'),
+    (0xfe53, 0x7e1, 'ᾶ'),
+    (0xfe53, 0x7e7, 'ῆ'),
+    (0xfe53, 0x7e9, 'ῖ'),
+    (0xfe53, 0x7f5, 'ῦ'),
+    (0xfe53, 0x7f9, 'ῶ'),
+    (0xfe53, 0xfe53, '~'),
+    (0xfe53, 0x10001a0, 'Ỡ'),
+    (0xfe53, 0x10001a1, 'ỡ'),
+    (0xfe53, 0x10001af, 'Ữ'),
+    (0xfe53, 0x10001b0, 'ữ'),
+    (0xfe53, 0x1001f00, 'ἆ'),
+    (0xfe53, 0x1001f01, 'ἇ'),
+    (0xfe53, 0x1001f08, 'Ἆ'),
+    (0xfe53, 0x1001f09, 'Ἇ'),
+    (0xfe53, 0x1001f20, 'ἦ'),
+    (0xfe53, 0x1001f21, 'ἧ'),
+    (0xfe53, 0x1001f28, 'Ἦ'),
+    (0xfe53, 0x1001f29, 'Ἧ'),
+    (0xfe53, 0x1001f30, 'ἶ'),
+    (0xfe53, 0x1001f31, 'ἷ'),
+    (0xfe53, 0x1001f38, 'Ἶ'),
+    (0xfe53, 0x1001f39, 'Ἷ'),
+    (0xfe53, 0x1001f50, 'ὖ'),
+    (0xfe53, 0x1001f51, 'ὗ'),
+    (0xfe53, 0x1001f59, 'Ὗ'),
+    (0xfe53, 0x1001f60, 'ὦ'),
+    (0xfe53, 0x1001f61, 'ὧ'),
+    (0xfe53, 0x1001f68, 'Ὦ'),
+    (0xfe53, 0x1001f69, 'Ὧ'),
+    (0xfe54, 0x20, '¯'),
+    (0xfe54, 0x41, 'Ā'),
+    (0xfe54, 0x45, 'Ē'),
+    (0xfe54, 0x47, 'Ḡ'),
+    (0xfe54, 0x49, 'Ī'),
+    (0xfe54, 0x4f, 'Ō'),
+    (0xfe54, 0x55, 'Ū'),
+    (0xfe54, 0x56, 'Ǖ'),
+    (0xfe54, 0x59, 'Ȳ'),
+    (0xfe54, 0x61, 'ā'),
+    (0xfe54, 0x65, 'ē'),
+    (0xfe54, 0x67, 'ḡ'),
+    (0xfe54, 0x69, 'ī'),
+    (0xfe54, 0x6f, 'ō'),
+    (0xfe54, 0x75, 'ū'),
+    (0xfe54, 0x76, 'ǖ'),
+    (0xfe54, 0x79, 'ȳ'),
+    (0xfe54, 0xa0, '\u{304}'),
+    (0xfe54, 0xc4, 'Ǟ'),
+    (0xfe54, 0xc6, 'Ǣ'),
+    (0xfe54, 0xc8, 'Ḕ'),
+    (0xfe54, 0xc9, 'Ḗ'),
+    (0xfe54, 0xd2, 'Ṑ'),
+    (0xfe54, 0xd3, 'Ṓ'),
+    (0xfe54, 0xd5, 'Ȭ'),
+    (0xfe54, 0xd6, 'Ȫ'),
+    (0xfe54, 0xdc, 'Ǖ'),
+    (0xfe54, 0xe4, 'ǟ'),
+    (0xfe54, 0xe6, 'ǣ'),
+    (0xfe54, 0xe8, 'ḕ'),
+    (0xfe54, 0xe9, 'ḗ'),
+    (0xfe54, 0xf2, 'ṑ'),
+    (0xfe54, 0xf3, 'ṓ'),
+    (0xfe54, 0xf5, 'ȭ'),
+    (0xfe54, 0xf6, 'ȫ'),
+    (0xfe54, 0xfc, 'ǖ'),
+    (0xfe54, 0x6c9, 'ӣ'),
+    (0xfe54, 0x6d5, 'ӯ'),
+    (0xfe54, 0x6e9, 'Ӣ'),
+    (0xfe54, 0x6f5, 'Ӯ'),
+    (0xfe54, 0x7c1, 'Ᾱ'),
+    (0xfe54, 0x7c9, 'Ῑ'),
+    (0xfe54, 0x7d5, 'Ῡ'),
+    (0xfe54, 0x7e1, 'ᾱ'),
+    (0xfe54, 0x7e9, 'ῑ'),
+    (0xfe54, 0x7f5, 'ῡ'),
+    (0xfe54, 0xfe54, '¯'),
+    (0xfe54, 0x10001ea, 'Ǭ'),
+    (0xfe54, 0x10001eb, 'ǭ'),
+    (0xfe54, 0x1000226, 'Ǡ'),
+    (0xfe54, 0x1000227, 'ǡ'),
+    (0xfe54, 0x100022e, 'Ȱ'),
+    (0xfe54, 0x100022f, 'ȱ'),
+    (0xfe54, 0x1001e36, 'Ḹ'),
+    (0xfe54, 0x1001e37, 'ḹ'),
+    (0xfe54, 0x1001e5a, 'Ṝ'),
+    (0xfe54, 0x1001e5b, 'ṝ'),
+    (0xfe55, 0x20, '˘'),
+    (0xfe55, 0x41, 'Ă'),
+    (0xfe55, 0x45, 'Ĕ'),
+    (0xfe55, 0x47, 'Ğ'),
+    (0xfe55, 0x49, 'Ĭ'),
+    (0xfe55, 0x4f, 'Ŏ'),
+    (0xfe55, 0x55, 'Ŭ'),
+    (0xfe55, 0x61, 'ă'),
+    (0xfe55, 0x65, 'ĕ'),
+    (0xfe55, 0x67, 'ğ'),
+    (0xfe55, 0x69, 'ĭ'),
+    (0xfe55, 0x6f, 'ŏ'),
+    (0xfe55, 0x75, 'ŭ'),
+    (0xfe55, 0xa0, '\u{306}'),
+    (0xfe55, 0xc0, 'Ằ'),
+    (0xfe55, 0xc1, 'Ắ'),
+    (0xfe55, 0xc3, 'Ẵ'),
+    (0xfe55, 0xe0, 'ằ'),
+    (0xfe55, 0xe1, 'ắ'),
+    (0xfe55, 0xe3, 'ẵ'),
+    (0xfe55, 0x6c1, 'ӑ'),
+    (0xfe55, 0x6c5, 'ӗ'),
+    (0xfe55, 0x6c9, 'й'),
+    (0xfe55, 0x6d5, 'ў'),
+    (0xfe55, 0x6d6, 'ӂ'),
+    (0xfe55, 0x6e1, 'Ӑ'),
+    (0xfe55, 0x6e5, 'Ӗ'),
+    (0xfe55, 0x6e9, 'Й'),
+    (0xfe55, 0x6f5, 'Ў'),
+    (0xfe55, 0x6f6, 'Ӂ'),
+    (0xfe55, 0x7c1, 'Ᾰ'),
+    (0xfe55, 0x7c9, 'Ῐ'),
+    (0xfe55, 0x7d5, 'Ῠ'),
+    (0xfe55, 0x7e1, 'ᾰ'),
+    (0xfe55, 0x7e9, 'ῐ'),
+    (0xfe55, 0x7f5, 'ῠ'),
+    (0xfe55, 0xfe55, '˘'),
+    (0xfe55, 0x1000228, 'Ḝ'),
+    (0xfe55, 0x1000229, 'ḝ'),
+    (0xfe55, 0x1001ea0, 'Ặ'),
+    (0xfe55, 0x1001ea1, 'ặ'),
+    (0xfe56, 0x20, '˙'),
+    (0xfe56, 0x41, 'Ȧ'),
+    (0xfe56, 0x42, 'Ḃ'),
+    (0xfe56, 0x43, 'Ċ'),
+    (0xfe56, 0x44, 'Ḋ'),
+    (0xfe56, 0x45, 'Ė'),
+    (0xfe56, 0x46, 'Ḟ'),
+    (0xfe56, 0x47, 'Ġ'),
+    (0xfe56, 0x48, 'Ḣ'),
+    (0xfe56, 0x49, 'İ'),
+    (0xfe56, 0x4c, 'Ŀ'),
+    (0xfe56, 0x4d, 'Ṁ'),
+    (0xfe56, 0x4e, 'Ṅ'),
+    (0xfe56, 0x4f, 'Ȯ'),
+    (0xfe56, 0x50, 'Ṗ'),
+    (0xfe56, 0x52, 'Ṙ'),
+    (0xfe56, 0x53, 'Ṡ'),
+    (0xfe56, 0x54, 'Ṫ'),
+    (0xfe56, 0x57, 'Ẇ'),
+    (0xfe56, 0x58, 'Ẋ'),
+    (0xfe56, 0x59, 'Ẏ'),
+    (0xfe56, 0x5a, 'Ż'),
+    (0xfe56, 0x61, 'ȧ'),
+    (0xfe56, 0x62, 'ḃ'),
+    (0xfe56, 0x63, 'ċ'),
+    (0xfe56, 0x64, 'ḋ'),
+    (0xfe56, 0x65, 'ė'),
+    (0xfe56, 0x66, 'ḟ'),
+    (0xfe56, 0x67, 'ġ'),
+    (0xfe56, 0x68, 'ḣ'),
+    (0xfe56, 0x69, 'ı'),
+    (0xfe56, 0x6a, 'ȷ'),
+    (0xfe56, 0x6c, 'ŀ'),
+    (0xfe56, 0x6d, 'ṁ'),
+    (0xfe56, 0x6e, 'ṅ'),
+    (0xfe56, 0x6f, 'ȯ'),
+    (0xfe56, 0x70, 'ṗ'),
+    (0xfe56, 0x72, 'ṙ'),
+    (0xfe56, 0x73, 'ṡ'),
+    (0xfe56, 0x74, 'ṫ'),
+    (0xfe56, 0x77, 'ẇ'),
+    (0xfe56, 0x78, 'ẋ'),
+    (0xfe56, 0x79, 'ẏ'),
+    (0xfe56, 0x7a, 'ż'),
+    (0xfe56, 0xa0, '\u{307}'),
+    (0xfe56, 0x1a6, 'Ṥ'),
+    (0xfe56, 0x1a9, 'Ṧ'),
+    (0xfe56, 0x1b6, 'ṥ'),
+    (0xfe56, 0x1b9, 'ṧ'),
+    (0xfe56, 0xfe56, '˙'),
+    (0xfe56, 0x100017f, 'ẛ'),
+    (0xfe56, 0x1001e62, 'Ṩ'),
+    (0xfe56, 0x1001e63, 'ṩ'),
+    (0xfe57, 0x20, '"'),
+    (0xfe57, 0x27, '\u{344}'),
+    (0xfe57, 0x41, 'Ä'),
+    (0xfe57, 0x45, 'Ë'),
+    (0xfe57, 0x48, 'Ḧ'),
+    (0xfe57, 0x49, 'Ï'),
+    (0xfe57, 0x4f, 'Ö'),
+    (0xfe57, 0x55, 'Ü'),
+    (0xfe57, 0x57, 'Ẅ'),
+    (0xfe57, 0x58, 'Ẍ'),
+    (0xfe57, 0x59, 'Ÿ'),
+    (0xfe57, 0x61, 'ä'),
+    (0xfe57, 0x65, 'ë'),
+    (0xfe57, 0x68, 'ḧ'),
+    (0xfe57, 0x69, 'ï'),
+    (0xfe57, 0x6f, 'ö'),
+    (0xfe57, 0x74, 'ẗ'),
+    (0xfe57, 0x75, 'ü'),
+    (0xfe57, 0x77, 'ẅ'),
+    (0xfe57, 0x78, 'ẍ'),
+    (0xfe57, 0x79, 'ÿ'),
+    (0xfe57, 0xa0, '\u{308}'),
+    (0xfe57, 0xb4, '\u{344}'),
+    (0xfe57, 0xcd, 'Ḯ'),
+    (0xfe57, 0xd5, 'Ṏ'),
+    (0xfe57, 0xd9, 'Ǜ'),
+    (0xfe57, 0xda, 'Ǘ'),
+    (0xfe57, 0xed, 'ḯ'),
+    (0xfe57, 0xf5, 'ṏ'),
+    (0xfe57, 0xf9, 'ǜ'),
+    (0xfe57, 0xfa, 'ǘ'),
+    (0xfe57, 0x3de, 'Ṻ'),
+    (0xfe57, 0x3fe, 'ṻ'),
+    (0xfe57, 0x6a6, 'ї'),
+    (0xfe57, 0x6b6, 'Ї'),
+    (0xfe57, 0x6c1, 'ӓ'),
+    (0xfe57, 0x6c5, 'ё'),
+    (0xfe57, 0x6c9, 'ӥ'),
+    (0xfe57, 0x6cf, 'ӧ'),
+    (0xfe57, 0x6d5, 'ӱ'),
+    (0xfe57, 0x6d6, 'ӝ'),
+    (0xfe57, 0x6d9, 'ӹ'),
+    (0xfe57, 0x6da, 'ӟ'),
+    (0xfe57, 0x6dc, 'ӭ'),
+    (0xfe57, 0x6de, 'ӵ'),
+    (0xfe57, 0x6e1, 'Ӓ'),
+    (0xfe57, 0x6e5, 'Ё'),
+    (0xfe57, 0x6e9, 'Ӥ'),
+    (0xfe57, 0x6ef, 'Ӧ'),
+    (0xfe57, 0x6f5, 'Ӱ'),
+    (0xfe57, 0x6f6, 'Ӝ'),
+    (0xfe57, 0x6f9, 'Ӹ'),
+    (0xfe57, 0x6fa, 'Ӟ'),
+    (0xfe57, 0x6fc, 'Ӭ'),
+    (0xfe57, 0x6fe, 'Ӵ'),
+    (0xfe57, 0x7c9, 'Ϊ'),
+    (0xfe57, 0x7d5, 'Ϋ'),
+    (0xfe57, 0x7e9, 'ϊ'),
+    (0xfe57, 0x7f5, 'ϋ'),
+    (0xfe57, 0xfe57, '¨'),
+    (0xfe57, 0x10001d3, 'Ǚ'),
+    (0xfe57, 0x10001d4, 'ǚ'),
+    (0xfe57, 0x10004d8, 'Ӛ'),
+    (0xfe57, 0x10004d9, 'ӛ'),
+    (0xfe57, 0x10004e8, 'Ӫ'),
+    (0xfe57, 0x10004e9, 'ӫ'),
+    (0xfe58, 0x20, '°'),
+    (0xfe58, 0x41, 'Å'),
+    (0xfe58, 0x55, 'Ů'),
+    (0xfe58, 0x61, 'å'),
+    (0xfe58, 0x75, 'ů'),
+    (0xfe58, 0x77, 'ẘ'),
+    (0xfe58, 0x79, 'ẙ'),
+    (0xfe58, 0xa0, '\u{30a}'),
+    (0xfe58, 0xc1, 'Ǻ'),
+    (0xfe58, 0xe1, 'ǻ'),
+    (0xfe58, 0xfe58, '°'),
+    (0xfe59, 0x20, '˝'),
+    (0xfe59, 0x4f, 'Ő'),
+    (0xfe59, 0x55, 'Ű'),
+    (0xfe59, 0x6f, 'ő'),
+    (0xfe59, 0x75, 'ű'),
+    (0xfe59, 0xa0, '\u{30b}'),
+    (0xfe59, 0x6d5, 'ӳ'),
+    (0xfe59, 0x6f5, 'Ӳ'),
+    (0xfe59, 0xfe59, '˝'),
+    (0xfe5a, 0x20, 'ˇ'),
+    (0xfe5a, 0x28, '₍'),
+    (0xfe5a, 0x29, '₎'),
+    (0xfe5a, 0x2b, '₊'),
+    (0xfe5a, 0x2d, '₋'),
+    (0xfe5a, 0x30, '₀'),
+    (0xfe5a, 0x31, '₁'),
+    (0xfe5a, 0x32, '₂'),
+    (0xfe5a, 0x33, '₃'),
+    (0xfe5a, 0x34, '₄'),
+    (0xfe5a, 0x35, '₅'),
+    (0xfe5a, 0x36, '₆'),
+    (0xfe5a, 0x37, '₇'),
+    (0xfe5a, 0x38, '₈'),
+    (0xfe5a, 0x39, '₉'),
+    (0xfe5a, 0x3d, '₌'),
+    (0xfe5a, 0x41, 'Ǎ'),
+    (0xfe5a, 0x43, 'Č'),
+    (0xfe5a, 0x44, 'Ď'),
+    (0xfe5a, 0x45, 'Ě'),
+    (0xfe5a, 0x47, 'Ǧ'),
+    (0xfe5a, 0x48, 'Ȟ'),
+    (0xfe5a, 0x49, 'Ǐ'),
+    (0xfe5a, 0x4b, 'Ǩ'),
+    (0xfe5a, 0x4c, 'Ľ'),
+    (0xfe5a, 0x4e, 'Ň'),
+    (0xfe5a, 0x4f, 'Ǒ'),
+    (0xfe5a, 0x52, 'Ř'),
+    (0xfe5a, 0x53, 'Š'),
+    (0xfe5a, 0x54, 'Ť'),
+    (0xfe5a, 0x55, 'Ǔ'),
+    (0xfe5a, 0x56, 'Ǚ'),
+    (0xfe5a, 0x5a, 'Ž'),
+    (0xfe5a, 0x61, 'ǎ'),
+    (0xfe5a, 0x63, 'č'),
+    (0xfe5a, 0x64, 'ď'),
+    (0xfe5a, 0x65, 'ě'),
+    (0xfe5a, 0x67, 'ǧ'),
+    (0xfe5a, 0x68, 'ȟ'),
+    (0xfe5a, 0x69, 'ǐ'),
+    (0xfe5a, 0x6a, 'ǰ'),
+    (0xfe5a, 0x6b, 'ǩ'),
+    (0xfe5a, 0x6c, 'ľ'),
+    (0xfe5a, 0x6e, 'ň'),
+    (0xfe5a, 0x6f, 'ǒ'),
+    (0xfe5a, 0x72, 'ř'),
+    (0xfe5a, 0x73, 'š'),
+    (0xfe5a, 0x74, 'ť'),
+    (0xfe5a, 0x75, 'ǔ'),
+    (0xfe5a, 0x76, 'ǚ'),
+    (0xfe5a, 0x7a, 'ž'),
+    (0xfe5a, 0xa0, '\u{30c}'),
+    (0xfe5a, 0xdc, 'Ǚ'),
+    (0xfe5a, 0xfc, 'ǚ'),
+    (0xfe5a, 0xfe5a, 'ˇ'),
+    (0xfe5a, 0x10001b7, 'Ǯ'),
+    (0xfe5a, 0x10001f2, 'ǅ'),
+    (0xfe5a, 0x1000292, 'ǯ'),
+    (0xfe5b, 0x20, '¸'),
+    (0xfe5b, 0x43, 'Ç'),
+    (0xfe5b, 0x44, 'Ḑ'),
+    (0xfe5b, 0x45, 'Ȩ'),
+    (0xfe5b, 0x47, 'Ģ'),
+    (0xfe5b, 0x48, 'Ḩ'),
+    (0xfe5b, 0x4b, 'Ķ'),
+    (0xfe5b, 0x4c, 'Ļ'),
+    (0xfe5b, 0x4e, 'Ņ'),
+    (0xfe5b, 0x52, 'Ŗ'),
+    (0xfe5b, 0x53, 'Ş'),
+    (0xfe5b, 0x54, 'Ţ'),
+    (0xfe5b, 0x63, 'ç'),
+    (0xfe5b, 0x64, 'ḑ'),
+    (0xfe5b, 0x65, 'ȩ'),
+    (0xfe5b, 0x67, 'ģ'),
+    (0xfe5b, 0x68, 'ḩ'),
+    (0xfe5b, 0x6b, 'ķ'),
+    (0xfe5b, 0x6c, 'ļ'),
+    (0xfe5b, 0x6e, 'ņ'),
+    (0xfe5b, 0x72, 'ŗ'),
+    (0xfe5b, 0x73, 'ş'),
+    (0xfe5b, 0x74, 'ţ'),
+    (0xfe5b, 0xa0, '\u{327}'),
+    (0xfe5b, 0xa2, '₵'),
+    (0xfe5b, 0x1c6, 'Ḉ'),
+    (0xfe5b, 0x1e6, 'ḉ'),
+    (0xfe5b, 0xfe5b, '¸'),
+    (0xfe5b, 0x1000114, 'Ḝ'),
+    (0xfe5b, 0x1000115, 'ḝ'),
+    (0xfe5c, 0x20, '˛'),
+    (0xfe5c, 0x41, 'Ą'),
+    (0xfe5c, 0x45, 'Ę'),
+    (0xfe5c, 0x49, 'Į'),
+    (0xfe5c, 0x4f, 'Ǫ'),
+    (0xfe5c, 0x55, 'Ų'),
+    (0xfe5c, 0x61, 'ą'),
+    (0xfe5c, 0x65, 'ę'),
+    (0xfe5c, 0x69, 'į'),
+    (0xfe5c, 0x6f, 'ǫ'),
+    (0xfe5c, 0x75, 'ų'),
+    (0xfe5c, 0xa0, '\u{328}'),
+    (0xfe5c, 0xfe5c, '˛'),
+    (0xfe5d, 0x20, 'ͺ'),
+    (0xfe5d, 0x7b1, 'ᾴ'),
+    (0xfe5d, 0x7b3, 'ῄ'),
+    (0xfe5d, 0x7bb, 'ῴ'),
+    (0xfe5d, 0x7c1, 'ᾼ'),
+    (0xfe5d, 0x7c7, 'ῌ'),
+    (0xfe5d, 0x7d9, 'ῼ'),
+    (0xfe5d, 0x7e1, 'ᾳ'),
+    (0xfe5d, 0x7e7, 'ῃ'),
+    (0xfe5d, 0x7f9, 'ῳ'),
+    (0xfe5d, 0xfe5d, 'ͺ'),
+    (0xfe5d, 0x1001f00, 'ᾀ'),
+    (0xfe5d, 0x1001f01, 'ᾁ'),
+    (0xfe5d, 0x1001f02, 'ᾂ'),
+    (0xfe5d, 0x1001f03, 'ᾃ'),
+    (0xfe5d, 0x1001f04, 'ᾄ'),
+    (0xfe5d, 0x1001f05, 'ᾅ'),
+    (0xfe5d, 0x1001f06, 'ᾆ'),
+    (0xfe5d, 0x1001f07, 'ᾇ'),
+    (0xfe5d, 0x1001f08, 'ᾈ'),
+    (0xfe5d, 0x1001f09, 'ᾉ'),
+    (0xfe5d, 0x1001f0a, 'ᾊ'),
+    (0xfe5d, 0x1001f0b, 'ᾋ'),
+    (0xfe5d, 0x1001f0c, 'ᾌ'),
+    (0xfe5d, 0x1001f0d, 'ᾍ'),
+    (0xfe5d, 0x1001f0e, 'ᾎ'),
+    (0xfe5d, 0x1001f0f, 'ᾏ'),
+    (0xfe5d, 0x1001f20, 'ᾐ'),
+    (0xfe5d, 0x1001f21, 'ᾑ'),
+    (0xfe5d, 0x1001f22, 'ᾒ'),
+    (0xfe5d, 0x1001f23, 'ᾓ'),
+    (0xfe5d, 0x1001f24, 'ᾔ'),
+    (0xfe5d, 0x1001f25, 'ᾕ'),
+    (0xfe5d, 0x1001f26, 'ᾖ'),
+    (0xfe5d, 0x1001f27, 'ᾗ'),
+    (0xfe5d, 0x1001f28, 'ᾘ'),
+    (0xfe5d, 0x1001f29, 'ᾙ'),
+    (0xfe5d, 0x1001f2a, 'ᾚ'),
+    (0xfe5d, 0x1001f2b, 'ᾛ'),
+    (0xfe5d, 0x1001f2c, 'ᾜ'),
+    (0xfe5d, 0x1001f2d, 'ᾝ'),
+    (0xfe5d, 0x1001f2e, 'ᾞ'),
+    (0xfe5d, 0x1001f2f, 'ᾟ'),
+    (0xfe5d, 0x1001f60, 'ᾠ'),
+    (0xfe5d, 0x1001f61, 'ᾡ'),
+    (0xfe5d, 0x1001f62, 'ᾢ'),
+    (0xfe5d, 0x1001f63, 'ᾣ'),
+    (0xfe5d, 0x1001f64, 'ᾤ'),
+    (0xfe5d, 0x1001f65, 'ᾥ'),
+    (0xfe5d, 0x1001f66, 'ᾦ'),
+    (0xfe5d, 0x1001f67, 'ᾧ'),
+    (0xfe5d, 0x1001f68, 'ᾨ'),
+    (0xfe5d, 0x1001f69, 'ᾩ'),
+    (0xfe5d, 0x1001f6a, 'ᾪ'),
+    (0xfe5d, 0x1001f6b, 'ᾫ'),
+    (0xfe5d, 0x1001f6c, 'ᾬ'),
+    (0xfe5d, 0x1001f6d, 'ᾭ'),
+    (0xfe5d, 0x1001f6e, 'ᾮ'),
+    (0xfe5d, 0x1001f6f, 'ᾯ'),
+    (0xfe5d, 0x1001f70, 'ᾲ'),
+    (0xfe5d, 0x1001f74, 'ῂ'),
+    (0xfe5d, 0x1001f7c, 'ῲ'),
+    (0xfe5d, 0x1001fb6, 'ᾷ'),
+    (0xfe5d, 0x1001fc6, 'ῇ'),
+    (0xfe5d, 0x1001ff6, 'ῷ'),
+    (0xfe5e, 0x4a6, 'ヺ'),
+    (0xfe5e, 0x4b3, 'ヴ'),
+    (0xfe5e, 0x4b6, 'ガ'),
+    (0xfe5e, 0x4b7, 'ギ'),
+    (0xfe5e, 0x4b8, 'グ'),
+    (0xfe5e, 0x4b9, 'ゲ'),
+    (0xfe5e, 0x4ba, 'ゴ'),
+    (0xfe5e, 0x4bb, 'ザ'),
+    (0xfe5e, 0x4bc, 'ジ'),
+    (0xfe5e, 0x4bd, 'ズ'),
+    (0xfe5e, 0x4be, 'ゼ'),
+    (0xfe5e, 0x4bf, 'ゾ'),
+    (0xfe5e, 0x4c0, 'ダ'),
+    (0xfe5e, 0x4c1, 'ヂ'),
+    (0xfe5e, 0x4c2, 'ヅ'),
+    (0xfe5e, 0x4c3, 'デ'),
+    (0xfe5e, 0x4c4, 'ド'),
+    (0xfe5e, 0x4ca, 'バ'),
+    (0xfe5e, 0x4cb, 'ビ'),
+    (0xfe5e, 0x4cc, 'ブ'),
+    (0xfe5e, 0x4cd, 'ベ'),
+    (0xfe5e, 0x4ce, 'ボ'),
+    (0xfe5e, 0x4dc, 'ヷ'),
+    (0xfe5e, 0x1003046, 'ゔ'),
+    (0xfe5e, 0x100304b, 'が'),
+    (0xfe5e, 0x100304d, 'ぎ'),
+    (0xfe5e, 0x100304f, 'ぐ'),
+    (0xfe5e, 0x1003051, 'げ'),
+    (0xfe5e, 0x1003053, 'ご'),
+    (0xfe5e, 0x1003055, 'ざ'),
+    (0xfe5e, 0x1003057, 'じ'),
+    (0xfe5e, 0x1003059, 'ず'),
+    (0xfe5e, 0x100305b, 'ぜ'),
+    (0xfe5e, 0x100305d, 'ぞ'),
+    (0xfe5e, 0x100305f, 'だ'),
+    (0xfe5e, 0x1003061, 'ぢ'),
+    (0xfe5e, 0x1003064, 'づ'),
+    (0xfe5e, 0x1003066, 'で'),
+    (0xfe5e, 0x1003068, 'ど'),
+    (0xfe5e, 0x100306f, 'ば'),
+    (0xfe5e, 0x1003072, 'び'),
+    (0xfe5e, 0x1003075, 'ぶ'),
+    (0xfe5e, 0x1003078, 'べ'),
+    (0xfe5e, 0x100307b, 'ぼ'),
+    (0xfe5e, 0x100309d, 'ゞ'),
+    (0xfe5e, 0x10030f0, 'ヸ'),
+    (0xfe5e, 0x10030f1, 'ヹ'),
+    (0xfe5e, 0x10030fd, 'ヾ'),
+    (0xfe5f, 0x4ca, 'パ'),
+    (0xfe5f, 0x4cb, 'ピ'),
+    (0xfe5f, 0x4cc, 'プ'),
+    (0xfe5f, 0x4cd, 'ペ'),
+    (0xfe5f, 0x4ce, 'ポ'),
+    (0xfe5f, 0x100306f, 'ぱ'),
+    (0xfe5f, 0x1003072, 'ぴ'),
+    (0xfe5f, 0x1003075, 'ぷ'),
+    (0xfe5f, 0x1003078, 'ぺ'),
+    (0xfe5f, 0x100307b, 'ぽ'),
+    (0xfe60, 0x20, '\u{323}'),
+    (0xfe60, 0x2b, '⨥'),
+    (0xfe60, 0x2d, '⨪'),
+    (0xfe60, 0x3d, '⩦'),
+    (0xfe60, 0x41, 'Ạ'),
+    (0xfe60, 0x42, 'Ḅ'),
+    (0xfe60, 0x44, 'Ḍ'),
+    (0xfe60, 0x45, 'Ẹ'),
+    (0xfe60, 0x48, 'Ḥ'),
+    (0xfe60, 0x49, 'Ị'),
+    (0xfe60, 0x4b, 'Ḳ'),
+    (0xfe60, 0x4c, 'Ḷ'),
+    (0xfe60, 0x4d, 'Ṃ'),
+    (0xfe60, 0x4e, 'Ṇ'),
+    (0xfe60, 0x4f, 'Ọ'),
+    (0xfe60, 0x52, 'Ṛ'),
+    (0xfe60, 0x53, 'Ṣ'),
+    (0xfe60, 0x54, 'Ṭ'),
+    (0xfe60, 0x55, 'Ụ'),
+    (0xfe60, 0x56, 'Ṿ'),
+    (0xfe60, 0x57, 'Ẉ'),
+    (0xfe60, 0x59, 'Ỵ'),
+    (0xfe60, 0x5a, 'Ẓ'),
+    (0xfe60, 0x61, 'ạ'),
+    (0xfe60, 0x62, 'ḅ'),
+    (0xfe60, 0x64, 'ḍ'),
+    (0xfe60, 0x65, 'ẹ'),
+    (0xfe60, 0x68, 'ḥ'),
+    (0xfe60, 0x69, 'ị'),
+    (0xfe60, 0x6b, 'ḳ'),
+    (0xfe60, 0x6c, 'ḷ'),
+    (0xfe60, 0x6d, 'ṃ'),
+    (0xfe60, 0x6e, 'ṇ'),
+    (0xfe60, 0x6f, 'ọ'),
+    (0xfe60, 0x72, 'ṛ'),
+    (0xfe60, 0x73, 'ṣ'),
+    (0xfe60, 0x74, 'ṭ'),
+    (0xfe60, 0x75, 'ụ'),
+    (0xfe60, 0x76, 'ṿ'),
+    (0xfe60, 0x77, 'ẉ'),
+    (0xfe60, 0x79, 'ỵ'),
+    (0xfe60, 0x7a, 'ẓ'),
+    (0xfe60, 0xa0, '\u{323}'),
+    (0xfe60, 0xc2, 'Ậ'),
+    (0xfe60, 0xca, 'Ệ'),
+    (0xfe60, 0xd4, 'Ộ'),
+    (0xfe60, 0xe2, 'ậ'),
+    (0xfe60, 0xea, 'ệ'),
+    (0xfe60, 0xf4, 'ộ'),
+    (0xfe60, 0x1c3, 'Ặ'),
+    (0xfe60, 0x1e3, 'ặ'),
+    (0xfe60, 0xfe60, '\u{323}'),
+    (0xfe60, 0x10001a0, 'Ợ'),
+    (0xfe60, 0x10001a1, 'ợ'),
+    (0xfe60, 0x10001af, 'Ự'),
+    (0xfe60, 0x10001b0, 'ự'),
+    (0xfe61, 0x20, '\u{309}'),
+    (0xfe61, 0x41, 'Ả'),
+    (0xfe61, 0x42, 'Ɓ'),
+    (0xfe61, 0x43, 'Ƈ'),
+    (0xfe61, 0x44, 'Ɗ'),
+    (0xfe61, 0x45, 'Ẻ'),
+    (0xfe61, 0x46, 'Ƒ'),
+    (0xfe61, 0x47, 'Ɠ'),
+    (0xfe61, 0x49, 'Ỉ'),
+    (0xfe61, 0x4b, 'Ƙ'),
+    (0xfe61, 0x4d, 'Ɱ'),
+    (0xfe61, 0x4e, 'Ɲ'),
+    (0xfe61, 0x4f, 'Ỏ'),
+    (0xfe61, 0x50, 'Ƥ'),
+    (0xfe61, 0x54, 'Ƭ'),
+    (0xfe61, 0x55, 'Ủ'),
+    (0xfe61, 0x56, 'Ʋ'),
+    (0xfe61, 0x57, 'Ⱳ'),
+    (0xfe61, 0x59, 'Ỷ'),
+    (0xfe61, 0x5a, 'Ȥ'),
+    (0xfe61, 0x61, 'ả'),
+    (0xfe61, 0x62, 'ɓ'),
+    (0xfe61, 0x63, 'ƈ'),
+    (0xfe61, 0x64, 'ɗ'),
+    (0xfe61, 0x65, 'ẻ'),
+    (0xfe61, 0x66, 'ƒ'),
+    (0xfe61, 0x67, 'ɠ'),
+    (0xfe61, 0x68, 'ɦ'),
+    (0xfe61, 0x69, 'ỉ'),
+    (0xfe61, 0x6b, 'ƙ'),
+    (0xfe61, 0x6d, 'ɱ'),
+    (0xfe61, 0x6e, 'ɲ'),
+    (0xfe61, 0x6f, 'ỏ'),
+    (0xfe61, 0x70, 'ƥ'),
+    (0xfe61, 0x71, 'ʠ'),
+    (0xfe61, 0x72, 'ɼ'),
+    (0xfe61, 0x73, 'ʂ'),
+    (0xfe61, 0x74, 'ƭ'),
+    (0xfe61, 0x75, 'ủ'),
+    (0xfe61, 0x76, 'ʋ'),
+    (0xfe61, 0x77, 'ⱳ'),
+    (0xfe61, 0x79, 'ỷ'),
+    (0xfe61, 0x7a, 'ȥ'),
+    (0xfe61, 0xa0, '\u{309}'),
+    (0xfe61, 0xc2, 'Ẩ'),
+    (0xfe61, 0xca, 'Ể'),
+    (0xfe61, 0xd4, 'Ổ'),
+    (0xfe61, 0xe2, 'ẩ'),
+    (0xfe61, 0xea, 'ể'),
+    (0xfe61, 0xf4, 'ổ'),
+    (0xfe61, 0x1c3, 'Ẳ'),
+    (0xfe61, 0x1e3, 'ẳ'),
+    (0xfe61, 0xfe61, '\u{309}'),
+    (0xfe61, 0x10001a0, 'Ở'),
+    (0xfe61, 0x10001a1, 'ở'),
+    (0xfe61, 0x10001af, 'Ử'),
+    (0xfe61, 0x10001b0, 'ử'),
+    (0xfe61, 0x1000259, 'ɚ'),
+    (0xfe62, 0x20, '\u{31b}'),
+    (0xfe62, 0x4f, 'Ơ'),
+    (0xfe62, 0x55, 'Ư'),
+    (0xfe62, 0x6f, 'ơ'),
+    (0xfe62, 0x75, 'ư'),
+    (0xfe62, 0xa0, '\u{31b}'),
+    (0xfe62, 0xd2, 'Ờ'),
+    (0xfe62, 0xd3, 'Ớ'),
+    (0xfe62, 0xd9, 'Ừ'),
+    (0xfe62, 0xda, 'Ứ'),
+    (0xfe62, 0xf2, 'ờ'),
+    (0xfe62, 0xf3, 'ớ'),
+    (0xfe62, 0xf9, 'ừ'),
+    (0xfe62, 0xfa, 'ứ'),
+    (0xfe62, 0xfe62, '\u{31b}'),
+    (0xfe62, 0x1001ece, 'Ở'),
+    (0xfe62, 0x1001ecf, 'ở'),
+    (0xfe62, 0x1001ee6, 'Ử'),
+    (0xfe62, 0x1001ee7, 'ử'),
+    (0xfe63, 0x20, '/'),
+    (0xfe63, 0x32, 'ƻ'),
+    (0xfe63, 0x3c, '≮'),
+    (0xfe63, 0x3d, '≠'),
+    (0xfe63, 0x3e, '≯'),
+    (0xfe63, 0x41, 'Ⱥ'),
+    (0xfe63, 0x42, 'Ƀ'),
+    (0xfe63, 0x43, 'Ȼ'),
+    (0xfe63, 0x44, 'Đ'),
+    (0xfe63, 0x45, 'Ɇ'),
+    (0xfe63, 0x47, 'Ǥ'),
+    (0xfe63, 0x48, 'Ħ'),
+    (0xfe63, 0x49, 'Ɨ'),
+    (0xfe63, 0x4a, 'Ɉ'),
+    (0xfe63, 0x4c, 'Ł'),
+    (0xfe63, 0x4f, 'Ø'),
+    (0xfe63, 0x50, 'Ᵽ'),
+    (0xfe63, 0x52, 'Ɍ'),
+    (0xfe63, 0x54, 'Ŧ'),
+    (0xfe63, 0x55, 'Ʉ'),
+    (0xfe63, 0x59, 'Ɏ'),
+    (0xfe63, 0x5a, 'Ƶ'),
+    (0xfe63, 0x61, 'ⱥ'),
+    (0xfe63, 0x62, 'ƀ'),
+    (0xfe63, 0x63, 'ȼ'),
+    (0xfe63, 0x64, 'đ'),
+    (0xfe63, 0x65, 'ɇ'),
+    (0xfe63, 0x67, 'ǥ'),
+    (0xfe63, 0x68, 'ħ'),
+    (0xfe63, 0x69, 'ɨ'),
+    (0xfe63, 0x6a, 'ɉ'),
+    (0xfe63, 0x6c, 'ł'),
+    (0xfe63, 0x6f, 'ø'),
+    (0xfe63, 0x70, 'ᵽ'),
+    (0xfe63, 0x72, 'ɍ'),
+    (0xfe63, 0x74, 'ŧ'),
+    (0xfe63, 0x75, 'ʉ'),
+    (0xfe63, 0x79, 'ɏ'),
+    (0xfe63, 0x7a, 'ƶ'),
+    (0xfe63, 0xa0, '\u{338}'),
+    (0xfe63, 0xd3, 'Ǿ'),
+    (0xfe63, 0xf3, 'ǿ'),
+    (0xfe63, 0x8bc, '≰'),
+    (0xfe63, 0x8be, '≱'),
+    (0xfe63, 0xfe63, '/'),
+    (0xfe63, 0x1000237, 'ɟ'),
+    (0xfe63, 0x1000269, 'ᵼ'),
+    (0xfe64, 0x7c1, 'Ἀ'),
+    (0xfe64, 0x7c5, 'Ἐ'),
+    (0xfe64, 0x7c7, 'Ἠ'),
+    (0xfe64, 0x7c9, 'Ἰ'),
+    (0xfe64, 0x7cf, 'Ὀ'),
+    (0xfe64, 0x7d9, 'Ὠ'),
+    (0xfe64, 0x7e1, 'ἀ'),
+    (0xfe64, 0x7e5, 'ἐ'),
+    (0xfe64, 0x7e7, 'ἠ'),
+    (0xfe64, 0x7e9, 'ἰ'),
+    (0xfe64, 0x7ef, 'ὀ'),
+    (0xfe64, 0x7f1, 'ῤ'),
+    (0xfe64, 0x7f5, 'ὐ'),
+    (0xfe64, 0x7f9, 'ὠ'),
+    (0xfe65, 0x7c1, 'Ἁ'),
+    (0xfe65, 0x7c5, 'Ἑ'),
+    (0xfe65, 0x7c7, 'Ἡ'),
+    (0xfe65, 0x7c9, 'Ἱ'),
+    (0xfe65, 0x7cf, 'Ὁ'),
+    (0xfe65, 0x7d1, 'Ῥ'),
+    (0xfe65, 0x7d5, 'Ὑ'),
+    (0xfe65, 0x7d9, 'Ὡ'),
+    (0xfe65, 0x7e1, 'ἁ'),
+    (0xfe65, 0x7e5, 'ἑ'),
+    (0xfe65, 0x7e7, 'ἡ'),
+    (0xfe65, 0x7e9, 'ἱ'),
+    (0xfe65, 0x7ef, 'ὁ'),
+    (0xfe65, 0x7f1, 'ῥ'),
+    (0xfe65, 0x7f5, 'ὑ'),
+    (0xfe65, 0x7f9, 'ὡ'),
+    (0xfe66, 0x41, 'Ȁ'),
+    (0xfe66, 0x45, 'Ȅ'),
+    (0xfe66, 0x49, 'Ȉ'),
+    (0xfe66, 0x4f, 'Ȍ'),
+    (0xfe66, 0x52, 'Ȑ'),
+    (0xfe66, 0x55, 'Ȕ'),
+    (0xfe66, 0x61, 'ȁ'),
+    (0xfe66, 0x65, 'ȅ'),
+    (0xfe66, 0x69, 'ȉ'),
+    (0xfe66, 0x6f, 'ȍ'),
+    (0xfe66, 0x72, 'ȑ'),
+    (0xfe66, 0x75, 'ȕ'),
+    (0xfe66, 0x1000474, 'Ѷ'),
+    (0xfe66, 0x1000475, 'ѷ'),
+    (0xfe67, 0x41, 'Ḁ'),
+    (0xfe67, 0x61, 'ḁ'),
+    (0xfe67, 0x7c, '⫰'),
+    (0xfe68, 0x42, 'Ḇ'),
+    (0xfe68, 0x44, 'Ḏ'),
+    (0xfe68, 0x4b, 'Ḵ'),
+    (0xfe68, 0x4c, 'Ḻ'),
+    (0xfe68, 0x4e, 'Ṉ'),
+    (0xfe68, 0x52, 'Ṟ'),
+    (0xfe68, 0x54, 'Ṯ'),
+    (0xfe68, 0x5a, 'Ẕ'),
+    (0xfe68, 0x62, 'ḇ'),
+    (0xfe68, 0x64, 'ḏ'),
+    (0xfe68, 0x68, 'ẖ'),
+    (0xfe68, 0x6b, 'ḵ'),
+    (0xfe68, 0x6c, 'ḻ'),
+    (0xfe68, 0x6e, 'ṉ'),
+    (0xfe68, 0x72, 'ṟ'),
+    (0xfe68, 0x74, 'ṯ'),
+    (0xfe68, 0x7a, 'ẕ'),
+    (0xfe69, 0x44, 'Ḓ'),
+    (0xfe69, 0x45, 'Ḙ'),
+    (0xfe69, 0x4c, 'Ḽ'),
+    (0xfe69, 0x4e, 'Ṋ'),
+    (0xfe69, 0x54, 'Ṱ'),
+    (0xfe69, 0x55, 'Ṷ'),
+    (0xfe69, 0x64, 'ḓ'),
+    (0xfe69, 0x65, 'ḙ'),
+    (0xfe69, 0x6c, 'ḽ'),
+    (0xfe69, 0x6e, 'ṋ'),
+    (0xfe69, 0x74, 'ṱ'),
+    (0xfe69, 0x75, 'ṷ'),
+    (0xfe6a, 0x2b, '⨦'),
+    (0xfe6a, 0x45, 'Ḛ'),
+    (0xfe6a, 0x49, 'Ḭ'),
+    (0xfe6a, 0x55, 'Ṵ'),
+    (0xfe6a, 0x65, 'ḛ'),
+    (0xfe6a, 0x69, 'ḭ'),
+    (0xfe6a, 0x75, 'ṵ'),
+    (0xfe6b, 0x48, 'Ḫ'),
+    (0xfe6b, 0x68, 'ḫ'),
+    (0xfe6c, 0x55, 'Ṳ'),
+    (0xfe6c, 0x75, 'ṳ'),
+    (0xfe6d, 0x41, 'Ȃ'),
+    (0xfe6d, 0x45, 'Ȇ'),
+    (0xfe6d, 0x49, 'Ȋ'),
+    (0xfe6d, 0x4f, 'Ȏ'),
+    (0xfe6d, 0x52, 'Ȓ'),
+    (0xfe6d, 0x55, 'Ȗ'),
+    (0xfe6d, 0x61, 'ȃ'),
+    (0xfe6d, 0x65, 'ȇ'),
+    (0xfe6d, 0x69, 'ȋ'),
+    (0xfe6d, 0x6f, 'ȏ'),
+    (0xfe6d, 0x72, 'ȓ'),
+    (0xfe6d, 0x75, 'ȗ'),
+    (0xfe6e, 0x20, ','),
+    (0xfe6e, 0x53, 'Ș'),
+    (0xfe6e, 0x54, 'Ț'),
+    (0xfe6e, 0x73, 'ș'),
+    (0xfe6e, 0x74, 'ț'),
+    (0xfe6e, 0xa0, '\u{326}'),
+    (0xfe6e, 0xfe6e, ','),
+    (0xfe6f, 0x20, '¤'),
+    (0xfe6f, 0x41, '₳'),
+    (0xfe6f, 0x42, '₱'),
+    (0xfe6f, 0x43, '₡'),
+    (0xfe6f, 0x44, '₯'),
+    (0xfe6f, 0x45, '₠'),
+    (0xfe6f, 0x46, '₣'),
+    (0xfe6f, 0x47, '₲'),
+    (0xfe6f, 0x48, '₴'),
+    (0xfe6f, 0x49, '៛'),
+    (0xfe6f, 0x4b, '₭'),
+    (0xfe6f, 0x4c, '₤'),
+    (0xfe6f, 0x4d, 'ℳ'),
+    (0xfe6f, 0x4e, '₦'),
+    (0xfe6f, 0x4f, '૱'),
+    (0xfe6f, 0x50, '₧'),
+    (0xfe6f, 0x52, '₨'),
+    (0xfe6f, 0x53, '$'),
+    (0xfe6f, 0x54, '₮'),
+    (0xfe6f, 0x55, '圓'),
+    (0xfe6f, 0x57, '₩'),
+    (0xfe6f, 0x59, '円'),
+    (0xfe6f, 0x61, '؋'),
+    (0xfe6f, 0x62, '฿'),
+    (0xfe6f, 0x63, '¢'),
+    (0xfe6f, 0x64, '₫'),
+    (0xfe6f, 0x65, '€'),
+    (0xfe6f, 0x66, 'ƒ'),
+    (0xfe6f, 0x67, '₲'),
+    (0xfe6f, 0x68, '₴'),
+    (0xfe6f, 0x69, '﷼'),
+    (0xfe6f, 0x6b, '₭'),
+    (0xfe6f, 0x6c, '£'),
+    (0xfe6f, 0x6d, '₥'),
+    (0xfe6f, 0x6e, '₦'),
+    (0xfe6f, 0x6f, '௹'),
+    (0xfe6f, 0x70, '₰'),
+    (0xfe6f, 0x72, '₢'),
+    (0xfe6f, 0x73, '₪'),
+    (0xfe6f, 0x74, '৳'),
+    (0xfe6f, 0x75, '元'),
+    (0xfe6f, 0x77, '₩'),
+    (0xfe6f, 0x79, '¥'),
+    (0xfe6f, 0xa0, '¤'),
+    (0xfe6f, 0xc7, '₵'),
+    (0xfe6f, 0xde, '৲'),
+    (0xfe6f, 0xe7, '₵'),
+    (0xfe6f, 0xfe, '৲'),
+    (0xfe6f, 0xfe6f, '¤'),
+    (0xfe8c, 0x20, 'µ'),
+    (0xfe8c, 0x41, 'Α'),
+    (0xfe8c, 0x42, 'Β'),
+    (0xfe8c, 0x44, 'Δ'),
+    (0xfe8c, 0x45, 'Ε'),
+    (0xfe8c, 0x46, 'Φ'),
+    (0xfe8c, 0x47, 'Γ'),
+    (0xfe8c, 0x48, 'Η'),
+    (0xfe8c, 0x49, 'Ι'),
+    (0xfe8c, 0x4a, 'Θ'),
+    (0xfe8c, 0x4b, 'Κ'),
+    (0xfe8c, 0x4c, 'Λ'),
+    (0xfe8c, 0x4d, 'Μ'),
+    (0xfe8c, 0x4e, 'Ν'),
+    (0xfe8c, 0x4f, 'Ο'),
+    (0xfe8c, 0x50, 'Π'),
+    (0xfe8c, 0x51, 'Χ'),
+    (0xfe8c, 0x52, 'Ρ'),
+    (0xfe8c, 0x53, 'Σ'),
+    (0xfe8c, 0x54, 'Τ'),
+    (0xfe8c, 0x55, 'Υ'),
+    (0xfe8c, 0x57, 'Ω'),
+    (0xfe8c, 0x58, 'Ξ'),
+    (0xfe8c, 0x59, 'Ψ'),
+    (0xfe8c, 0x5a, 'Ζ'),
+    (0xfe8c, 0x61, 'α'),
+    (0xfe8c, 0x62, 'β'),
+    (0xfe8c, 0x64, 'δ'),
+    (0xfe8c, 0x65, 'ε'),
+    (0xfe8c, 0x66, 'φ'),
+    (0xfe8c, 0x67, 'γ'),
+    (0xfe8c, 0x68, 'η'),
+    (0xfe8c, 0x69, 'ι'),
+    (0xfe8c, 0x6a, 'θ'),
+    (0xfe8c, 0x6b, 'κ'),
+    (0xfe8c, 0x6c, 'λ'),
+    (0xfe8c, 0x6d, 'μ'),
+    (0xfe8c, 0x6e, 'ν'),
+    (0xfe8c, 0x6f, 'ο'),
+    (0xfe8c, 0x70, 'π'),
+    (0xfe8c, 0x71, 'χ'),
+    (0xfe8c, 0x72, 'ρ'),
+    (0xfe8c, 0x73, 'σ'),
+    (0xfe8c, 0x74, 'τ'),
+    (0xfe8c, 0x75, 'υ'),
+    (0xfe8c, 0x77, 'ω'),
+    (0xfe8c, 0x78, 'ξ'),
+    (0xfe8c, 0x79, 'ψ'),
+    (0xfe8c, 0x7a, 'ζ'),
+    (0xfe8c, 0xa0, 'µ'),
+    (0xfe8c, 0xfe8c, 'µ'),
+];
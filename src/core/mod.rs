@@ -1,13 +1,34 @@
 mod core_state_implementation;
 
 pub(crate) use self::core_state_implementation::CoreWindowRef;
+pub(crate) use core_state_implementation::CoreError;
 pub(crate) use core_state_implementation::CoreStateEnum;
 pub use core_state_implementation::CoreStateImplementation;
+pub use core_state_implementation::CoreWaker;
 
+#[cfg(wayland)]
+mod wayland;
 #[cfg(windows)]
 mod win32;
 #[cfg(x11)]
 mod x11rb;
 
+#[cfg(any(headless, wayland))]
+mod canvas;
+#[cfg(any(x11, wayland))]
+mod keysym;
+#[cfg(any(x11, wayland))]
+mod keysym_tables;
+#[cfg(any(x11, wayland))]
+mod pipe_waker;
+
+#[cfg(headless)]
+mod headless;
+#[cfg(headless)]
+pub use headless::{
+    Framebuffer, HeadlessCursor, HeadlessDrag, HeadlessEvent, HeadlessHandle, HeadlessWindowId,
+};
+
 pub use core_state_implementation::DrawingContextEnum;
 pub use core_state_implementation::WWindCoreEvent;
+pub(crate) use core_state_implementation::WakerEnum;
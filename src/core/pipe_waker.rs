@@ -0,0 +1,51 @@
+use std::{
+    io,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+    sync::Arc,
+};
+
+use crate::core::CoreWaker;
+
+/// The write end of a self-pipe. Writing to it wakes up the event loop.
+#[derive(Clone)]
+pub struct PipeWaker(Arc<OwnedFd>);
+
+/// The read end of a self-pipe, which is polled alongside the connection to the display server
+pub struct PipeReceiver(OwnedFd);
+
+/// Creates a non-blocking self-pipe
+pub fn pipe() -> io::Result<(PipeReceiver, PipeWaker)> {
+    let mut fds = [0; 2];
+
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let (read, write) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+
+    Ok((PipeReceiver(read), PipeWaker(Arc::new(write))))
+}
+
+impl CoreWaker for PipeWaker {
+    fn wake(&self) {
+        // If the pipe is full, the loop is going to wake up anyway
+        unsafe { libc::write(self.0.as_raw_fd(), [0u8].as_ptr().cast(), 1) };
+    }
+}
+
+impl PipeReceiver {
+    /// Empties the pipe so that it stops being readable
+    pub fn drain(&self) {
+        let mut buffer = [0u8; 64];
+
+        while unsafe { libc::read(self.0.as_raw_fd(), buffer.as_mut_ptr().cast(), buffer.len()) }
+            > 0
+        {}
+    }
+}
+
+impl AsRawFd for PipeReceiver {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+    os::fd::{AsFd, AsRawFd, FromRawFd, OwnedFd},
+    ptr, slice,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use wayland_client::{
+    protocol::{wl_buffer::WlBuffer, wl_shm, wl_shm_pool::WlShmPool},
+    QueueHandle,
+};
+
+use crate::{core::canvas::Canvas, Color, RgbaImage};
+
+use super::WaylandData;
+
+/// What has been drawn on a window, as XRGB8888 pixels. This is kept separately from the
+/// shared memory buffers because the compositor may still be reading those.
+pub struct PixelBuffer {
+    width: u16,
+    height: u16,
+    pixels: Vec<u32>,
+}
+
+impl PixelBuffer {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize],
+        }
+    }
+
+    /// Changes the size of the buffer. The part that is still inside of it is kept.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        let mut resized = Self::new(width, height);
+
+        let row_length = width.min(self.width) as usize;
+
+        for y in 0..height.min(self.height) as usize {
+            let old_start = y * self.width as usize;
+            let new_start = y * width as usize;
+
+            resized.pixels[new_start..new_start + row_length]
+                .copy_from_slice(&self.pixels[old_start..old_start + row_length]);
+        }
+
+        *self = resized;
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+}
+
+impl Canvas for PixelBuffer {
+    fn width(&self) -> u16 {
+        self.width
+    }
+
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn put_pixel(&mut self, x: u16, y: u16, color: Color) {
+        self.pixels[y as usize * self.width as usize + x as usize] =
+            (color.red as u32) << 16 | (color.green as u32) << 8 | color.blue as u32;
+    }
+}
+
+/// A buffer that the compositor can read from
+struct ShmBuffer {
+    buffer: WlBuffer,
+    /// Set when the buffer is attached and cleared when the compositor releases it
+    busy: Arc<AtomicBool>,
+}
+
+/// Two buffers of the same size in one memory-mapped file. While the compositor reads from one,
+/// the other can be written to.
+pub struct ShmBuffers {
+    pool: WlShmPool,
+    memory: *mut u8,
+    length: usize,
+    width: u16,
+    height: u16,
+    buffers: [ShmBuffer; 2],
+}
+
+impl ShmBuffers {
+    pub fn new(
+        shm: &wl_shm::WlShm,
+        queue_handle: &QueueHandle<WaylandData>,
+        width: u16,
+        height: u16,
+    ) -> io::Result<Self> {
+        // Surfaces can't have empty buffers
+        let (width, height) = (width.max(1), height.max(1));
+
+        let stride = width as usize * 4;
+        let buffer_length = stride * height as usize;
+        let length = buffer_length * 2;
+
+        let file = create_shm_file(length)?;
+
+        let memory = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                length,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+
+        if memory == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        let pool = shm.create_pool(file.as_fd(), length as i32, queue_handle, ());
+
+        let create_buffer = |offset: usize| {
+            let busy = Arc::new(AtomicBool::new(false));
+
+            let buffer = pool.create_buffer(
+                offset as i32,
+                width as i32,
+                height as i32,
+                stride as i32,
+                wl_shm::Format::Xrgb8888,
+                queue_handle,
+                busy.clone(),
+            );
+
+            ShmBuffer { buffer, busy }
+        };
+
+        let buffers = [create_buffer(0), create_buffer(buffer_length)];
+
+        Ok(Self {
+            pool,
+            memory: memory.cast(),
+            length,
+            width,
+            height,
+            buffers,
+        })
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Copies `pixels` into a buffer that the compositor isn't using and returns it. Returns
+    /// `None` if both buffers are in use. `pixels` must be the same size as the buffers.
+    pub fn present(&mut self, pixels: &PixelBuffer) -> Option<&WlBuffer> {
+        let index = self
+            .buffers
+            .iter()
+            .position(|buffer| !buffer.busy.load(Ordering::Acquire))?;
+
+        let pixel_count = self.width as usize * self.height as usize;
+
+        let memory = unsafe {
+            slice::from_raw_parts_mut(
+                self.memory.cast::<u32>().add(index * pixel_count),
+                pixel_count,
+            )
+        };
+
+        memory.copy_from_slice(&pixels.pixels);
+
+        let buffer = &self.buffers[index];
+        buffer.busy.store(true, Ordering::Release);
+
+        Some(&buffer.buffer)
+    }
+}
+
+impl Drop for ShmBuffers {
+    fn drop(&mut self) {
+        // The compositor keeps the contents of destroyed buffers that are still attached
+        for buffer in &self.buffers {
+            buffer.buffer.destroy();
+        }
+
+        self.pool.destroy();
+
+        unsafe { libc::munmap(self.memory.cast(), self.length) };
+    }
+}
+
+/// An image in shared memory that never changes, such as a cursor. The compositor only reads
+/// from it, so one buffer is enough.
+pub struct ShmImage {
+    pool: WlShmPool,
+    buffer: WlBuffer,
+}
+
+impl ShmImage {
+    /// `image` can't be empty, because buffers can't be
+    pub fn new(
+        shm: &wl_shm::WlShm,
+        queue_handle: &QueueHandle<WaylandData>,
+        image: &RgbaImage,
+    ) -> io::Result<Self> {
+        let (width, height) = (image.width(), image.height());
+        let data: Vec<u8> = image
+            .premultiplied_argb_pixels()
+            .flat_map(u32::to_ne_bytes)
+            .collect();
+
+        let file = create_shm_file(data.len())?;
+        File::from(file.try_clone()?).write_all(&data)?;
+
+        let pool = shm.create_pool(file.as_fd(), data.len() as i32, queue_handle, ());
+        let buffer = pool.create_buffer(
+            0,
+            width as i32,
+            height as i32,
+            width as i32 * 4,
+            wl_shm::Format::Argb8888,
+            queue_handle,
+            (),
+        );
+
+        Ok(Self { pool, buffer })
+    }
+
+    pub fn buffer(&self) -> &WlBuffer {
+        &self.buffer
+    }
+}
+
+impl Drop for ShmImage {
+    fn drop(&mut self) {
+        self.buffer.destroy();
+        self.pool.destroy();
+    }
+}
+
+/// Creates an anonymous file of `length` bytes that can be shared with the compositor
+fn create_shm_file(length: usize) -> io::Result<OwnedFd> {
+    let fd = unsafe { libc::memfd_create(c"wwind-shm".as_ptr(), libc::MFD_CLOEXEC) };
+
+    if fd == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let file = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    if unsafe { libc::ftruncate(fd, length as libc::off_t) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(file)
+}
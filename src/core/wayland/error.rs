@@ -0,0 +1,69 @@
+use std::{fmt, io};
+
+use wayland_client::{
+    backend::WaylandError,
+    globals::{BindError, GlobalError},
+    ConnectError, DispatchError,
+};
+
+#[derive(Debug)]
+pub enum WlError {
+    ConnectError(ConnectError),
+    ConnectionError(WaylandError),
+    DispatchError(DispatchError),
+    GlobalError(GlobalError),
+    /// A global that wwind depends on is not advertised by the compositor
+    BindError(&'static str, BindError),
+    IoError(io::Error),
+    /// libxkbcommon couldn't be loaded, so keyboard input can't be handled
+    MissingXkbCommon,
+    /// The backend can't do what was asked
+    Unsupported,
+}
+
+impl fmt::Display for WlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WlError::ConnectError(err) => {
+                write!(f, "failed to connect to the Wayland compositor: {err}")
+            }
+            WlError::ConnectionError(err) => write!(f, "Wayland connection error: {err}"),
+            WlError::DispatchError(err) => write!(f, "failed to handle a Wayland event: {err}"),
+            WlError::GlobalError(err) => write!(f, "failed to list the Wayland globals: {err}"),
+            WlError::BindError(interface, err) => write!(f, "failed to bind {interface}: {err}"),
+            WlError::IoError(err) => write!(f, "IO error: {err}"),
+            WlError::MissingXkbCommon => f.write_str("libxkbcommon could not be loaded"),
+            WlError::Unsupported => f.write_str("the Wayland backend doesn't support this"),
+        }
+    }
+}
+
+impl From<ConnectError> for WlError {
+    fn from(value: ConnectError) -> Self {
+        Self::ConnectError(value)
+    }
+}
+
+impl From<WaylandError> for WlError {
+    fn from(value: WaylandError) -> Self {
+        Self::ConnectionError(value)
+    }
+}
+
+impl From<DispatchError> for WlError {
+    fn from(value: DispatchError) -> Self {
+        Self::DispatchError(value)
+    }
+}
+
+impl From<GlobalError> for WlError {
+    fn from(value: GlobalError) -> Self {
+        Self::GlobalError(value)
+    }
+}
+
+impl From<io::Error> for WlError {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
@@ -0,0 +1,384 @@
+use std::{
+    collections::VecDeque,
+    os::fd::{AsRawFd, OwnedFd},
+    ptr,
+    time::{Duration, Instant},
+};
+
+use wayland_client::{
+    protocol::wl_keyboard::{self, KeyState, KeymapFormat},
+    Proxy, WEnum,
+};
+use xkbcommon_dl::{
+    xkb_context, xkb_context_flags, xkb_keymap, xkb_keymap_compile_flags, xkb_keymap_format,
+    xkb_state, xkb_state_component, XkbCommon,
+};
+
+use crate::{
+    core::{
+        core_state_implementation::WWindCoreEvent,
+        keysym::{self, Composer},
+    },
+    input::{Key, KeyEvent, Modifiers},
+};
+
+use super::{WaylandWindowId, WlError};
+
+/// A keymap sent by the compositor, together with the state of its modifiers
+struct Keymap {
+    xkb: &'static XkbCommon,
+    keymap: *mut xkb_keymap,
+    state: *mut xkb_state,
+}
+
+impl Drop for Keymap {
+    fn drop(&mut self) {
+        unsafe {
+            (self.xkb.xkb_state_unref)(self.state);
+            (self.xkb.xkb_keymap_unref)(self.keymap);
+        }
+    }
+}
+
+/// Turns wl_keyboard events into key and text events
+pub struct Keyboard {
+    xkb: &'static XkbCommon,
+    context: *mut xkb_context,
+    /// `None` until the compositor sends a keymap
+    keymap: Option<Keymap>,
+    composer: Composer,
+    /// The window with keyboard focus
+    focus: Option<WaylandWindowId>,
+    repeat: Option<KeyRepeat>,
+    /// Repeated key presses per second. Zero disables key repeat.
+    repeat_rate: u32,
+    repeat_delay: Duration,
+}
+
+/// A key that is being held down
+struct KeyRepeat {
+    keycode: u32,
+    /// When the next repeated press should be sent
+    deadline: Instant,
+    /// The time of the original press and when it happened. Used to make up timestamps for
+    /// the repeated presses.
+    timestamp: u32,
+    pressed_at: Instant,
+}
+
+const MODIFIER_NAMES: [(&[u8], Modifiers); 6] = [
+    (xkbcommon_dl::XKB_MOD_NAME_SHIFT, Modifiers::SHIFT),
+    (xkbcommon_dl::XKB_MOD_NAME_CTRL, Modifiers::CONTROL),
+    (xkbcommon_dl::XKB_MOD_NAME_ALT, Modifiers::ALT),
+    (xkbcommon_dl::XKB_MOD_NAME_LOGO, Modifiers::SUPER),
+    (xkbcommon_dl::XKB_MOD_NAME_CAPS, Modifiers::CAPS_LOCK),
+    (xkbcommon_dl::XKB_MOD_NAME_NUM, Modifiers::NUM_LOCK),
+];
+
+impl Keyboard {
+    pub fn new() -> Result<Self, WlError> {
+        let xkb = xkbcommon_dl::xkbcommon_option().ok_or(WlError::MissingXkbCommon)?;
+
+        let context = unsafe { (xkb.xkb_context_new)(xkb_context_flags::XKB_CONTEXT_NO_FLAGS) };
+
+        if context.is_null() {
+            return Err(WlError::MissingXkbCommon);
+        }
+
+        Ok(Self {
+            xkb,
+            context,
+            keymap: None,
+            composer: Composer::default(),
+            focus: None,
+            repeat: None,
+            // The defaults of wl_keyboard version 3 and older, which don't send repeat_info
+            repeat_rate: 25,
+            repeat_delay: Duration::from_millis(600),
+        })
+    }
+
+    /// Forgets the focus if it was on `window`, which is being destroyed
+    pub fn remove_window(&mut self, window: WaylandWindowId) {
+        if self.focus == Some(window) {
+            self.focus = None;
+            self.repeat = None;
+        }
+    }
+
+    /// Gets the modifiers that are held down, for the events of other devices
+    pub fn modifiers(&self) -> Modifiers {
+        self.keymap
+            .as_ref()
+            .map_or(Modifiers::NONE, |keymap| self.get_modifiers(keymap))
+    }
+
+    /// When the next repeated key press is due
+    pub fn next_repeat(&self) -> Option<Instant> {
+        self.repeat.as_ref().map(|repeat| repeat.deadline)
+    }
+
+    pub fn handle_event(
+        &mut self,
+        event: wl_keyboard::Event,
+        events: &mut VecDeque<WWindCoreEvent>,
+    ) {
+        match event {
+            wl_keyboard::Event::Keymap { format, fd, size } => {
+                // Without a keymap, key events are dropped until the compositor sends one that
+                // works
+                if format != WEnum::Value(KeymapFormat::XkbV1) {
+                    return;
+                }
+
+                if let Some(keymap) = self.load_keymap(fd, size as usize) {
+                    self.keymap = Some(keymap);
+                }
+            }
+            wl_keyboard::Event::Enter { surface, .. } => {
+                let Some(&window) = surface.data::<WaylandWindowId>() else {
+                    return;
+                };
+
+                self.focus = Some(window);
+
+                events.push_back(WWindCoreEvent::FocusChange(window.into(), true));
+            }
+            wl_keyboard::Event::Leave { .. } => {
+                self.repeat = None;
+
+                if let Some(window) = self.focus.take() {
+                    events.push_back(WWindCoreEvent::FocusChange(window.into(), false));
+                }
+            }
+            wl_keyboard::Event::Key {
+                time, key, state, ..
+            } => {
+                // Wayland keycodes are evdev codes, which are offset by 8 in XKB
+                let keycode = key + 8;
+
+                if state == WEnum::Value(KeyState::Pressed) {
+                    self.repeat = self.key_repeats(keycode).then(|| KeyRepeat {
+                        keycode,
+                        deadline: Instant::now() + self.repeat_delay,
+                        timestamp: time,
+                        pressed_at: Instant::now(),
+                    });
+
+                    self.press_key(keycode, time, false, events);
+                } else {
+                    if self
+                        .repeat
+                        .as_ref()
+                        .is_some_and(|repeat| repeat.keycode == keycode)
+                    {
+                        self.repeat = None;
+                    }
+
+                    let Some(window) = self.focus else {
+                        return;
+                    };
+
+                    if let Some(key_event) = self.get_key_event(keycode, time, false) {
+                        events.push_back(WWindCoreEvent::Keyup(window.into(), key_event));
+                    }
+                }
+            }
+            wl_keyboard::Event::Modifiers {
+                mods_depressed,
+                mods_latched,
+                mods_locked,
+                group,
+                ..
+            } => {
+                if let Some(keymap) = &self.keymap {
+                    unsafe {
+                        (self.xkb.xkb_state_update_mask)(
+                            keymap.state,
+                            mods_depressed,
+                            mods_latched,
+                            mods_locked,
+                            0,
+                            0,
+                            group,
+                        )
+                    };
+                }
+            }
+            wl_keyboard::Event::RepeatInfo { rate, delay } => {
+                self.repeat_rate = rate.max(0) as u32;
+                self.repeat_delay = Duration::from_millis(delay.max(0) as u64);
+            }
+            _ => {}
+        }
+    }
+
+    /// Sends the repeated key presses that are due
+    pub fn handle_repeat(&mut self, events: &mut VecDeque<WWindCoreEvent>) {
+        let now = Instant::now();
+
+        let Some(repeat) = &mut self.repeat else {
+            return;
+        };
+
+        if self.repeat_rate == 0 {
+            self.repeat = None;
+            return;
+        }
+
+        if repeat.deadline > now {
+            return;
+        }
+
+        let interval = Duration::from_secs(1) / self.repeat_rate;
+
+        // If we fell behind, skip the presses that were missed instead of sending them all
+        while repeat.deadline <= now {
+            repeat.deadline += interval;
+        }
+
+        let keycode = repeat.keycode;
+        let elapsed = now.duration_since(repeat.pressed_at).as_millis() as u32;
+        let timestamp = repeat.timestamp.wrapping_add(elapsed);
+
+        self.press_key(keycode, timestamp, true, events);
+    }
+
+    fn press_key(
+        &mut self,
+        keycode: u32,
+        timestamp: u32,
+        repeat: bool,
+        events: &mut VecDeque<WWindCoreEvent>,
+    ) {
+        let Some(window) = self.focus else {
+            return;
+        };
+
+        let Some(key_event) = self.get_key_event(keycode, timestamp, repeat) else {
+            return;
+        };
+
+        events.push_back(WWindCoreEvent::Keydown(window.into(), key_event));
+
+        // Shortcuts shouldn't type anything. AltGr is a separate modifier, so it is not
+        // affected by this.
+        if key_event.modifiers.contains(Modifiers::CONTROL)
+            || key_event.modifiers.contains(Modifiers::ALT)
+        {
+            return;
+        }
+
+        for character in self.composer.feed(key_event.raw_key) {
+            events.push_back(WWindCoreEvent::TextInput(window.into(), character));
+        }
+    }
+
+    fn get_key_event(&self, keycode: u32, timestamp: u32, repeat: bool) -> Option<KeyEvent> {
+        let keymap = self.keymap.as_ref()?;
+
+        let raw_key = unsafe { (self.xkb.xkb_state_key_get_one_sym)(keymap.state, keycode) };
+
+        let key = self
+            .get_base_keysym(keymap, keycode)
+            .map_or(Key::Unknown, keysym::keysym_to_key);
+
+        Some(KeyEvent {
+            key,
+            raw_key,
+            modifiers: self.get_modifiers(keymap),
+            repeat,
+            timestamp,
+        })
+    }
+
+    /// Gets the keysym that a key produces in the first layout without any modifiers held
+    fn get_base_keysym(&self, keymap: &Keymap, keycode: u32) -> Option<u32> {
+        let mut keysyms = ptr::null();
+
+        let count = unsafe {
+            (self.xkb.xkb_keymap_key_get_syms_by_level)(keymap.keymap, keycode, 0, 0, &mut keysyms)
+        };
+
+        (count > 0).then(|| unsafe { *keysyms })
+    }
+
+    fn get_modifiers(&self, keymap: &Keymap) -> Modifiers {
+        let mut modifiers = Modifiers::NONE;
+
+        for (name, modifier) in MODIFIER_NAMES {
+            let active = unsafe {
+                (self.xkb.xkb_state_mod_name_is_active)(
+                    keymap.state,
+                    name.as_ptr().cast(),
+                    xkb_state_component::XKB_STATE_MODS_EFFECTIVE,
+                )
+            };
+
+            if active > 0 {
+                modifiers |= modifier;
+            }
+        }
+
+        modifiers
+    }
+
+    fn key_repeats(&self, keycode: u32) -> bool {
+        self.keymap.as_ref().is_some_and(|keymap| unsafe {
+            (self.xkb.xkb_keymap_key_repeats)(keymap.keymap, keycode) != 0
+        })
+    }
+
+    fn load_keymap(&self, fd: OwnedFd, size: usize) -> Option<Keymap> {
+        let memory = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                size,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+
+        if memory == libc::MAP_FAILED {
+            return None;
+        }
+
+        // The keymap is a null-terminated string
+        let keymap = unsafe {
+            (self.xkb.xkb_keymap_new_from_string)(
+                self.context,
+                memory.cast(),
+                xkb_keymap_format::XKB_KEYMAP_FORMAT_TEXT_V1,
+                xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+            )
+        };
+
+        unsafe { libc::munmap(memory, size) };
+
+        if keymap.is_null() {
+            return None;
+        }
+
+        let state = unsafe { (self.xkb.xkb_state_new)(keymap) };
+
+        if state.is_null() {
+            unsafe { (self.xkb.xkb_keymap_unref)(keymap) };
+            return None;
+        }
+
+        Some(Keymap {
+            xkb: self.xkb,
+            keymap,
+            state,
+        })
+    }
+}
+
+impl Drop for Keyboard {
+    fn drop(&mut self) {
+        // The keymap keeps its own reference to the context
+        unsafe { (self.xkb.xkb_context_unref)(self.context) };
+    }
+}
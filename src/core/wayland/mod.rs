@@ -0,0 +1,1138 @@
+//! A backend for Wayland compositors that support xdg_shell. Windows are drawn in memory with
+//! the software rasterizer and handed to the compositor through wl_shm buffers.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    io,
+    os::fd::AsRawFd,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use wayland_client::{
+    backend::WaylandError,
+    delegate_noop,
+    globals::{registry_queue_init, GlobalListContents},
+    protocol::{
+        wl_buffer::{self, WlBuffer},
+        wl_compositor::WlCompositor,
+        wl_keyboard::{self, WlKeyboard},
+        wl_output::{self, WlOutput},
+        wl_pointer::{self, WlPointer},
+        wl_registry::{self, WlRegistry},
+        wl_seat::{self, WlSeat},
+        wl_shm::WlShm,
+        wl_shm_pool::WlShmPool,
+        wl_surface::{self, WlSurface},
+    },
+    Connection, Dispatch, EventQueue, Proxy, QueueHandle, WEnum,
+};
+use wayland_protocols::{
+    wp::cursor_shape::v1::client::{
+        wp_cursor_shape_device_v1::WpCursorShapeDeviceV1,
+        wp_cursor_shape_manager_v1::WpCursorShapeManagerV1,
+    },
+    xdg::shell::client::{
+        xdg_surface::{self, XdgSurface},
+        xdg_toplevel::{self, XdgToplevel},
+        xdg_wm_base::{self, XdgWmBase},
+    },
+};
+
+use crate::{
+    clipboard::{Selection, SelectionContents},
+    Color, CursorIcon, DropPayload, Monitor, RectRegion, ResizeEdge, RgbaImage, WindowBuilder,
+    WindowState, WindowStateFlags,
+};
+
+use super::{
+    canvas::Canvas,
+    core_state_implementation::WWindCoreEvent,
+    pipe_waker::{self, PipeReceiver, PipeWaker},
+    CoreStateImplementation,
+};
+
+mod buffer;
+mod error;
+mod keyboard;
+mod output;
+mod pointer;
+pub use error::WlError;
+
+use buffer::{PixelBuffer, ShmBuffers, ShmImage};
+use keyboard::Keyboard;
+use output::Output;
+use pointer::{CustomCursor, Pointer, WindowCursor};
+#[cfg(raw_window_handle)]
+use raw_window_handle::{
+    HandleError, RawDisplayHandle, RawWindowHandle, WaylandDisplayHandle, WaylandWindowHandle,
+};
+#[cfg(raw_window_handle)]
+use std::ptr::NonNull;
+
+/// Identifies a window of the Wayland backend
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WaylandWindowId(u32);
+
+struct WaylandWindow {
+    surface: WlSurface,
+    xdg_surface: XdgSurface,
+    toplevel: XdgToplevel,
+    canvas: PixelBuffer,
+    /// `None` until the window is first presented
+    buffers: Option<ShmBuffers>,
+    /// The size from the last toplevel configure, which takes effect once the xdg_surface
+    /// configure that follows it is acknowledged
+    pending_size: Option<(u16, u16)>,
+    /// The state from the last toplevel configure, applied together with the size
+    pending_state: WindowStateFlags,
+    /// Compositors don't say when a window is minimized, so that is never set
+    state: WindowStateFlags,
+    /// Whether the compositor has configured the surface. Buffers can't be attached before that.
+    configured: bool,
+    /// Whether the canvas has changes that haven't been presented
+    dirty: bool,
+    /// Toplevels are only shown once a buffer is attached, so hidden windows never get one
+    visible: bool,
+    /// Set on the pointer whenever it enters the window
+    cursor: WindowCursor,
+}
+
+/// The state that is passed to the event handlers of the Wayland objects
+pub struct WaylandData {
+    shm: WlShm,
+    windows: HashMap<WaylandWindowId, WaylandWindow>,
+    /// Events that were generated but not yet passed to the event handler
+    events: VecDeque<WWindCoreEvent>,
+    keyboard: Keyboard,
+    wl_keyboard: Option<WlKeyboard>,
+    pointer: Option<Pointer>,
+    /// `None` if the compositor doesn't support cursor-shape-v1
+    cursor_shape_manager: Option<WpCursorShapeManagerV1>,
+    /// The monitors, by the names of their globals
+    outputs: BTreeMap<u32, Output>,
+}
+
+pub struct WaylandState {
+    event_queue: EventQueue<WaylandData>,
+    queue_handle: QueueHandle<WaylandData>,
+    compositor: WlCompositor,
+    wm_base: XdgWmBase,
+    /// Needed to move and resize windows, and kept alive so that input events keep coming
+    seat: Option<WlSeat>,
+    data: WaylandData,
+    next_window_id: u32,
+    draw_color: Color,
+    /// wl_data_device isn't supported yet, so the selections are only shared within the program
+    selections: HashMap<Selection, SelectionContents>,
+    wake_receiver: PipeReceiver,
+    waker: PipeWaker,
+}
+
+impl WaylandState {
+    /// Attaches the canvases that changed since they were last presented
+    fn present_windows(&mut self) -> Result<(), WlError> {
+        for window in self.data.windows.values_mut() {
+            if !window.configured || !window.dirty || !window.visible {
+                continue;
+            }
+
+            let (width, height) = window.canvas.size();
+
+            if window
+                .buffers
+                .as_ref()
+                .is_none_or(|buffers| buffers.size() != (width.max(1), height.max(1)))
+            {
+                // Drop the old buffers first so that the memory is freed before allocating more
+                window.buffers = None;
+                window.buffers = Some(ShmBuffers::new(
+                    &self.data.shm,
+                    &self.queue_handle,
+                    width,
+                    height,
+                )?);
+            }
+
+            let Some(buffers) = &mut window.buffers else {
+                unreachable!()
+            };
+
+            // If both buffers are in use, this is retried once the compositor releases one
+            let Some(buffer) = buffers.present(&window.canvas) else {
+                continue;
+            };
+
+            window.surface.attach(Some(buffer), 0, 0);
+            window
+                .surface
+                .damage_buffer(0, 0, width as i32, height as i32);
+            window.surface.commit();
+
+            window.dirty = false;
+        }
+
+        Ok(())
+    }
+
+    /// Blocks until the compositor sends something, the event loop is woken up or `timeout`
+    /// runs out, then queues the events that were read
+    fn read_events(&mut self, timeout: Option<Duration>) -> Result<(), WlError> {
+        let Some(guard) = self.event_queue.prepare_read() else {
+            // Events were already read by another queue of this connection
+            self.event_queue.dispatch_pending(&mut self.data)?;
+            return Ok(());
+        };
+
+        let mut poll_fds = [
+            libc::pollfd {
+                fd: guard.connection_fd().as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: self.wake_receiver.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+
+        // Rounded up so that we don't wake up right before the timeout and spin
+        let timeout = timeout.map_or(-1, |timeout| {
+            timeout
+                .as_nanos()
+                .div_ceil(1_000_000)
+                .try_into()
+                .unwrap_or(i32::MAX)
+        });
+
+        if unsafe { libc::poll(poll_fds.as_mut_ptr(), poll_fds.len() as _, timeout) } <= 0 {
+            return Ok(());
+        }
+
+        if poll_fds[1].revents != 0 {
+            self.wake_receiver.drain();
+        }
+
+        if poll_fds[0].revents != 0 {
+            match guard.read() {
+                Ok(_) => {}
+                Err(WaylandError::Io(err)) if err.kind() == io::ErrorKind::WouldBlock => {}
+                Err(err) => return Err(err.into()),
+            }
+
+            self.event_queue.dispatch_pending(&mut self.data)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_window(&mut self, window: WaylandWindowId) -> Option<&mut WaylandWindow> {
+        self.data.windows.get_mut(&window)
+    }
+
+    /// Changes the cursor of a window, and shows it right away if the pointer is over it
+    fn set_cursor(&mut self, window_id: WaylandWindowId, cursor: WindowCursor) {
+        let Some(window) = self.data.windows.get_mut(&window_id) else {
+            return;
+        };
+
+        window.cursor = cursor;
+
+        if let Some(pointer) = &self.data.pointer {
+            if pointer.focus() == Some(window_id) {
+                pointer.set_cursor(&window.cursor);
+            }
+        }
+    }
+}
+
+fn get_resize_edge(edge: ResizeEdge) -> xdg_toplevel::ResizeEdge {
+    match edge {
+        ResizeEdge::Top => xdg_toplevel::ResizeEdge::Top,
+        ResizeEdge::Bottom => xdg_toplevel::ResizeEdge::Bottom,
+        ResizeEdge::Left => xdg_toplevel::ResizeEdge::Left,
+        ResizeEdge::Right => xdg_toplevel::ResizeEdge::Right,
+        ResizeEdge::TopLeft => xdg_toplevel::ResizeEdge::TopLeft,
+        ResizeEdge::TopRight => xdg_toplevel::ResizeEdge::TopRight,
+        ResizeEdge::BottomLeft => xdg_toplevel::ResizeEdge::BottomLeft,
+        ResizeEdge::BottomRight => xdg_toplevel::ResizeEdge::BottomRight,
+    }
+}
+
+impl CoreStateImplementation for WaylandState {
+    type Error = WlError;
+    type Window = WaylandWindowId;
+    type DrawingContext = WaylandWindowId;
+    type Waker = PipeWaker;
+
+    unsafe fn new() -> Result<Self, Self::Error> {
+        let connection = Connection::connect_to_env()?;
+
+        let (globals, event_queue) = registry_queue_init::<WaylandData>(&connection)?;
+        let queue_handle = event_queue.handle();
+
+        let compositor = globals
+            .bind::<WlCompositor, _, _>(&queue_handle, 4..=6, ())
+            .map_err(|err| WlError::BindError("wl_compositor", err))?;
+        let shm = globals
+            .bind::<WlShm, _, _>(&queue_handle, 1..=1, ())
+            .map_err(|err| WlError::BindError("wl_shm", err))?;
+        let wm_base = globals
+            .bind::<XdgWmBase, _, _>(&queue_handle, 1..=5, ())
+            .map_err(|err| WlError::BindError("xdg_wm_base", err))?;
+
+        // Without a seat there is no input, but windows can still be shown
+        let seat = globals.bind::<WlSeat, _, _>(&queue_handle, 1..=7, ()).ok();
+        let cursor_shape_manager = globals
+            .bind::<WpCursorShapeManagerV1, _, _>(&queue_handle, 1..=1, ())
+            .ok();
+
+        // Outputs that are added later are bound when the registry announces them
+        let outputs = globals.contents().with_list(|list| {
+            list.iter()
+                .filter(|global| global.interface == WlOutput::interface().name)
+                .filter_map(|global| {
+                    let output = Output::bind(
+                        globals.registry(),
+                        global.name,
+                        global.version,
+                        &queue_handle,
+                    )?;
+
+                    Some((global.name, output))
+                })
+                .collect()
+        });
+
+        let keyboard = Keyboard::new()?;
+
+        let (wake_receiver, waker) = pipe_waker::pipe()?;
+
+        let mut state = Self {
+            event_queue,
+            queue_handle,
+            compositor,
+            wm_base,
+            seat,
+            data: WaylandData {
+                shm,
+                windows: HashMap::new(),
+                events: VecDeque::new(),
+                keyboard,
+                wl_keyboard: None,
+                pointer: None,
+                cursor_shape_manager,
+                outputs,
+            },
+            next_window_id: 0,
+            draw_color: Color::from_hex(0),
+            selections: HashMap::new(),
+            wake_receiver,
+            waker,
+        };
+
+        // Gets the seat capabilities, so that input works from the start, and the monitors
+        state.event_queue.roundtrip(&mut state.data)?;
+
+        // The monitors that were there from the start didn't change
+        state.data.events.clear();
+
+        Ok(state)
+    }
+
+    /// Wayland clients can't position their windows, so this does nothing
+    fn set_window_position(
+        &mut self,
+        _window: Self::Window,
+        _x: i16,
+        _y: i16,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_window_size(
+        &mut self,
+        window_id: Self::Window,
+        width: u16,
+        height: u16,
+    ) -> Result<(), Self::Error> {
+        let Some(window) = self.data.windows.get_mut(&window_id) else {
+            return Ok(());
+        };
+
+        // The size of a toplevel is the size of its buffer, so the next buffer that is
+        // presented resizes it
+        if window.canvas.size() != (width, height) {
+            window.canvas.resize(width, height);
+            window.dirty = true;
+
+            let region = RectRegion {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            };
+
+            let events = &mut self.data.events;
+            events.push_back(WWindCoreEvent::Resize(window_id.into(), width, height));
+            events.push_back(WWindCoreEvent::Expose(window_id.into(), region));
+        }
+
+        Ok(())
+    }
+
+    fn set_window_visible(
+        &mut self,
+        window: Self::Window,
+        visible: bool,
+    ) -> Result<(), Self::Error> {
+        let Some(window) = self.get_window(window) else {
+            return Ok(());
+        };
+
+        if window.visible == visible {
+            return Ok(());
+        }
+
+        window.visible = visible;
+
+        if visible {
+            // An unmapped toplevel has to be configured again before it gets a buffer
+            window.dirty = true;
+        } else {
+            window.surface.attach(None, 0, 0);
+        }
+
+        window.configured = false;
+        window.surface.commit();
+
+        self.event_queue.flush()?;
+
+        Ok(())
+    }
+
+    /// Taking focus needs a token from xdg-activation, so this does nothing
+    fn focus_window(&mut self, _window: Self::Window) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// The compositor decides the stacking order, so this does nothing
+    fn raise_window(&mut self, _window: Self::Window) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_window_fullscreen(
+        &mut self,
+        window: Self::Window,
+        fullscreen: bool,
+    ) -> Result<(), Self::Error> {
+        let Some(window) = self.get_window(window) else {
+            return Ok(());
+        };
+
+        if fullscreen {
+            window.toplevel.set_fullscreen(None);
+        } else {
+            window.toplevel.unset_fullscreen();
+        }
+
+        Ok(())
+    }
+
+    fn set_window_maximized(
+        &mut self,
+        window: Self::Window,
+        maximized: bool,
+    ) -> Result<(), Self::Error> {
+        let Some(window) = self.get_window(window) else {
+            return Ok(());
+        };
+
+        if maximized {
+            window.toplevel.set_maximized();
+        } else {
+            window.toplevel.unset_maximized();
+        }
+
+        Ok(())
+    }
+
+    fn minimize_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        if let Some(window) = self.get_window(window) {
+            window.toplevel.set_minimized();
+        }
+
+        Ok(())
+    }
+
+    /// Only client-side decorations are supported, and wwind doesn't draw any, so this does
+    /// nothing
+    fn set_window_decorations(
+        &mut self,
+        _window: Self::Window,
+        _decorations: bool,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// xdg_shell has no icons. The compositor finds them through the app id instead, which is
+    /// set with [class](crate::WindowBuilder::class).
+    fn set_window_icon(
+        &mut self,
+        _window: Self::Window,
+        _icons: &[RgbaImage],
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Standard cursors are set through cursor-shape-v1. Compositors without it keep showing
+    /// their own cursor.
+    fn set_window_cursor(
+        &mut self,
+        window: Self::Window,
+        cursor: CursorIcon,
+    ) -> Result<(), Self::Error> {
+        self.set_cursor(window, WindowCursor::Shape(cursor));
+
+        Ok(())
+    }
+
+    fn set_window_custom_cursor(
+        &mut self,
+        window: Self::Window,
+        image: &RgbaImage,
+        hotspot: (u16, u16),
+    ) -> Result<(), Self::Error> {
+        if !self.data.windows.contains_key(&window) {
+            return Ok(());
+        }
+
+        let image = ShmImage::new(&self.data.shm, &self.queue_handle, image)?;
+
+        let surface = self.compositor.create_surface(&self.queue_handle, ());
+        surface.attach(Some(image.buffer()), 0, 0);
+        surface.damage_buffer(0, 0, i32::MAX, i32::MAX);
+        surface.commit();
+
+        self.set_cursor(
+            window,
+            WindowCursor::Custom(CustomCursor {
+                surface,
+                _image: image,
+                hotspot,
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// The compositor only starts a move or resize while a pointer button is held, so this
+    /// does nothing unless a button was pressed over the window
+    fn start_window_drag(
+        &mut self,
+        window: Self::Window,
+        edge: Option<ResizeEdge>,
+    ) -> Result<(), Self::Error> {
+        let (Some(seat), Some(pointer)) = (&self.seat, &self.data.pointer) else {
+            return Ok(());
+        };
+
+        let (Some(serial), Some(focus)) = (pointer.button_serial(), pointer.focus()) else {
+            return Ok(());
+        };
+
+        let Some(toplevel) = self
+            .data
+            .windows
+            .get(&window)
+            .filter(|_| focus == window)
+            .map(|window| &window.toplevel)
+        else {
+            return Ok(());
+        };
+
+        match edge {
+            None => toplevel._move(seat, serial),
+            Some(edge) => toplevel.resize(seat, serial, get_resize_edge(edge)),
+        }
+
+        self.event_queue.flush()?;
+
+        Ok(())
+    }
+
+    /// Dragging needs a data device, which this backend doesn't have yet
+    fn start_data_drag(
+        &mut self,
+        _window: Self::Window,
+        _payloads: &[DropPayload],
+        _image: Option<(&RgbaImage, (u16, u16))>,
+    ) -> Result<(), Self::Error> {
+        Err(WlError::Unsupported)
+    }
+
+    fn add_window(&mut self, builder: &WindowBuilder) -> Result<Self::Window, Self::Error> {
+        // Wayland clients can't position their windows. There are no server-side decorations
+        // without xdg-decoration either, so `decorations` is ignored.
+        let (width, height) = builder.clamped_size();
+
+        let window = WaylandWindowId(self.next_window_id);
+        self.next_window_id += 1;
+
+        let surface = self.compositor.create_surface(&self.queue_handle, window);
+        let xdg_surface = self
+            .wm_base
+            .get_xdg_surface(&surface, &self.queue_handle, window);
+        let toplevel = xdg_surface.get_toplevel(&self.queue_handle, window);
+
+        toplevel.set_title(builder.title.clone());
+
+        if let Some(class) = &builder.class {
+            toplevel.set_app_id(class.clone());
+        }
+
+        let (min_size, max_size) = if builder.resizable {
+            (builder.min_size, builder.max_size)
+        } else {
+            (Some((width, height)), Some((width, height)))
+        };
+
+        if let Some((min_width, min_height)) = min_size {
+            toplevel.set_min_size(min_width as i32, min_height as i32);
+        }
+        if let Some((max_width, max_height)) = max_size {
+            toplevel.set_max_size(max_width as i32, max_height as i32);
+        }
+
+        match builder.state {
+            WindowState::Normal => {}
+            WindowState::Maximized => toplevel.set_maximized(),
+            WindowState::Minimized => toplevel.set_minimized(),
+            WindowState::Fullscreen => toplevel.set_fullscreen(None),
+        }
+
+        // Asks the compositor to configure the window. Nothing is attached until then.
+        surface.commit();
+
+        self.data.windows.insert(
+            window,
+            WaylandWindow {
+                surface,
+                xdg_surface,
+                toplevel,
+                canvas: PixelBuffer::new(width, height),
+                buffers: None,
+                pending_size: None,
+                pending_state: WindowStateFlags::NONE,
+                state: WindowStateFlags::NONE,
+                configured: false,
+                dirty: true,
+                visible: builder.visible,
+                cursor: WindowCursor::Shape(CursorIcon::Default),
+            },
+        );
+
+        self.event_queue.flush()?;
+
+        Ok(window)
+    }
+
+    fn set_window_title(&mut self, window: Self::Window, title: &str) -> Result<(), Self::Error> {
+        if let Some(window) = self.get_window(window) {
+            window.toplevel.set_title(title.to_owned());
+        }
+
+        Ok(())
+    }
+
+    /// Receiving drops needs a data device, which this backend doesn't have yet
+    fn accept_window_drops(&mut self, _window: Self::Window) {}
+
+    fn set_selection(
+        &mut self,
+        selection: Selection,
+        contents: SelectionContents,
+    ) -> Result<(), Self::Error> {
+        if contents.is_empty() {
+            self.selections.remove(&selection);
+        } else {
+            self.selections.insert(selection, contents);
+        }
+
+        Ok(())
+    }
+
+    fn get_selection(
+        &mut self,
+        selection: Selection,
+        mime_type: &str,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self
+            .selections
+            .get(&selection)
+            .and_then(|contents| contents.get(mime_type))
+            .map(<[u8]>::to_vec))
+    }
+
+    fn get_selection_types(&mut self, selection: Selection) -> Result<Vec<String>, Self::Error> {
+        Ok(self
+            .selections
+            .get(&selection)
+            .map(SelectionContents::types)
+            .unwrap_or_default())
+    }
+
+    /// Wayland has no primary monitor, so none of them are marked as one
+    fn get_monitors(&self) -> Result<Vec<Monitor>, Self::Error> {
+        Ok(self
+            .data
+            .outputs
+            .values()
+            .filter_map(Output::monitor)
+            .cloned()
+            .collect())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.present_windows()?;
+        self.event_queue.flush()?;
+
+        Ok(())
+    }
+
+    unsafe fn destroy_window(&mut self, window: Self::Window) {
+        self.data.keyboard.remove_window(window);
+
+        if let Some(pointer) = &mut self.data.pointer {
+            pointer.remove_window(window);
+        }
+
+        if let Some(window) = self.data.windows.remove(&window) {
+            // Roles have to be destroyed before the objects they were given to
+            window.toplevel.destroy();
+            window.xdg_surface.destroy();
+            drop(window.buffers);
+            window.surface.destroy();
+        }
+    }
+
+    fn get_waker(&self) -> Self::Waker {
+        self.waker.clone()
+    }
+
+    unsafe fn wait_for_events(
+        &mut self,
+        on_event: &mut unsafe fn(WWindCoreEvent),
+        timeout: Option<Duration>,
+    ) -> Result<bool, Self::Error> {
+        if self.data.events.is_empty() {
+            self.event_queue.dispatch_pending(&mut self.data)?;
+            self.data.keyboard.handle_repeat(&mut self.data.events);
+        }
+
+        if self.data.events.is_empty() {
+            self.flush()?;
+
+            // Key repeat is driven by us, so the wait has to end when the next repeat is due
+            let timeout = match self.data.keyboard.next_repeat() {
+                Some(deadline) => {
+                    let until_repeat = deadline.saturating_duration_since(Instant::now());
+                    Some(timeout.map_or(until_repeat, |timeout| timeout.min(until_repeat)))
+                }
+                None => timeout,
+            };
+
+            self.read_events(timeout)?;
+            self.data.keyboard.handle_repeat(&mut self.data.events);
+        }
+
+        let Some(event) = self.data.events.pop_front() else {
+            return Ok(false);
+        };
+
+        on_event(event);
+
+        Ok(true)
+    }
+
+    #[cfg(raw_window_handle)]
+    fn raw_window_handle(&self, window: Self::Window) -> Result<RawWindowHandle, HandleError> {
+        let window = self
+            .data
+            .windows
+            .get(&window)
+            .ok_or(HandleError::Unavailable)?;
+
+        let surface = NonNull::new(window.surface.id().as_ptr()).ok_or(HandleError::Unavailable)?;
+
+        Ok(WaylandWindowHandle::new(surface.cast()).into())
+    }
+
+    #[cfg(raw_window_handle)]
+    fn raw_display_handle(&self) -> Result<RawDisplayHandle, HandleError> {
+        let backend = self
+            .compositor
+            .backend()
+            .upgrade()
+            .ok_or(HandleError::Unavailable)?;
+
+        let display = NonNull::new(backend.display_ptr()).ok_or(HandleError::Unavailable)?;
+
+        Ok(WaylandDisplayHandle::new(display.cast()).into())
+    }
+
+    unsafe fn get_context(&mut self, window: Self::Window) -> Self::DrawingContext {
+        window
+    }
+
+    fn draw_line(
+        &mut self,
+        drawing_context: Self::DrawingContext,
+        x1: u16,
+        y1: u16,
+        x2: u16,
+        y2: u16,
+    ) -> Result<(), Self::Error> {
+        let color = self.draw_color;
+
+        if let Some(window) = self.get_window(drawing_context) {
+            window.canvas.draw_line(x1, y1, x2, y2, color);
+            window.dirty = true;
+        }
+
+        Ok(())
+    }
+
+    fn draw_rectangle(
+        &mut self,
+        drawing_context: Self::DrawingContext,
+        rectangle: RectRegion,
+    ) -> Result<(), Self::Error> {
+        let color = self.draw_color;
+
+        if let Some(window) = self.get_window(drawing_context) {
+            window.canvas.fill_rectangle(rectangle, color);
+            window.dirty = true;
+        }
+
+        Ok(())
+    }
+
+    fn set_draw_color(
+        &mut self,
+        _context: Self::DrawingContext,
+        color: Color,
+    ) -> Result<(), Self::Error> {
+        self.draw_color = color;
+
+        Ok(())
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for WaylandData {
+    fn event(
+        state: &mut Self,
+        registry: &WlRegistry,
+        event: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        queue_handle: &QueueHandle<Self>,
+    ) {
+        // Other globals that appear later, such as new seats, are not used
+        match event {
+            wl_registry::Event::Global {
+                name,
+                interface,
+                version,
+            } if interface == WlOutput::interface().name => {
+                if let Some(output) = Output::bind(registry, name, version, queue_handle) {
+                    state.outputs.insert(name, output);
+                }
+            }
+            wl_registry::Event::GlobalRemove { name } => {
+                if let Some(output) = state.outputs.remove(&name) {
+                    output.remove(&mut state.events);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<XdgWmBase, ()> for WaylandData {
+    fn event(
+        _: &mut Self,
+        wm_base: &XdgWmBase,
+        event: xdg_wm_base::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let xdg_wm_base::Event::Ping { serial } = event {
+            wm_base.pong(serial);
+        }
+    }
+}
+
+impl Dispatch<XdgSurface, WaylandWindowId> for WaylandData {
+    fn event(
+        state: &mut Self,
+        xdg_surface: &XdgSurface,
+        event: xdg_surface::Event,
+        &window_id: &WaylandWindowId,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let xdg_surface::Event::Configure { serial } = event else {
+            return;
+        };
+
+        xdg_surface.ack_configure(serial);
+
+        let Some(window) = state.windows.get_mut(&window_id) else {
+            return;
+        };
+
+        let old_size = window.canvas.size();
+        let (width, height) = window.pending_size.take().unwrap_or(old_size);
+
+        let resized = (width, height) != old_size;
+        let first_configure = !window.configured;
+
+        window.configured = true;
+
+        if window.state != window.pending_state {
+            window.state = window.pending_state;
+
+            state
+                .events
+                .push_back(WWindCoreEvent::StateChange(window_id.into(), window.state));
+        }
+
+        if resized {
+            window.canvas.resize(width, height);
+            window.dirty = true;
+
+            state
+                .events
+                .push_back(WWindCoreEvent::Resize(window_id.into(), width, height));
+        }
+
+        if resized || first_configure {
+            let region = RectRegion {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            };
+
+            state
+                .events
+                .push_back(WWindCoreEvent::Expose(window_id.into(), region));
+        }
+    }
+}
+
+impl Dispatch<XdgToplevel, WaylandWindowId> for WaylandData {
+    fn event(
+        state: &mut Self,
+        _: &XdgToplevel,
+        event: xdg_toplevel::Event,
+        &window_id: &WaylandWindowId,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            xdg_toplevel::Event::Configure {
+                width,
+                height,
+                states,
+            } => {
+                let Some(window) = state.windows.get_mut(&window_id) else {
+                    return;
+                };
+
+                // An array of native-endian xdg_toplevel states
+                window.pending_state = WindowStateFlags::NONE;
+
+                for chunk in states.chunks_exact(4) {
+                    let value = u32::from_ne_bytes(chunk.try_into().unwrap());
+
+                    match xdg_toplevel::State::try_from(value) {
+                        Ok(xdg_toplevel::State::Maximized) => {
+                            window.pending_state.insert(WindowStateFlags::MAXIMIZED)
+                        }
+                        Ok(xdg_toplevel::State::Fullscreen) => {
+                            window.pending_state.insert(WindowStateFlags::FULLSCREEN)
+                        }
+                        _ => {}
+                    }
+                }
+
+                // Zero means that we get to pick the size
+                if width > 0 && height > 0 {
+                    window.pending_size = Some((
+                        width.min(u16::MAX as i32) as u16,
+                        height.min(u16::MAX as i32) as u16,
+                    ));
+                }
+            }
+            xdg_toplevel::Event::Close => {
+                state
+                    .events
+                    .push_back(WWindCoreEvent::CloseWindow(window_id.into()));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<WlBuffer, Arc<AtomicBool>> for WaylandData {
+    fn event(
+        _: &mut Self,
+        _: &WlBuffer,
+        event: wl_buffer::Event,
+        busy: &Arc<AtomicBool>,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let wl_buffer::Event::Release = event {
+            // Windows that were waiting for a free buffer are presented on the next flush
+            busy.store(false, Ordering::Release);
+        }
+    }
+}
+
+impl Dispatch<WlSeat, ()> for WaylandData {
+    fn event(
+        state: &mut Self,
+        seat: &WlSeat,
+        event: wl_seat::Event,
+        _: &(),
+        _: &Connection,
+        queue_handle: &QueueHandle<Self>,
+    ) {
+        let wl_seat::Event::Capabilities {
+            capabilities: WEnum::Value(capabilities),
+        } = event
+        else {
+            return;
+        };
+
+        let has_keyboard = capabilities.contains(wl_seat::Capability::Keyboard);
+        let has_pointer = capabilities.contains(wl_seat::Capability::Pointer);
+
+        match (&state.wl_keyboard, has_keyboard) {
+            (None, true) => state.wl_keyboard = Some(seat.get_keyboard(queue_handle, ())),
+            (Some(keyboard), false) => {
+                if keyboard.version() >= 3 {
+                    keyboard.release();
+                }
+
+                state.wl_keyboard = None;
+            }
+            _ => {}
+        }
+
+        match (&state.pointer, has_pointer) {
+            (None, true) => {
+                let wl_pointer = seat.get_pointer(queue_handle, ());
+                let shape_device = state
+                    .cursor_shape_manager
+                    .as_ref()
+                    .map(|manager| manager.get_pointer(&wl_pointer, queue_handle, ()));
+
+                state.pointer = Some(Pointer::new(wl_pointer, shape_device));
+            }
+            (Some(_), false) => {
+                if let Some(pointer) = state.pointer.take() {
+                    pointer.release();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<WlKeyboard, ()> for WaylandData {
+    fn event(
+        state: &mut Self,
+        _: &WlKeyboard,
+        event: wl_keyboard::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        state.keyboard.handle_event(event, &mut state.events);
+    }
+}
+
+impl Dispatch<WlPointer, ()> for WaylandData {
+    fn event(
+        state: &mut Self,
+        _: &WlPointer,
+        event: wl_pointer::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let Some(pointer) = &mut state.pointer else {
+            return;
+        };
+
+        let entered = matches!(event, wl_pointer::Event::Enter { .. });
+
+        pointer.handle_event(event, state.keyboard.modifiers(), &mut state.events);
+
+        // The cursor has to be set again every time the pointer enters a window
+        if entered {
+            if let Some(window) = pointer
+                .focus()
+                .and_then(|window| state.windows.get(&window))
+            {
+                pointer.set_cursor(&window.cursor);
+            }
+        }
+    }
+}
+
+impl Dispatch<WlOutput, u32> for WaylandData {
+    fn event(
+        state: &mut Self,
+        _: &WlOutput,
+        event: wl_output::Event,
+        name: &u32,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let Some(output) = state.outputs.get_mut(name) {
+            output.handle_event(event, &mut state.events);
+        }
+    }
+}
+
+impl Dispatch<WlSurface, WaylandWindowId> for WaylandData {
+    fn event(
+        _: &mut Self,
+        _: &WlSurface,
+        _: wl_surface::Event,
+        _: &WaylandWindowId,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        // Which outputs the surface is on doesn't matter yet
+    }
+}
+
+delegate_noop!(WaylandData: ignore WlCompositor);
+delegate_noop!(WaylandData: ignore WlShm);
+delegate_noop!(WaylandData: ignore WlShmPool);
+// Cursor surfaces and images
+delegate_noop!(WaylandData: ignore WlSurface);
+delegate_noop!(WaylandData: ignore WlBuffer);
+delegate_noop!(WaylandData: WpCursorShapeManagerV1);
+delegate_noop!(WaylandData: WpCursorShapeDeviceV1);
@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+
+use wayland_client::{
+    protocol::{
+        wl_output::{self, Mode, Transform, WlOutput},
+        wl_registry::WlRegistry,
+    },
+    Proxy, QueueHandle, WEnum,
+};
+
+use crate::{core::core_state_implementation::WWindCoreEvent, Monitor};
+
+use super::WaylandData;
+
+/// A monitor, which the compositor describes with a batch of events that ends with a done event
+pub struct Output {
+    wl_output: WlOutput,
+    /// `None` until the first done event
+    monitor: Option<Monitor>,
+    /// The monitor with the changes since the last done event
+    pending: Monitor,
+    /// The size of the current mode, before the transform is applied
+    mode_size: (i32, i32),
+    /// Whether the monitor is rotated by 90 or 270 degrees
+    rotated: bool,
+}
+
+impl Output {
+    /// Binds a wl_output global. Version 2 is needed for done events.
+    pub fn bind(
+        registry: &WlRegistry,
+        name: u32,
+        version: u32,
+        queue_handle: &QueueHandle<WaylandData>,
+    ) -> Option<Self> {
+        if version < 2 {
+            return None;
+        }
+
+        let wl_output = registry.bind::<WlOutput, _, _>(name, version.min(4), queue_handle, name);
+
+        Some(Self {
+            wl_output,
+            monitor: None,
+            pending: Monitor {
+                name: String::new(),
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+                refresh_rate: None,
+                physical_width: 0,
+                physical_height: 0,
+                // Wayland has no primary monitor
+                primary: false,
+            },
+            mode_size: (0, 0),
+            rotated: false,
+        })
+    }
+
+    pub fn monitor(&self) -> Option<&Monitor> {
+        self.monitor.as_ref()
+    }
+
+    /// Destroys the output after its global was removed
+    pub fn remove(self, events: &mut VecDeque<WWindCoreEvent>) {
+        if self.wl_output.version() >= 3 {
+            self.wl_output.release();
+        }
+
+        if self.monitor.is_some() {
+            events.push_back(WWindCoreEvent::MonitorsChanged);
+        }
+    }
+
+    pub fn handle_event(&mut self, event: wl_output::Event, events: &mut VecDeque<WWindCoreEvent>) {
+        match event {
+            wl_output::Event::Geometry {
+                x,
+                y,
+                physical_width,
+                physical_height,
+                transform,
+                ..
+            } => {
+                self.pending.x = clamp_i16(x);
+                self.pending.y = clamp_i16(y);
+                self.pending.physical_width = physical_width.max(0) as u32;
+                self.pending.physical_height = physical_height.max(0) as u32;
+
+                self.rotated = matches!(
+                    transform,
+                    WEnum::Value(
+                        Transform::_90
+                            | Transform::_270
+                            | Transform::Flipped90
+                            | Transform::Flipped270
+                    )
+                );
+            }
+            wl_output::Event::Mode {
+                flags: WEnum::Value(flags),
+                width,
+                height,
+                refresh,
+            } if flags.contains(Mode::Current) => {
+                self.mode_size = (width, height);
+                self.pending.refresh_rate = (refresh > 0).then_some(refresh as u32);
+            }
+            wl_output::Event::Name { name } => self.pending.name = name,
+            wl_output::Event::Done => {
+                let (width, height) = if self.rotated {
+                    (self.mode_size.1, self.mode_size.0)
+                } else {
+                    self.mode_size
+                };
+
+                self.pending.width = clamp_u16(width);
+                self.pending.height = clamp_u16(height);
+
+                if self.monitor.as_ref() != Some(&self.pending) {
+                    self.monitor = Some(self.pending.clone());
+
+                    events.push_back(WWindCoreEvent::MonitorsChanged);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn clamp_i16(value: i32) -> i16 {
+    value.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+}
+
+fn clamp_u16(value: i32) -> u16 {
+    value.clamp(0, u16::MAX as i32) as u16
+}
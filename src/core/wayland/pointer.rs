@@ -0,0 +1,334 @@
+use std::collections::VecDeque;
+
+use wayland_client::{
+    protocol::{
+        wl_pointer::{self, Axis, AxisSource, ButtonState, WlPointer},
+        wl_surface::WlSurface,
+    },
+    Proxy, WEnum,
+};
+use wayland_protocols::wp::cursor_shape::v1::client::wp_cursor_shape_device_v1::{
+    Shape, WpCursorShapeDeviceV1,
+};
+
+use crate::{
+    core::core_state_implementation::WWindCoreEvent,
+    input::{Modifiers, MouseButton, MouseButtonEvent, MouseMoveEvent, ScrollEvent},
+    CursorIcon,
+};
+
+use super::{buffer::ShmImage, WaylandWindowId};
+
+/// The Linux input event codes of the mouse buttons
+const BTN_LEFT: u32 = 0x110;
+const BTN_RIGHT: u32 = 0x111;
+const BTN_MIDDLE: u32 = 0x112;
+const BTN_SIDE: u32 = 0x113;
+const BTN_EXTRA: u32 = 0x114;
+
+/// How far compositors scroll for one step of a mouse wheel in wl_pointer axis events
+const AXIS_STEP: f64 = 10.0;
+
+/// The cursor that is shown while the pointer is over a window
+pub enum WindowCursor {
+    Shape(CursorIcon),
+    Custom(CustomCursor),
+}
+
+/// A surface that shows a cursor image
+pub struct CustomCursor {
+    pub surface: WlSurface,
+    /// Attached to the surface, so it has to live as long as the surface does
+    pub _image: ShmImage,
+    pub hotspot: (u16, u16),
+}
+
+impl Drop for CustomCursor {
+    fn drop(&mut self) {
+        self.surface.destroy();
+    }
+}
+
+/// Axis events that are collected until the frame event that ends them
+#[derive(Default)]
+struct PendingScroll {
+    /// The horizontal and vertical distances in surface coordinates
+    value: [f64; 2],
+    /// The number of wheel steps, which replaces `value` when it is known
+    discrete: [Option<i32>; 2],
+    source: Option<AxisSource>,
+}
+
+/// Turns wl_pointer events into mouse events
+pub struct Pointer {
+    wl_pointer: WlPointer,
+    /// `None` if the compositor doesn't support cursor-shape-v1
+    shape_device: Option<WpCursorShapeDeviceV1>,
+    /// The window under the pointer, with the serial of the enter event, which setting the
+    /// cursor needs
+    focus: Option<(WaylandWindowId, u32)>,
+    position: (f64, f64),
+    /// The serial of the last button press, which moving and resizing windows needs
+    button_serial: Option<u32>,
+    /// The time of the last event that had one. Enter and leave events don't.
+    timestamp: u32,
+    scroll: Option<PendingScroll>,
+}
+
+impl Pointer {
+    pub fn new(wl_pointer: WlPointer, shape_device: Option<WpCursorShapeDeviceV1>) -> Self {
+        Self {
+            wl_pointer,
+            shape_device,
+            focus: None,
+            position: (0.0, 0.0),
+            button_serial: None,
+            timestamp: 0,
+            scroll: None,
+        }
+    }
+
+    /// Destroys the pointer after the seat lost it
+    pub fn release(self) {
+        if let Some(shape_device) = &self.shape_device {
+            shape_device.destroy();
+        }
+
+        if self.wl_pointer.version() >= 3 {
+            self.wl_pointer.release();
+        }
+    }
+
+    pub fn focus(&self) -> Option<WaylandWindowId> {
+        self.focus.map(|(window, _)| window)
+    }
+
+    pub fn button_serial(&self) -> Option<u32> {
+        self.button_serial
+    }
+
+    /// Forgets the focus if it was on `window`, which is being destroyed
+    pub fn remove_window(&mut self, window: WaylandWindowId) {
+        if self.focus() == Some(window) {
+            self.focus = None;
+            self.button_serial = None;
+        }
+    }
+
+    /// Shows `cursor` while the pointer is over the window that it is on now. Compositors
+    /// without cursor-shape-v1 keep showing their own cursor instead of the standard shapes.
+    pub fn set_cursor(&self, cursor: &WindowCursor) {
+        let Some((_, serial)) = self.focus else {
+            return;
+        };
+
+        match cursor {
+            WindowCursor::Shape(icon) => {
+                if let Some(shape_device) = &self.shape_device {
+                    shape_device.set_shape(serial, get_shape(*icon));
+                }
+            }
+            WindowCursor::Custom(cursor) => {
+                let (x, y) = cursor.hotspot;
+
+                self.wl_pointer
+                    .set_cursor(serial, Some(&cursor.surface), x as i32, y as i32);
+            }
+        }
+    }
+
+    pub fn handle_event(
+        &mut self,
+        event: wl_pointer::Event,
+        modifiers: Modifiers,
+        events: &mut VecDeque<WWindCoreEvent>,
+    ) {
+        match event {
+            wl_pointer::Event::Enter {
+                serial,
+                surface,
+                surface_x,
+                surface_y,
+            } => {
+                // Cursor surfaces don't have a window
+                let Some(&window) = surface.data::<WaylandWindowId>() else {
+                    return;
+                };
+
+                self.focus = Some((window, serial));
+                self.position = (surface_x, surface_y);
+
+                let event = self.get_move_event(modifiers);
+                events.push_back(WWindCoreEvent::PointerEnter(window.into(), event));
+            }
+            wl_pointer::Event::Leave { .. } => {
+                // The surface may already be destroyed, so the focus is used instead
+                if let Some((window, _)) = self.focus.take() {
+                    let event = self.get_move_event(modifiers);
+                    events.push_back(WWindCoreEvent::PointerLeave(window.into(), event));
+                }
+
+                self.button_serial = None;
+                self.scroll = None;
+            }
+            wl_pointer::Event::Motion {
+                time,
+                surface_x,
+                surface_y,
+            } => {
+                self.timestamp = time;
+                self.position = (surface_x, surface_y);
+
+                if let Some(window) = self.focus() {
+                    let event = self.get_move_event(modifiers);
+                    events.push_back(WWindCoreEvent::MouseMove(window.into(), event));
+                }
+            }
+            wl_pointer::Event::Button {
+                serial,
+                time,
+                button,
+                state,
+            } => {
+                self.timestamp = time;
+
+                let Some(window) = self.focus() else {
+                    return;
+                };
+
+                let (x, y) = self.get_position();
+                let event = MouseButtonEvent {
+                    x,
+                    y,
+                    button: get_mouse_button(button),
+                    modifiers,
+                    timestamp: time,
+                };
+
+                if state == WEnum::Value(ButtonState::Pressed) {
+                    self.button_serial = Some(serial);
+
+                    events.push_back(WWindCoreEvent::MouseDown(window.into(), event));
+                } else {
+                    events.push_back(WWindCoreEvent::MouseUp(window.into(), event));
+                }
+            }
+            wl_pointer::Event::Axis { time, axis, value } => {
+                self.timestamp = time;
+
+                if let Some(index) = get_axis_index(axis) {
+                    self.scroll.get_or_insert_with(Default::default).value[index] += value;
+                }
+
+                // Before version 5 there are no frame events, so every axis event stands alone
+                if self.wl_pointer.version() < 5 {
+                    self.send_scroll(modifiers, events);
+                }
+            }
+            wl_pointer::Event::AxisSource {
+                axis_source: WEnum::Value(source),
+            } => {
+                self.scroll.get_or_insert_with(Default::default).source = Some(source);
+            }
+            wl_pointer::Event::AxisDiscrete { axis, discrete } => {
+                if let Some(index) = get_axis_index(axis) {
+                    let pending = self.scroll.get_or_insert_with(Default::default);
+                    *pending.discrete[index].get_or_insert(0) += discrete;
+                }
+            }
+            wl_pointer::Event::Frame => self.send_scroll(modifiers, events),
+            _ => {}
+        }
+    }
+
+    /// Sends the axis events since the last frame as one scroll event
+    fn send_scroll(&mut self, modifiers: Modifiers, events: &mut VecDeque<WWindCoreEvent>) {
+        let Some(scroll) = self.scroll.take() else {
+            return;
+        };
+
+        let Some(window) = self.focus() else {
+            return;
+        };
+
+        let [delta_x, delta_y] = [0, 1].map(|index| match scroll.discrete[index] {
+            Some(steps) => steps as f64,
+            None => scroll.value[index] / AXIS_STEP,
+        });
+
+        // Axis stop events come without a distance
+        if delta_x == 0.0 && delta_y == 0.0 {
+            return;
+        }
+
+        let precise = scroll.discrete == [None, None] && scroll.source != Some(AxisSource::Wheel);
+
+        let (x, y) = self.get_position();
+        let event = ScrollEvent {
+            x,
+            y,
+            delta_x,
+            delta_y,
+            precise,
+            modifiers,
+            timestamp: self.timestamp,
+        };
+
+        events.push_back(WWindCoreEvent::Scroll(window.into(), event));
+    }
+
+    fn get_position(&self) -> (i16, i16) {
+        // Float to integer casts saturate
+        (self.position.0 as i16, self.position.1 as i16)
+    }
+
+    fn get_move_event(&self, modifiers: Modifiers) -> MouseMoveEvent {
+        let (x, y) = self.get_position();
+
+        MouseMoveEvent {
+            x,
+            y,
+            modifiers,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+fn get_axis_index(axis: WEnum<Axis>) -> Option<usize> {
+    match axis {
+        WEnum::Value(Axis::HorizontalScroll) => Some(0),
+        WEnum::Value(Axis::VerticalScroll) => Some(1),
+        _ => None,
+    }
+}
+
+fn get_mouse_button(button: u32) -> MouseButton {
+    match button {
+        BTN_LEFT => MouseButton::Left,
+        BTN_MIDDLE => MouseButton::Middle,
+        BTN_RIGHT => MouseButton::Right,
+        BTN_SIDE => MouseButton::Back,
+        BTN_EXTRA => MouseButton::Forward,
+        button => MouseButton::Other(button.wrapping_sub(BTN_LEFT) as u8),
+    }
+}
+
+fn get_shape(icon: CursorIcon) -> Shape {
+    match icon {
+        CursorIcon::Default => Shape::Default,
+        CursorIcon::Text => Shape::Text,
+        CursorIcon::Pointer => Shape::Pointer,
+        CursorIcon::Crosshair => Shape::Crosshair,
+        CursorIcon::Move => Shape::Move,
+        CursorIcon::Wait => Shape::Wait,
+        CursorIcon::Progress => Shape::Progress,
+        CursorIcon::Help => Shape::Help,
+        CursorIcon::NotAllowed => Shape::NotAllowed,
+        CursorIcon::Grab => Shape::Grab,
+        CursorIcon::Grabbing => Shape::Grabbing,
+        CursorIcon::ResizeHorizontal => Shape::EwResize,
+        CursorIcon::ResizeVertical => Shape::NsResize,
+        CursorIcon::ResizeTopLeftBottomRight => Shape::NwseResize,
+        CursorIcon::ResizeTopRightBottomLeft => Shape::NeswResize,
+    }
+}
@@ -0,0 +1,31 @@
+use std::{fmt, io};
+
+use winapi::um::errhandlingapi::GetLastError;
+
+#[derive(Debug)]
+pub enum Win32Error {
+    /// An error code from `GetLastError`
+    Os(u32),
+    /// The backend can't do what was asked
+    Unsupported,
+}
+
+impl Win32Error {
+    /// Gets the error of the last Win32 call that failed on this thread
+    pub fn last() -> Self {
+        Self::Os(unsafe { GetLastError() })
+    }
+}
+
+impl fmt::Display for Win32Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Win32Error::Os(code) => {
+                let message = io::Error::from_raw_os_error(*code as i32);
+
+                write!(f, "Win32 call failed: {message}")
+            }
+            Win32Error::Unsupported => f.write_str("the Win32 backend doesn't support this"),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use winapi::shared::minwindef::LPARAM;
+
+use crate::input::Key;
+
+/// Converts a virtual-key code into a [Key]. The `lparam` of the key message is needed to tell
+/// apart keys that share a virtual-key code.
+pub fn virtual_key_to_key(virtual_key: u32, lparam: LPARAM) -> Key {
+    const LETTERS: [Key; 26] = [
+        Key::A,
+        Key::B,
+        Key::C,
+        Key::D,
+        Key::E,
+        Key::F,
+        Key::G,
+        Key::H,
+        Key::I,
+        Key::J,
+        Key::K,
+        Key::L,
+        Key::M,
+        Key::N,
+        Key::O,
+        Key::P,
+        Key::Q,
+        Key::R,
+        Key::S,
+        Key::T,
+        Key::U,
+        Key::V,
+        Key::W,
+        Key::X,
+        Key::Y,
+        Key::Z,
+    ];
+    const DIGITS: [Key; 10] = [
+        Key::Digit0,
+        Key::Digit1,
+        Key::Digit2,
+        Key::Digit3,
+        Key::Digit4,
+        Key::Digit5,
+        Key::Digit6,
+        Key::Digit7,
+        Key::Digit8,
+        Key::Digit9,
+    ];
+    const NUMPAD_DIGITS: [Key; 10] = [
+        Key::Numpad0,
+        Key::Numpad1,
+        Key::Numpad2,
+        Key::Numpad3,
+        Key::Numpad4,
+        Key::Numpad5,
+        Key::Numpad6,
+        Key::Numpad7,
+        Key::Numpad8,
+        Key::Numpad9,
+    ];
+    const FUNCTION_KEYS: [Key; 24] = [
+        Key::F1,
+        Key::F2,
+        Key::F3,
+        Key::F4,
+        Key::F5,
+        Key::F6,
+        Key::F7,
+        Key::F8,
+        Key::F9,
+        Key::F10,
+        Key::F11,
+        Key::F12,
+        Key::F13,
+        Key::F14,
+        Key::F15,
+        Key::F16,
+        Key::F17,
+        Key::F18,
+        Key::F19,
+        Key::F20,
+        Key::F21,
+        Key::F22,
+        Key::F23,
+        Key::F24,
+    ];
+
+    let scancode = (lparam >> 16) & 0xff;
+    let extended = lparam & (1 << 24) != 0;
+
+    match virtual_key {
+        0x41..=0x5a => LETTERS[(virtual_key - 0x41) as usize],
+        0x30..=0x39 => DIGITS[(virtual_key - 0x30) as usize],
+        0x60..=0x69 => NUMPAD_DIGITS[(virtual_key - 0x60) as usize],
+        0x70..=0x87 => FUNCTION_KEYS[(virtual_key - 0x70) as usize],
+
+        0x08 => Key::Backspace,
+        0x09 => Key::Tab,
+        0x0d if extended => Key::NumpadEnter,
+        0x0d => Key::Enter,
+        // VK_SHIFT. The right shift key has its own scancode.
+        0x10 if scancode == 0x36 => Key::RightShift,
+        0x10 => Key::LeftShift,
+        // VK_CONTROL and VK_MENU. The right keys have the extended bit set.
+        0x11 if extended => Key::RightControl,
+        0x11 => Key::LeftControl,
+        0x12 if extended => Key::RightAlt,
+        0x12 => Key::LeftAlt,
+        0x13 => Key::Pause,
+        0x14 => Key::CapsLock,
+        0x1b => Key::Escape,
+        0x20 => Key::Space,
+        0x21 => Key::PageUp,
+        0x22 => Key::PageDown,
+        0x23 => Key::End,
+        0x24 => Key::Home,
+        0x25 => Key::Left,
+        0x26 => Key::Up,
+        0x27 => Key::Right,
+        0x28 => Key::Down,
+        0x2c => Key::PrintScreen,
+        0x2d => Key::Insert,
+        0x2e => Key::Delete,
+        0x5b => Key::LeftSuper,
+        0x5c => Key::RightSuper,
+        0x5d => Key::Menu,
+        0x6a => Key::NumpadMultiply,
+        0x6b => Key::NumpadAdd,
+        0x6d => Key::NumpadSubtract,
+        0x6e => Key::NumpadDecimal,
+        0x6f => Key::NumpadDivide,
+        0x90 => Key::NumLock,
+        0x91 => Key::ScrollLock,
+        0xa0 => Key::LeftShift,
+        0xa1 => Key::RightShift,
+        0xa2 => Key::LeftControl,
+        0xa3 => Key::RightControl,
+        0xa4 => Key::LeftAlt,
+        0xa5 => Key::RightAlt,
+
+        0xba => Key::Semicolon,
+        0xbb => Key::Equals,
+        0xbc => Key::Comma,
+        0xbd => Key::Minus,
+        0xbe => Key::Period,
+        0xbf => Key::Slash,
+        0xc0 => Key::Grave,
+        0xdb => Key::LeftBracket,
+        0xdc => Key::Backslash,
+        0xdd => Key::RightBracket,
+        0xde => Key::Apostrophe,
+
+        _ => Key::Unknown,
+    }
+}
@@ -1,42 +1,554 @@
-use std::convert::Infallible;
+use std::ffi::{CStr, OsString};
+use std::os::windows::ffi::OsStringExt;
+use std::path::PathBuf;
 
 use std::mem::MaybeUninit;
-use std::ptr::addr_of;
+use std::ptr::{addr_of, addr_of_mut};
 
-use std::{iter, mem, ptr};
+use std::time::Duration;
+use std::{iter, mem, ptr, slice};
 
-use winapi::um::errhandlingapi::GetLastError;
 use winapi::um::wingdi::{
-    GdiFlush, GetStockObject, LineTo, MoveToEx, SelectClipRgn, SelectObject, SetDCBrushColor,
-    SetDCPenColor, DC_BRUSH, DC_PEN,
+    CreateBitmap, CreateDCA, DeleteDC, DeleteObject, GdiFlush, GetDeviceCaps, GetStockObject,
+    LineTo, MoveToEx, SelectClipRgn, SelectObject, SetDCBrushColor, SetDCPenColor, DC_BRUSH,
+    DC_PEN, DEVMODEA, HORZSIZE, VERTSIZE,
 };
 
-use crate::RectRegion;
+use crate::clipboard::{Selection, SelectionContents, TEXT_MIME_TYPE};
+use crate::input::{
+    KeyEvent, Modifiers, MouseButton, MouseButtonEvent, MouseMoveEvent, ScrollEvent,
+};
+use crate::{
+    CursorIcon, DropEvent, DropPayload, Monitor, RectRegion, ResizeEdge, RgbaImage, WindowBuilder,
+    WindowState, WindowStateFlags,
+};
+#[cfg(raw_window_handle)]
+use raw_window_handle::{
+    HandleError, RawDisplayHandle, RawWindowHandle, Win32WindowHandle, WindowsDisplayHandle,
+};
+#[cfg(raw_window_handle)]
+use std::num::NonZeroIsize;
+
+mod error;
+mod keys;
+pub use error::Win32Error;
 
 use super::core_state_implementation::WWindCoreEvent;
-use super::CoreStateImplementation;
-use winapi::shared::minwindef::{HIWORD, HMODULE, LOWORD, LPARAM, LRESULT, UINT, WPARAM};
-use winapi::shared::windef::{HBRUSH, HDC, HPEN, HWND, RECT};
+use super::{CoreStateImplementation, CoreWaker};
+use winapi::shared::minwindef::{
+    BOOL, DWORD, FALSE, HIWORD, HMODULE, LOWORD, LPARAM, LRESULT, MAKELONG, TRUE, UINT, WORD,
+    WPARAM,
+};
+use winapi::shared::ntdef::LONG;
+use winapi::shared::windef::{
+    HBRUSH, HCURSOR, HDC, HICON, HMONITOR, HPEN, HWND, LPRECT, POINT, RECT,
+};
+use winapi::shared::windowsx::{GET_X_LPARAM, GET_Y_LPARAM};
 use winapi::um::libloaderapi::GetModuleHandleA;
+use winapi::um::processthreadsapi::GetCurrentThreadId;
+use winapi::um::shellapi::{DragAcceptFiles, DragFinish, DragQueryFileW, DragQueryPoint, HDROP};
+use winapi::um::winbase::{
+    GlobalAlloc, GlobalFree, GlobalLock, GlobalSize, GlobalUnlock, GMEM_MOVEABLE, INFINITE,
+};
 use winapi::um::winuser::{
-    CreateWindowExA, DefWindowProcA, DestroyWindow, DispatchMessageA, FillRect, GetClientRect,
-    GetDC, GetMessageA, GetUpdateRect, GetWindowLongPtrA, GetWindowRect, RedrawWindow,
-    RegisterClassA, SetWindowLongPtrA, SetWindowTextA, ShowWindow, TranslateMessage, ValidateRect,
-    CS_OWNDC, GWLP_USERDATA, RDW_INTERNALPAINT, RDW_NOINTERNALPAINT, SW_NORMAL, WM_CLOSE, WM_PAINT,
-    WM_SIZE, WNDCLASSA, WS_OVERLAPPEDWINDOW,
+    AdjustWindowRectEx, CloseClipboard, CreateIcon, CreateIconIndirect, CreateWindowExA,
+    DefWindowProcA, DestroyCursor, DestroyIcon, DestroyWindow, DispatchMessageA, EmptyClipboard,
+    EnumClipboardFormats, EnumDisplayMonitors, EnumDisplaySettingsA, FillRect, GetClipboardData,
+    GetClipboardFormatNameA, GetClipboardOwner, GetCursorPos, GetDC, GetKeyState, GetMessageA,
+    GetMessageTime, GetMonitorInfoA, GetSystemMetrics, GetUpdateRect, GetWindowLongA,
+    GetWindowLongPtrA, GetWindowRect, IsClipboardFormatAvailable, IsZoomed, LoadCursorW,
+    MonitorFromWindow, MsgWaitForMultipleObjects, OpenClipboard, PeekMessageA, PostMessageA,
+    PostThreadMessageA, RedrawWindow, RegisterClassA, RegisterClipboardFormatA, ReleaseCapture,
+    ScreenToClient, SendMessageA, SetCapture, SetClipboardData, SetCursor, SetForegroundWindow,
+    SetWindowLongA, SetWindowLongPtrA, SetWindowPos, SetWindowTextA, ShowWindow, TrackMouseEvent,
+    TranslateMessage, ValidateRect, CF_UNICODETEXT, CS_OWNDC, CW_USEDEFAULT, ENUM_CURRENT_SETTINGS,
+    GET_WHEEL_DELTA_WPARAM, GET_XBUTTON_WPARAM, GWLP_USERDATA, GWL_EXSTYLE, GWL_STYLE, HTBOTTOM,
+    HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTCLIENT, HTLEFT, HTRIGHT, HTTOP, HTTOPLEFT,
+    HTTOPRIGHT, HWND_MESSAGE, HWND_TOP, ICONINFO, ICON_BIG, ICON_SMALL, IDC_APPSTARTING, IDC_ARROW,
+    IDC_CROSS, IDC_HAND, IDC_HELP, IDC_IBEAM, IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS,
+    IDC_SIZENWSE, IDC_SIZEWE, IDC_WAIT, MINMAXINFO, MONITORINFO, MONITORINFOEXA,
+    MONITORINFOF_PRIMARY, MONITOR_DEFAULTTONEAREST, PM_REMOVE, QS_ALLINPUT, RDW_INTERNALPAINT,
+    SIZE_MAXIMIZED, SIZE_MINIMIZED, SM_CXICON, SM_CXSMICON, SWP_FRAMECHANGED, SWP_NOACTIVATE,
+    SWP_NOMOVE, SWP_NOOWNERZORDER, SWP_NOSIZE, SWP_NOZORDER, SW_HIDE, SW_MAXIMIZE, SW_MINIMIZE,
+    SW_NORMAL, SW_RESTORE, SW_SHOW, TME_LEAVE, TRACKMOUSEEVENT, VK_CAPITAL, VK_CONTROL, VK_LWIN,
+    VK_MENU, VK_NUMLOCK, VK_RWIN, VK_SHIFT, WHEEL_DELTA, WM_CHAR, WM_CLOSE, WM_DISPLAYCHANGE,
+    WM_DROPFILES, WM_GETMINMAXINFO, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN,
+    WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MOUSEHWHEEL, WM_MOUSELEAVE, WM_MOUSEMOVE,
+    WM_MOUSEWHEEL, WM_MOVE, WM_NCLBUTTONDOWN, WM_NULL, WM_PAINT, WM_QUIT, WM_RBUTTONDOWN,
+    WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETICON, WM_SIZE, WM_SYSKEYDOWN, WM_SYSKEYUP,
+    WM_XBUTTONDOWN, WM_XBUTTONUP, WNDCLASSA, WS_MAXIMIZEBOX, WS_OVERLAPPEDWINDOW, WS_POPUP,
+    WS_THICKFRAME, XBUTTON1,
 };
 
 static mut ON_EVENT: Option<unsafe fn(WWindCoreEvent)> = None;
+/// WM_CHAR sends characters outside of the BMP as two separate surrogates
+static mut HIGH_SURROGATE: Option<u16> = None;
+/// The monitors from the last WM_DISPLAYCHANGE, which every top-level window gets
+static mut MONITORS: Vec<Monitor> = Vec::new();
 
 pub struct Win32State {
     hinst: HMODULE,
     pen: HPEN,
     brush: HBRUSH,
+    /// The thread that runs the event loop
+    thread_id: DWORD,
+    /// A message-only window that owns the clipboard
+    clipboard_window: HWND,
+    /// Windows doesn't have a primary selection, so it is only shared within the program
+    primary: SelectionContents,
+}
+
+/// Wakes up the event loop by posting an empty message to its thread
+#[derive(Clone, Copy)]
+pub struct ThreadWaker(DWORD);
+
+impl CoreWaker for ThreadWaker {
+    fn wake(&self) {
+        unsafe { PostThreadMessageA(self.0, WM_NULL, 0, 0) };
+    }
 }
 
 struct Win32WindowData {
     width: u16,
     height: u16,
+    /// The size limits of the window's contents
+    min_size: Option<(u16, u16)>,
+    max_size: Option<(u16, u16)>,
+    /// Needed to bring back the right borders when decorations are turned back on
+    resizable: bool,
+    /// The style and placement of the window before it was made fullscreen. `None` while it
+    /// isn't fullscreen.
+    windowed: Option<(LONG, RECT)>,
+    /// The last state that was reported
+    state: WindowStateFlags,
+    /// The cursor that is shown over the window's contents
+    cursor: HCURSOR,
+    /// Whether the cursor was created from an image and has to be destroyed once it is replaced
+    owns_cursor: bool,
+    /// Whether the pointer is over the window. Win32 has no "enter" message, so this is used
+    /// to detect the first WM_MOUSEMOVE.
+    pointer_inside: bool,
+}
+
+unsafe fn get_modifiers() -> Modifiers {
+    let is_down = |key: i32| GetKeyState(key) < 0;
+    let is_toggled = |key: i32| GetKeyState(key) & 1 != 0;
+
+    let mut modifiers = Modifiers::NONE;
+
+    if is_down(VK_SHIFT) {
+        modifiers |= Modifiers::SHIFT;
+    }
+    if is_down(VK_CONTROL) {
+        modifiers |= Modifiers::CONTROL;
+    }
+    if is_down(VK_MENU) {
+        modifiers |= Modifiers::ALT;
+    }
+    if is_down(VK_LWIN) || is_down(VK_RWIN) {
+        modifiers |= Modifiers::SUPER;
+    }
+    if is_toggled(VK_CAPITAL) {
+        modifiers |= Modifiers::CAPS_LOCK;
+    }
+    if is_toggled(VK_NUMLOCK) {
+        modifiers |= Modifiers::NUM_LOCK;
+    }
+
+    modifiers
+}
+
+unsafe fn get_mouse_button_event(msg: UINT, wparam: WPARAM, lparam: LPARAM) -> MouseButtonEvent {
+    let button = match msg {
+        WM_LBUTTONDOWN | WM_LBUTTONUP => MouseButton::Left,
+        WM_MBUTTONDOWN | WM_MBUTTONUP => MouseButton::Middle,
+        WM_RBUTTONDOWN | WM_RBUTTONUP => MouseButton::Right,
+        _ if GET_XBUTTON_WPARAM(wparam) == XBUTTON1 => MouseButton::Back,
+        _ => MouseButton::Forward,
+    };
+
+    MouseButtonEvent {
+        x: GET_X_LPARAM(lparam) as i16,
+        y: GET_Y_LPARAM(lparam) as i16,
+        button,
+        modifiers: get_modifiers(),
+        timestamp: GetMessageTime() as u32,
+    }
+}
+
+unsafe fn get_key_event(wparam: WPARAM, lparam: LPARAM, repeat: bool) -> KeyEvent {
+    let virtual_key = wparam as u32;
+
+    KeyEvent {
+        key: keys::virtual_key_to_key(virtual_key, lparam),
+        raw_key: virtual_key,
+        modifiers: get_modifiers(),
+        repeat,
+        timestamp: GetMessageTime() as u32,
+    }
+}
+
+/// Gets the size of a window whose contents are `width` by `height`
+unsafe fn get_outer_size(window: HWND, (width, height): (u16, u16)) -> POINT {
+    let mut rect = RECT {
+        left: 0,
+        top: 0,
+        right: width as i32,
+        bottom: height as i32,
+    };
+
+    AdjustWindowRectEx(
+        addr_of_mut!(rect),
+        GetWindowLongA(window, GWL_STYLE) as DWORD,
+        FALSE,
+        GetWindowLongA(window, GWL_EXSTYLE) as DWORD,
+    );
+
+    POINT {
+        x: rect.right - rect.left,
+        y: rect.bottom - rect.top,
+    }
+}
+
+/// Picks the smallest icon that is at least `size` pixels wide, or the largest one if none are
+fn pick_icon(icons: &[RgbaImage], size: u16) -> Option<&RgbaImage> {
+    icons
+        .iter()
+        .filter(|icon| icon.width() >= size)
+        .min_by_key(|icon| icon.width())
+        .or_else(|| icons.iter().max_by_key(|icon| icon.width()))
+}
+
+unsafe fn create_icon(image: &RgbaImage) -> HICON {
+    // Win32 wants BGRA, which is ARGB in little-endian
+    let colors: Vec<u8> = image.argb_pixels().flat_map(u32::to_le_bytes).collect();
+
+    // The transparency comes from the alpha channel, so the mask is left empty. Its rows are
+    // padded to 16 bits.
+    let mask = vec![0u8; (image.width() as usize).div_ceil(16) * 2 * image.height() as usize];
+
+    CreateIcon(
+        ptr::null_mut(),
+        image.width() as i32,
+        image.height() as i32,
+        1,
+        32,
+        mask.as_ptr(),
+        colors.as_ptr(),
+    )
+}
+
+/// Replaces the big (alt-tab) or small (title bar and taskbar) icon of a window. The old icon
+/// is destroyed; only icons created by [create_icon] are ever set.
+unsafe fn replace_icon(window: HWND, kind: UINT, icon: HICON) {
+    let old_icon = SendMessageA(window, WM_SETICON, kind as WPARAM, icon as LPARAM) as HICON;
+
+    if !old_icon.is_null() {
+        DestroyIcon(old_icon);
+    }
+}
+
+unsafe fn load_cursor(cursor: CursorIcon) -> HCURSOR {
+    let name = match cursor {
+        CursorIcon::Default => IDC_ARROW,
+        CursorIcon::Text => IDC_IBEAM,
+        CursorIcon::Pointer => IDC_HAND,
+        CursorIcon::Crosshair => IDC_CROSS,
+        CursorIcon::Move | CursorIcon::Grab | CursorIcon::Grabbing => IDC_SIZEALL,
+        CursorIcon::Wait => IDC_WAIT,
+        CursorIcon::Progress => IDC_APPSTARTING,
+        CursorIcon::Help => IDC_HELP,
+        CursorIcon::NotAllowed => IDC_NO,
+        CursorIcon::ResizeHorizontal => IDC_SIZEWE,
+        CursorIcon::ResizeVertical => IDC_SIZENS,
+        CursorIcon::ResizeTopLeftBottomRight => IDC_SIZENWSE,
+        CursorIcon::ResizeTopRightBottomLeft => IDC_SIZENESW,
+    };
+
+    // System cursors are shared and never destroyed
+    LoadCursorW(ptr::null_mut(), name)
+}
+
+unsafe fn create_cursor(image: &RgbaImage, (hotspot_x, hotspot_y): (u16, u16)) -> HCURSOR {
+    let (width, height) = (image.width() as i32, image.height() as i32);
+
+    let colors: Vec<u8> = image.argb_pixels().flat_map(u32::to_le_bytes).collect();
+    let mask = vec![0u8; (image.width() as usize).div_ceil(16) * 2 * image.height() as usize];
+
+    let color_bitmap = CreateBitmap(width, height, 1, 32, colors.as_ptr().cast());
+    let mask_bitmap = CreateBitmap(width, height, 1, 1, mask.as_ptr().cast());
+
+    let mut info = ICONINFO {
+        fIcon: FALSE,
+        xHotspot: hotspot_x as DWORD,
+        yHotspot: hotspot_y as DWORD,
+        hbmMask: mask_bitmap,
+        hbmColor: color_bitmap,
+    };
+
+    // The cursor gets its own copies of the bitmaps
+    let cursor = CreateIconIndirect(addr_of_mut!(info));
+
+    DeleteObject(color_bitmap.cast());
+    DeleteObject(mask_bitmap.cast());
+
+    cursor
+}
+
+/// Changes the cursor of a window and destroys the old one if it was created from an image
+unsafe fn replace_cursor(window_data: &mut Win32WindowData, cursor: HCURSOR, owns_cursor: bool) {
+    let old_cursor = mem::replace(&mut window_data.cursor, cursor);
+    let owned_old_cursor = mem::replace(&mut window_data.owns_cursor, owns_cursor);
+
+    // WM_SETCURSOR only comes once the pointer moves
+    if window_data.pointer_inside {
+        SetCursor(cursor);
+    }
+
+    if owned_old_cursor {
+        DestroyCursor(old_cursor);
+    }
+}
+
+/// Gets the style bits that decide what the window's frame looks like
+fn get_frame_style(decorations: bool, resizable: bool) -> DWORD {
+    if !decorations {
+        WS_POPUP
+    } else if resizable {
+        WS_OVERLAPPEDWINDOW
+    } else {
+        WS_OVERLAPPEDWINDOW & !(WS_THICKFRAME | WS_MAXIMIZEBOX)
+    }
+}
+
+/// Gets the clipboard format that a MIME type is stored as. Text uses the native format so
+/// that other programs can paste it.
+unsafe fn get_clipboard_format(mime_type: &str) -> UINT {
+    if mime_type == TEXT_MIME_TYPE {
+        return CF_UNICODETEXT;
+    }
+
+    let name: Vec<i8> = mime_type
+        .bytes()
+        .filter(|&b| b != 0)
+        .chain(iter::once(0))
+        .map(|n| n as i8)
+        .collect();
+
+    RegisterClipboardFormatA(name.as_ptr())
+}
+
+/// Gets the MIME type of a clipboard format, or `None` if it isn't one that wwind offers
+unsafe fn get_clipboard_mime_type(format: UINT) -> Option<String> {
+    if format == CF_UNICODETEXT {
+        return Some(TEXT_MIME_TYPE.to_owned());
+    }
+
+    // The predefined formats don't have names
+    let mut name = [0i8; 256];
+    let length = GetClipboardFormatNameA(format, name.as_mut_ptr(), name.len() as i32);
+    if length <= 0 {
+        return None;
+    }
+
+    let name: Vec<u8> = name[..length as usize].iter().map(|&c| c as u8).collect();
+    let name = String::from_utf8_lossy(&name).into_owned();
+
+    name.contains('/').then_some(name)
+}
+
+/// Copies data into memory that the clipboard takes ownership of. The clipboard has to be open.
+unsafe fn set_clipboard_data(format: UINT, data: &[u8]) {
+    let memory = GlobalAlloc(GMEM_MOVEABLE, data.len().max(1));
+    if memory.is_null() {
+        return;
+    }
+
+    let pointer = GlobalLock(memory) as *mut u8;
+    if pointer.is_null() {
+        GlobalFree(memory);
+        return;
+    }
+
+    ptr::copy_nonoverlapping(data.as_ptr(), pointer, data.len());
+    GlobalUnlock(memory);
+
+    if SetClipboardData(format, memory).is_null() {
+        GlobalFree(memory);
+    }
+}
+
+/// The clipboard has to be open
+unsafe fn get_clipboard_data(format: UINT) -> Option<Vec<u8>> {
+    let memory = GetClipboardData(format);
+    if memory.is_null() {
+        return None;
+    }
+
+    let pointer = GlobalLock(memory) as *const u8;
+    if pointer.is_null() {
+        return None;
+    }
+
+    let data = slice::from_raw_parts(pointer, GlobalSize(memory)).to_vec();
+    GlobalUnlock(memory);
+
+    Some(data)
+}
+
+/// Converts UTF-8 text to the null-terminated UTF-16 of `CF_UNICODETEXT`
+fn to_unicode_text(text: &[u8]) -> Vec<u8> {
+    String::from_utf8_lossy(text)
+        .encode_utf16()
+        .chain(iter::once(0))
+        .flat_map(u16::to_ne_bytes)
+        .collect()
+}
+
+fn from_unicode_text(text: &[u8]) -> Vec<u8> {
+    let text: Vec<u16> = text
+        .chunks_exact(2)
+        .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+
+    String::from_utf16_lossy(&text).into_bytes()
+}
+
+/// Sends a StateChange event if the state is different from the last one that was sent
+unsafe fn report_state(window: HWND, window_data: &mut Win32WindowData, state: WindowStateFlags) {
+    if state == window_data.state {
+        return;
+    }
+
+    // Windows that are created before the event loop starts report their state later
+    if let Some(on_event) = ON_EVENT {
+        window_data.state = state;
+
+        on_event(WWindCoreEvent::StateChange(window.into(), state));
+    }
+}
+
+/// Gets the monitors in the order that Windows lists them
+unsafe fn enumerate_monitors() -> Vec<Monitor> {
+    unsafe extern "system" fn add_monitor(
+        monitor: HMONITOR,
+        _dc: HDC,
+        _rect: LPRECT,
+        monitors: LPARAM,
+    ) -> BOOL {
+        let monitors = &mut *(monitors as *mut Vec<Monitor>);
+
+        let mut info: MONITORINFOEXA = mem::zeroed();
+        info.cbSize = mem::size_of::<MONITORINFOEXA>() as DWORD;
+
+        if GetMonitorInfoA(monitor, addr_of_mut!(info).cast()) == 0 {
+            return TRUE;
+        }
+
+        let device = info.szDevice.as_ptr();
+
+        let mut mode: DEVMODEA = mem::zeroed();
+        mode.dmSize = mem::size_of::<DEVMODEA>() as WORD;
+
+        // Zero and one mean that the default rate of the hardware is used
+        let refresh_rate =
+            (EnumDisplaySettingsA(device, ENUM_CURRENT_SETTINGS, addr_of_mut!(mode)) != 0
+                && mode.dmDisplayFrequency > 1)
+                .then(|| mode.dmDisplayFrequency * 1000);
+
+        let dc = CreateDCA(device, ptr::null(), ptr::null(), ptr::null());
+        let (physical_width, physical_height) = if dc.is_null() {
+            (0, 0)
+        } else {
+            let size = (GetDeviceCaps(dc, HORZSIZE), GetDeviceCaps(dc, VERTSIZE));
+            DeleteDC(dc);
+            size
+        };
+
+        let rect = info.rcMonitor;
+
+        monitors.push(Monitor {
+            name: CStr::from_ptr(device).to_string_lossy().into_owned(),
+            x: rect.left as i16,
+            y: rect.top as i16,
+            width: (rect.right - rect.left) as u16,
+            height: (rect.bottom - rect.top) as u16,
+            refresh_rate,
+            physical_width: physical_width.max(0) as u32,
+            physical_height: physical_height.max(0) as u32,
+            primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+        });
+
+        TRUE
+    }
+
+    let mut monitors = Vec::new();
+
+    EnumDisplayMonitors(
+        ptr::null_mut(),
+        ptr::null(),
+        Some(add_monitor),
+        addr_of_mut!(monitors) as LPARAM,
+    );
+
+    monitors
+}
+
+/// Removes the borders of a window and makes it cover the monitor that it is on, or undoes that
+unsafe fn set_fullscreen(window: HWND, window_data: &mut Win32WindowData, fullscreen: bool) {
+    if fullscreen == window_data.windowed.is_some() {
+        return;
+    }
+
+    if fullscreen {
+        let monitor = MonitorFromWindow(window, MONITOR_DEFAULTTONEAREST);
+
+        let mut info: MONITORINFO = mem::zeroed();
+        info.cbSize = mem::size_of::<MONITORINFO>() as DWORD;
+
+        if GetMonitorInfoA(monitor, addr_of_mut!(info)) == 0 {
+            return;
+        }
+
+        let style = GetWindowLongA(window, GWL_STYLE);
+        let mut rect = mem::zeroed();
+        GetWindowRect(window, addr_of_mut!(rect));
+
+        window_data.windowed = Some((style, rect));
+
+        let rect = info.rcMonitor;
+
+        SetWindowLongA(
+            window,
+            GWL_STYLE,
+            (style as DWORD & !WS_OVERLAPPEDWINDOW | WS_POPUP) as LONG,
+        );
+        SetWindowPos(
+            window,
+            HWND_TOP,
+            rect.left,
+            rect.top,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+            SWP_NOOWNERZORDER | SWP_FRAMECHANGED,
+        );
+    } else if let Some((style, rect)) = window_data.windowed.take() {
+        SetWindowLongA(window, GWL_STYLE, style);
+        SetWindowPos(
+            window,
+            ptr::null_mut(),
+            rect.left,
+            rect.top,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+            SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_FRAMECHANGED,
+        );
+    }
+
+    let mut state = window_data.state;
+    state.set(WindowStateFlags::FULLSCREEN, fullscreen);
+    report_state(window, window_data, state);
 }
 
 /// Neccesary because of "SendMessage" messages. Ugh
@@ -58,22 +570,270 @@ unsafe extern "system" fn window_proc(
             let width = LOWORD(lparam as u32);
             let height = HIWORD(lparam as u32);
 
-            let window_data =
-                &mut *(GetWindowLongPtrA(window, GWLP_USERDATA) as *mut Win32WindowData);
+            // This is also sent while the window is created, before its data is set
+            let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *mut Win32WindowData;
+
+            if let Some(window_data) = window_data.as_mut() {
+                if width <= window_data.width && height <= window_data.height {
+                    let rect = RECT {
+                        left: 0,
+                        bottom: 0,
+                        top: height as i32,
+                        right: width as i32,
+                    };
+
+                    RedrawWindow(window, addr_of!(rect), ptr::null_mut(), RDW_INTERNALPAINT);
+                }
+
+                window_data.width = width;
+                window_data.height = height;
+
+                if let Some(on_event) = ON_EVENT {
+                    on_event(WWindCoreEvent::Resize(window.into(), width, height));
+                }
+
+                let mut state = WindowStateFlags::NONE;
+                state.set(WindowStateFlags::MAXIMIZED, wparam == SIZE_MAXIMIZED);
+                state.set(WindowStateFlags::MINIMIZED, wparam == SIZE_MINIMIZED);
+                state.set(WindowStateFlags::FULLSCREEN, window_data.windowed.is_some());
+
+                report_state(window, window_data, state);
+            }
+        }
+        WM_GETMINMAXINFO => {
+            // This is also sent while the window is created, before its data is set
+            let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *const Win32WindowData;
+
+            if let Some(window_data) = window_data.as_ref() {
+                let info = &mut *(lparam as *mut MINMAXINFO);
+
+                if let Some(min_size) = window_data.min_size {
+                    info.ptMinTrackSize = get_outer_size(window, min_size);
+                }
+                if let Some(max_size) = window_data.max_size {
+                    info.ptMaxTrackSize = get_outer_size(window, max_size);
+                }
+
+                return 0;
+            }
+        }
+        WM_MOVE => {
+            if let Some(on_event) = ON_EVENT {
+                let x = GET_X_LPARAM(lparam) as i16;
+                let y = GET_Y_LPARAM(lparam) as i16;
+
+                on_event(WWindCoreEvent::Move(window.into(), x, y));
+            }
+        }
+        WM_KEYDOWN | WM_SYSKEYDOWN => {
+            if let Some(on_event) = ON_EVENT {
+                // Bit 30 is set if the key was already down
+                let repeat = lparam & (1 << 30) != 0;
+                let event = get_key_event(wparam, lparam, repeat);
+
+                on_event(WWindCoreEvent::Keydown(window.into(), event));
+            }
+        }
+        WM_KEYUP | WM_SYSKEYUP => {
+            if let Some(on_event) = ON_EVENT {
+                let event = get_key_event(wparam, lparam, false);
+
+                on_event(WWindCoreEvent::Keyup(window.into(), event));
+            }
+        }
+        WM_CHAR => {
+            let unit = wparam as u16;
+
+            if (0xd800..0xdc00).contains(&unit) {
+                HIGH_SURROGATE = Some(unit);
+                return 0;
+            }
+
+            let units = match (*addr_of_mut!(HIGH_SURROGATE)).take() {
+                Some(high_surrogate) => vec![high_surrogate, unit],
+                None => vec![unit],
+            };
+
+            let character = char::decode_utf16(units).next().and_then(Result::ok);
+
+            if let (Some(on_event), Some(character)) = (ON_EVENT, character) {
+                if !character.is_control() {
+                    on_event(WWindCoreEvent::TextInput(window.into(), character));
+                }
+            }
+
+            return 0;
+        }
+        WM_LBUTTONDOWN | WM_MBUTTONDOWN | WM_RBUTTONDOWN | WM_XBUTTONDOWN => {
+            // Makes sure that we still get the button release if the pointer leaves the window
+            SetCapture(window);
+
+            if let Some(on_event) = ON_EVENT {
+                let event = get_mouse_button_event(msg, wparam, lparam);
+
+                on_event(WWindCoreEvent::MouseDown(window.into(), event));
+            }
+        }
+        WM_LBUTTONUP | WM_MBUTTONUP | WM_RBUTTONUP | WM_XBUTTONUP => {
+            ReleaseCapture();
+
+            if let Some(on_event) = ON_EVENT {
+                let event = get_mouse_button_event(msg, wparam, lparam);
+
+                on_event(WWindCoreEvent::MouseUp(window.into(), event));
+            }
+        }
+        WM_DROPFILES => {
+            let drop = wparam as HDROP;
+            let count = DragQueryFileW(drop, 0xFFFFFFFF, ptr::null_mut(), 0);
 
-            if width <= window_data.width && height <= window_data.height {
-                let rect = RECT {
-                    left: 0,
-                    bottom: 0,
-                    top: height as i32,
-                    right: width as i32,
+            let paths = (0..count)
+                .map(|i| {
+                    // The length doesn't include the null terminator
+                    let len = DragQueryFileW(drop, i, ptr::null_mut(), 0);
+                    let mut path = vec![0u16; len as usize + 1];
+                    DragQueryFileW(drop, i, path.as_mut_ptr(), len + 1);
+                    path.truncate(len as usize);
+
+                    PathBuf::from(OsString::from_wide(&path))
+                })
+                .collect();
+
+            let mut point = POINT { x: 0, y: 0 };
+            DragQueryPoint(drop, &mut point);
+            DragFinish(drop);
+
+            if let Some(on_event) = ON_EVENT {
+                let event = DropEvent {
+                    x: point.x as i16,
+                    y: point.y as i16,
+                    payload: DropPayload::Files(paths),
                 };
 
-                RedrawWindow(window, addr_of!(rect), ptr::null_mut(), RDW_INTERNALPAINT);
+                on_event(WWindCoreEvent::DragDrop(window.into(), event));
             }
 
-            window_data.width = width;
-            window_data.height = height;
+            return 0;
+        }
+        WM_DISPLAYCHANGE => {
+            let monitors = enumerate_monitors();
+
+            // Only the first window that is told about a change reports it
+            if monitors != *addr_of!(MONITORS) {
+                *addr_of_mut!(MONITORS) = monitors;
+
+                if let Some(on_event) = ON_EVENT {
+                    on_event(WWindCoreEvent::MonitorsChanged);
+                }
+            }
+        }
+        WM_SETCURSOR => {
+            let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *const Win32WindowData;
+
+            // The borders keep their resize cursors
+            if let Some(window_data) = window_data.as_ref() {
+                if LOWORD(lparam as u32) as LRESULT == HTCLIENT {
+                    SetCursor(window_data.cursor);
+
+                    return 1;
+                }
+            }
+        }
+        WM_MOUSEMOVE => {
+            let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *mut Win32WindowData;
+
+            if let Some(window_data) = window_data.as_mut() {
+                let event = MouseMoveEvent {
+                    x: GET_X_LPARAM(lparam) as i16,
+                    y: GET_Y_LPARAM(lparam) as i16,
+                    modifiers: get_modifiers(),
+                    timestamp: GetMessageTime() as u32,
+                };
+
+                if !window_data.pointer_inside {
+                    window_data.pointer_inside = true;
+
+                    // Asks for a WM_MOUSELEAVE when the pointer leaves the window
+                    let mut track: TRACKMOUSEEVENT = mem::zeroed();
+                    track.cbSize = mem::size_of::<TRACKMOUSEEVENT>() as u32;
+                    track.dwFlags = TME_LEAVE;
+                    track.hwndTrack = window;
+                    TrackMouseEvent(addr_of_mut!(track));
+
+                    if let Some(on_event) = ON_EVENT {
+                        on_event(WWindCoreEvent::PointerEnter(window.into(), event));
+                    }
+                }
+
+                if let Some(on_event) = ON_EVENT {
+                    on_event(WWindCoreEvent::MouseMove(window.into(), event));
+                }
+            }
+        }
+        WM_MOUSELEAVE => {
+            let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *mut Win32WindowData;
+
+            if let Some(window_data) = window_data.as_mut() {
+                window_data.pointer_inside = false;
+
+                if let Some(on_event) = ON_EVENT {
+                    let mut point = MaybeUninit::uninit();
+                    GetCursorPos(point.as_mut_ptr());
+                    let mut point = point.assume_init();
+                    ScreenToClient(window, addr_of_mut!(point));
+
+                    let event = MouseMoveEvent {
+                        x: point.x as i16,
+                        y: point.y as i16,
+                        modifiers: get_modifiers(),
+                        timestamp: GetMessageTime() as u32,
+                    };
+
+                    on_event(WWindCoreEvent::PointerLeave(window.into(), event));
+                }
+            }
+        }
+        WM_MOUSEWHEEL | WM_MOUSEHWHEEL => {
+            if let Some(on_event) = ON_EVENT {
+                let delta = GET_WHEEL_DELTA_WPARAM(wparam);
+                let steps = delta as f64 / WHEEL_DELTA as f64;
+
+                // Unlike other mouse messages, the position is relative to the screen
+                let mut point = POINT {
+                    x: GET_X_LPARAM(lparam),
+                    y: GET_Y_LPARAM(lparam),
+                };
+                ScreenToClient(window, addr_of_mut!(point));
+
+                // Positive wheel deltas scroll up, but to the right
+                let (delta_x, delta_y) = if msg == WM_MOUSEWHEEL {
+                    (0.0, -steps)
+                } else {
+                    (steps, 0.0)
+                };
+
+                let event = ScrollEvent {
+                    x: point.x as i16,
+                    y: point.y as i16,
+                    delta_x,
+                    delta_y,
+                    // High resolution wheels and touchpads send fractions of WHEEL_DELTA
+                    precise: delta % WHEEL_DELTA != 0,
+                    modifiers: get_modifiers(),
+                    timestamp: GetMessageTime() as u32,
+                };
+
+                on_event(WWindCoreEvent::Scroll(window.into(), event));
+            }
+
+            return 0;
+        }
+        WM_SETFOCUS | WM_KILLFOCUS => {
+            if let Some(on_event) = ON_EVENT {
+                let focused = msg == WM_SETFOCUS;
+
+                on_event(WWindCoreEvent::FocusChange(window.into(), focused));
+            }
         }
         WM_PAINT => {
             if let Some(on_event) = ON_EVENT {
@@ -87,9 +847,9 @@ unsafe extern "system" fn window_proc(
 
                 let rect_region = RectRegion {
                     x: rect.left as u16,
-                    y: rect.bottom as u16,
-                    width: rect.right as u16,
-                    height: rect.top as u16,
+                    y: rect.top as u16,
+                    width: (rect.right - rect.left) as u16,
+                    height: (rect.bottom - rect.top) as u16,
                 };
 
                 on_event(WWindCoreEvent::Expose(window.into(), rect_region));
@@ -109,28 +869,311 @@ pub struct WindowsDrawingContext {
 }
 
 impl CoreStateImplementation for Win32State {
-    type Error = Infallible;
+    type Error = Win32Error;
 
     type Window = HWND;
 
     type DrawingContext = WindowsDrawingContext;
 
+    type Waker = ThreadWaker;
+
     unsafe fn new() -> Result<Self, Self::Error> {
         let hinst = GetModuleHandleA(ptr::null());
         let pen = GetStockObject(DC_PEN as i32) as *mut _;
         let brush = GetStockObject(DC_BRUSH as i32) as *mut _;
 
-        Ok(Win32State { hinst, pen, brush })
+        let thread_id = GetCurrentThreadId();
+
+        let clipboard_window = CreateWindowExA(
+            0,
+            c"STATIC".as_ptr(),
+            ptr::null(),
+            0,
+            0,
+            0,
+            0,
+            0,
+            HWND_MESSAGE,
+            ptr::null_mut(),
+            hinst,
+            ptr::null_mut(),
+        );
+
+        if clipboard_window.is_null() {
+            return Err(Win32Error::last());
+        }
+
+        // Later WM_DISPLAYCHANGE messages are compared against this
+        *addr_of_mut!(MONITORS) = enumerate_monitors();
+
+        Ok(Win32State {
+            hinst,
+            pen,
+            brush,
+            thread_id,
+            clipboard_window,
+            primary: SelectionContents::default(),
+        })
     }
 
-    fn add_window(
+    fn set_window_position(
         &mut self,
+        window: Self::Window,
         x: i16,
         y: i16,
-        height: u16,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            SetWindowPos(
+                window,
+                ptr::null_mut(),
+                x as i32,
+                y as i32,
+                0,
+                0,
+                SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE,
+            )
+        };
+
+        Ok(())
+    }
+
+    fn set_window_size(
+        &mut self,
+        window: Self::Window,
         width: u16,
-        title: &str,
-    ) -> Result<Self::Window, Self::Error> {
+        height: u16,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            let size = get_outer_size(window, (width, height));
+
+            SetWindowPos(
+                window,
+                ptr::null_mut(),
+                0,
+                0,
+                size.x,
+                size.y,
+                SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE,
+            )
+        };
+
+        Ok(())
+    }
+
+    fn set_window_visible(
+        &mut self,
+        window: Self::Window,
+        visible: bool,
+    ) -> Result<(), Self::Error> {
+        unsafe { ShowWindow(window, if visible { SW_SHOW } else { SW_HIDE }) };
+
+        Ok(())
+    }
+
+    fn focus_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        unsafe { SetForegroundWindow(window) };
+
+        Ok(())
+    }
+
+    fn raise_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        unsafe {
+            SetWindowPos(
+                window,
+                HWND_TOP,
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+            )
+        };
+
+        Ok(())
+    }
+
+    fn set_window_fullscreen(
+        &mut self,
+        window: Self::Window,
+        fullscreen: bool,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *mut Win32WindowData;
+
+            if let Some(window_data) = window_data.as_mut() {
+                set_fullscreen(window, window_data, fullscreen);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set_window_maximized(
+        &mut self,
+        window: Self::Window,
+        maximized: bool,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            if maximized {
+                ShowWindow(window, SW_MAXIMIZE);
+            } else if IsZoomed(window) != 0 {
+                ShowWindow(window, SW_RESTORE);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn minimize_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        unsafe { ShowWindow(window, SW_MINIMIZE) };
+
+        Ok(())
+    }
+
+    fn set_window_decorations(
+        &mut self,
+        window: Self::Window,
+        decorations: bool,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *mut Win32WindowData;
+
+            let Some(window_data) = window_data.as_mut() else {
+                return Ok(());
+            };
+
+            let frame_style = get_frame_style(decorations, window_data.resizable);
+            let with_frame = |style: LONG| {
+                (style as DWORD & !(WS_OVERLAPPEDWINDOW | WS_POPUP) | frame_style) as LONG
+            };
+
+            // Fullscreen windows get the new frame once they leave fullscreen
+            if let Some((style, _)) = &mut window_data.windowed {
+                *style = with_frame(*style);
+
+                return Ok(());
+            }
+
+            SetWindowLongA(
+                window,
+                GWL_STYLE,
+                with_frame(GetWindowLongA(window, GWL_STYLE)),
+            );
+
+            // Keep the size of the contents the same
+            let size = get_outer_size(window, (window_data.width, window_data.height));
+
+            SetWindowPos(
+                window,
+                ptr::null_mut(),
+                0,
+                0,
+                size.x,
+                size.y,
+                SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn set_window_icon(
+        &mut self,
+        window: Self::Window,
+        icons: &[RgbaImage],
+    ) -> Result<(), Self::Error> {
+        for (kind, metric) in [(ICON_BIG, SM_CXICON), (ICON_SMALL, SM_CXSMICON)] {
+            unsafe {
+                let size = GetSystemMetrics(metric) as u16;
+
+                let icon = pick_icon(icons, size).map_or(ptr::null_mut(), |icon| create_icon(icon));
+
+                replace_icon(window, kind, icon);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set_window_cursor(
+        &mut self,
+        window: Self::Window,
+        cursor: CursorIcon,
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *mut Win32WindowData;
+
+            if let Some(window_data) = window_data.as_mut() {
+                replace_cursor(window_data, load_cursor(cursor), false);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set_window_custom_cursor(
+        &mut self,
+        window: Self::Window,
+        image: &RgbaImage,
+        hotspot: (u16, u16),
+    ) -> Result<(), Self::Error> {
+        unsafe {
+            let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *mut Win32WindowData;
+
+            if let Some(window_data) = window_data.as_mut() {
+                replace_cursor(window_data, create_cursor(image, hotspot), true);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start_window_drag(
+        &mut self,
+        window: Self::Window,
+        edge: Option<ResizeEdge>,
+    ) -> Result<(), Self::Error> {
+        let hit_test = match edge {
+            None => HTCAPTION,
+            Some(ResizeEdge::Top) => HTTOP,
+            Some(ResizeEdge::Bottom) => HTBOTTOM,
+            Some(ResizeEdge::Left) => HTLEFT,
+            Some(ResizeEdge::Right) => HTRIGHT,
+            Some(ResizeEdge::TopLeft) => HTTOPLEFT,
+            Some(ResizeEdge::TopRight) => HTTOPRIGHT,
+            Some(ResizeEdge::BottomLeft) => HTBOTTOMLEFT,
+            Some(ResizeEdge::BottomRight) => HTBOTTOMRIGHT,
+        };
+
+        unsafe {
+            let mut cursor = mem::zeroed();
+            GetCursorPos(addr_of_mut!(cursor));
+
+            // Pretend that the frame was pressed. This is posted instead of sent, because
+            // Windows runs its own event loop until the button is released.
+            ReleaseCapture();
+            PostMessageA(
+                window,
+                WM_NCLBUTTONDOWN,
+                hit_test as WPARAM,
+                MAKELONG(cursor.x as u16, cursor.y as u16) as LPARAM,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Needs an OLE IDataObject and IDropSource, which aren't implemented yet
+    fn start_data_drag(
+        &mut self,
+        _window: Self::Window,
+        _payloads: &[DropPayload],
+        _image: Option<(&RgbaImage, (u16, u16))>,
+    ) -> Result<(), Self::Error> {
+        Err(Win32Error::Unsupported)
+    }
+
+    fn add_window(&mut self, builder: &WindowBuilder) -> Result<Self::Window, Self::Error> {
         const CLASS_NAME: &[u8] = b"WWIND Window\0";
 
         static mut WINDOW_CLASS_REGISTERED: bool = false;
@@ -149,7 +1192,8 @@ impl CoreStateImplementation for Win32State {
         }
 
         // C String moment
-        let title: Vec<i8> = title
+        let title: Vec<i8> = builder
+            .title
             .as_bytes()
             .iter()
             .copied()
@@ -158,16 +1202,34 @@ impl CoreStateImplementation for Win32State {
             .map(|n| n as i8)
             .collect();
 
+        let (width, height) = builder.clamped_size();
+        let (x, y) = builder
+            .position
+            .map_or((CW_USEDEFAULT, CW_USEDEFAULT), |(x, y)| {
+                (x as i32, y as i32)
+            });
+
+        let style = get_frame_style(builder.decorations, builder.resizable);
+
+        // The size passed to CreateWindowExA includes the borders and title bar
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: width as i32,
+            bottom: height as i32,
+        };
+        unsafe { AdjustWindowRectEx(addr_of_mut!(rect), style, FALSE, 0) };
+
         let window = unsafe {
             CreateWindowExA(
                 0,
-                dbg!(CLASS_NAME.as_ptr() as *const i8),
-                dbg!(title.as_ptr()),
-                WS_OVERLAPPEDWINDOW,
-                x as i32,
-                y as i32,
-                width as i32,
-                height as i32,
+                CLASS_NAME.as_ptr() as *const i8,
+                title.as_ptr(),
+                style,
+                x,
+                y,
+                rect.right - rect.left,
+                rect.bottom - rect.top,
                 ptr::null_mut(),
                 ptr::null_mut(),
                 self.hinst,
@@ -175,7 +1237,28 @@ impl CoreStateImplementation for Win32State {
             )
         };
 
-        let window_data = Box::into_raw(Box::new(Win32WindowData { width, height }));
+        if window.is_null() {
+            return Err(Win32Error::last());
+        }
+
+        let (min_size, max_size) = if builder.resizable {
+            (builder.min_size, builder.max_size)
+        } else {
+            (None, None)
+        };
+
+        let window_data = Box::into_raw(Box::new(Win32WindowData {
+            width,
+            height,
+            min_size,
+            max_size,
+            resizable: builder.resizable,
+            windowed: None,
+            state: WindowStateFlags::NONE,
+            cursor: unsafe { load_cursor(CursorIcon::Default) },
+            owns_cursor: false,
+            pointer_inside: false,
+        }));
 
         unsafe {
             let dc = GetDC(window);
@@ -186,15 +1269,25 @@ impl CoreStateImplementation for Win32State {
             SetWindowLongPtrA(window, GWLP_USERDATA, window_data as isize);
         }
 
-        println!("c");
-        dbg!(window);
+        if builder.state == WindowState::Fullscreen {
+            // Leaving fullscreen brings back the style that the window was created with
+            unsafe { set_fullscreen(window, &mut *window_data, true) };
+        }
+
+        if builder.visible {
+            let show_command = match builder.state {
+                WindowState::Normal | WindowState::Fullscreen => SW_NORMAL,
+                WindowState::Maximized => SW_MAXIMIZE,
+                WindowState::Minimized => SW_MINIMIZE,
+            };
 
-        unsafe { ShowWindow(dbg!(window), SW_NORMAL) };
+            unsafe { ShowWindow(window, show_command) };
+        }
 
         Ok(window)
     }
 
-    fn set_window_title(&mut self, window: Self::Window, title: &str) {
+    fn set_window_title(&mut self, window: Self::Window, title: &str) -> Result<(), Self::Error> {
         // C-String moment
         let title_vec: Vec<i8> = title
             .as_bytes()
@@ -205,26 +1298,179 @@ impl CoreStateImplementation for Win32State {
             .map(|n| n as i8)
             .collect();
 
-        unsafe { SetWindowTextA(window, title_vec.as_ptr()) };
+        if unsafe { SetWindowTextA(window, title_vec.as_ptr()) } == 0 {
+            return Err(Win32Error::last());
+        }
+
+        Ok(())
+    }
+
+    fn accept_window_drops(&mut self, window: Self::Window) {
+        unsafe { DragAcceptFiles(window, TRUE) };
     }
 
     unsafe fn destroy_window(&mut self, window: Self::Window) {
+        replace_icon(window, ICON_BIG, ptr::null_mut());
+        replace_icon(window, ICON_SMALL, ptr::null_mut());
+
+        let window_data = GetWindowLongPtrA(window, GWLP_USERDATA) as *const Win32WindowData;
+
+        if let Some(window_data) = window_data.as_ref() {
+            if window_data.owns_cursor {
+                DestroyCursor(window_data.cursor);
+            }
+        }
+
         DestroyWindow(window);
     }
 
-    unsafe fn wait_for_events(&mut self, on_event: &mut unsafe fn(WWindCoreEvent)) {
+    unsafe fn wait_for_events(
+        &mut self,
+        on_event: &mut unsafe fn(WWindCoreEvent),
+        timeout: Option<Duration>,
+    ) -> Result<bool, Self::Error> {
         // TODO: make GetMessageA close the window when it returns false
 
         ON_EVENT = Some(*on_event);
 
         let mut msg = MaybeUninit::uninit();
 
-        if GetMessageA(msg.as_mut_ptr(), ptr::null_mut(), 0, 0) == 0 {
-            return;
+        if let Some(timeout) = timeout {
+            if PeekMessageA(msg.as_mut_ptr(), ptr::null_mut(), 0, 0, PM_REMOVE) == 0 {
+                // Rounded up so that we don't wake up right before the timeout and spin
+                let timeout = timeout
+                    .as_nanos()
+                    .div_ceil(1_000_000)
+                    .try_into()
+                    .unwrap_or(INFINITE - 1);
+
+                MsgWaitForMultipleObjects(0, ptr::null(), FALSE, timeout, QS_ALLINPUT);
+
+                if PeekMessageA(msg.as_mut_ptr(), ptr::null_mut(), 0, 0, PM_REMOVE) == 0 {
+                    return Ok(false);
+                }
+            }
+
+            if msg.assume_init_ref().message == WM_QUIT {
+                return Ok(false);
+            }
+        } else if GetMessageA(msg.as_mut_ptr(), ptr::null_mut(), 0, 0) == 0 {
+            return Ok(false);
         }
 
         TranslateMessage(msg.as_ptr());
         DispatchMessageA(msg.as_ptr());
+
+        Ok(true)
+    }
+
+    fn get_waker(&self) -> Self::Waker {
+        ThreadWaker(self.thread_id)
+    }
+
+    fn set_selection(
+        &mut self,
+        selection: Selection,
+        contents: SelectionContents,
+    ) -> Result<(), Self::Error> {
+        if selection == Selection::Primary {
+            self.primary = contents;
+            return Ok(());
+        }
+
+        unsafe {
+            if contents.is_empty() && GetClipboardOwner() != self.clipboard_window {
+                return Ok(());
+            }
+
+            // Fails if another program has the clipboard open
+            if OpenClipboard(self.clipboard_window) == 0 {
+                return Ok(());
+            }
+
+            EmptyClipboard();
+
+            for (mime_type, data) in contents.iter() {
+                let format = get_clipboard_format(mime_type);
+
+                if format == CF_UNICODETEXT {
+                    set_clipboard_data(format, &to_unicode_text(data));
+                } else if format != 0 {
+                    set_clipboard_data(format, data);
+                }
+            }
+
+            CloseClipboard();
+        }
+
+        Ok(())
+    }
+
+    fn get_selection(
+        &mut self,
+        selection: Selection,
+        mime_type: &str,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        if selection == Selection::Primary {
+            return Ok(self.primary.get(mime_type).map(<[u8]>::to_vec));
+        }
+
+        unsafe {
+            let format = get_clipboard_format(mime_type);
+
+            if format == 0
+                || IsClipboardFormatAvailable(format) == 0
+                || OpenClipboard(self.clipboard_window) == 0
+            {
+                return Ok(None);
+            }
+
+            let data = get_clipboard_data(format);
+            CloseClipboard();
+
+            if format == CF_UNICODETEXT {
+                return Ok(data.map(|data| from_unicode_text(&data)));
+            }
+
+            // The size of the memory may have been rounded up, so there can be padding
+            Ok(data)
+        }
+    }
+
+    fn get_selection_types(&mut self, selection: Selection) -> Result<Vec<String>, Self::Error> {
+        if selection == Selection::Primary {
+            return Ok(self.primary.types());
+        }
+
+        let mut types = Vec::new();
+
+        unsafe {
+            if OpenClipboard(self.clipboard_window) == 0 {
+                return Ok(types);
+            }
+
+            let mut format = 0;
+            loop {
+                format = EnumClipboardFormats(format);
+                if format == 0 {
+                    break;
+                }
+
+                if let Some(mime_type) = get_clipboard_mime_type(format) {
+                    if !types.contains(&mime_type) {
+                        types.push(mime_type);
+                    }
+                }
+            }
+
+            CloseClipboard();
+        }
+
+        Ok(types)
+    }
+
+    fn get_monitors(&self) -> Result<Vec<Monitor>, Self::Error> {
+        Ok(unsafe { enumerate_monitors() })
     }
 
     fn flush(&mut self) -> Result<(), Self::Error> {
@@ -269,8 +1515,8 @@ impl CoreStateImplementation for Win32State {
     ) -> Result<(), Self::Error> {
         let left = rectangle.x as i32;
         let bottom = rectangle.y as i32;
-        let right = (rectangle.x + rectangle.width) as i32;
-        let top = (rectangle.y + rectangle.height) as i32;
+        let right = rectangle.get_right_x() as i32;
+        let top = rectangle.get_bottom_y() as i32;
 
         let rect = RECT {
             left,
@@ -286,6 +1532,21 @@ impl CoreStateImplementation for Win32State {
         Ok(())
     }
 
+    #[cfg(raw_window_handle)]
+    fn raw_window_handle(&self, window: Self::Window) -> Result<RawWindowHandle, HandleError> {
+        let hwnd = NonZeroIsize::new(window as isize).ok_or(HandleError::Unavailable)?;
+
+        let mut handle = Win32WindowHandle::new(hwnd);
+        handle.hinstance = NonZeroIsize::new(self.hinst as isize);
+
+        Ok(handle.into())
+    }
+
+    #[cfg(raw_window_handle)]
+    fn raw_display_handle(&self) -> Result<RawDisplayHandle, HandleError> {
+        Ok(WindowsDisplayHandle::new().into())
+    }
+
     unsafe fn get_context(&mut self, window: Self::Window) -> Self::DrawingContext {
         let context = GetDC(window);
 
@@ -303,11 +1564,4 @@ impl CoreStateImplementation for Win32State {
         }
         Ok(())
     }
-
-    fn get_size(&self, window: Self::Window) -> (u16, u16) {
-        let window_data =
-            unsafe { &*(GetWindowLongPtrA(window, GWLP_USERDATA) as *const Win32WindowData) };
-
-        (window_data.width, window_data.height)
-    }
 }
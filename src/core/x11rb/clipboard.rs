@@ -0,0 +1,680 @@
+use std::{
+    collections::HashMap,
+    os::fd::AsRawFd,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use x11rb::{
+    connection::{Connection, RequestConnection as _},
+    protocol::{
+        xproto::{
+            AtomEnum, ChangeWindowAttributesAux, ConnectionExt as _, CreateWindowAux, EventMask,
+            PropMode, Property, PropertyNotifyEvent, Screen, SelectionClearEvent,
+            SelectionNotifyEvent, SelectionRequestEvent, WindowClass, SELECTION_NOTIFY_EVENT,
+        },
+        Event,
+    },
+    wrapper::ConnectionExt as _,
+    COPY_DEPTH_FROM_PARENT, CURRENT_TIME, NONE,
+};
+
+use super::{Atoms, RbError, X11RbState, XConnection};
+use crate::clipboard::{Selection, SelectionContents, TEXT_MIME_TYPE};
+
+/// How long another client gets to answer each step of a selection transfer
+const SELECTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A target that an owned selection can be converted to
+struct Target {
+    atom: u32,
+    /// The type of the property that the data is stored in
+    type_: u32,
+    data: Rc<[u8]>,
+}
+
+struct OwnedSelection {
+    /// When the selection was taken. Requests from before then are refused.
+    time: u32,
+    contents: SelectionContents,
+    targets: Vec<Target>,
+}
+
+/// A selection that is being sent in chunks, because it doesn't fit in one request. This is the
+/// INCR protocol from the ICCCM.
+struct Transfer {
+    requestor: u32,
+    property: u32,
+    type_: u32,
+    data: Rc<[u8]>,
+    /// How much of the data has been sent
+    offset: usize,
+}
+
+/// Owns selections for the program and answers other clients that want them
+pub struct Clipboard {
+    /// An unmapped window that owns the selections and receives the selections of other clients
+    window: u32,
+    /// The selections that are owned, by selection atom
+    owned: HashMap<u32, OwnedSelection>,
+    transfers: Vec<Transfer>,
+    /// Data bigger than this is sent in chunks of this size
+    chunk_size: usize,
+}
+
+/// Converts UTF-8 text to ISO Latin-1, which is what the `STRING` type is
+fn to_latin1(text: &[u8]) -> Vec<u8> {
+    String::from_utf8_lossy(text)
+        .chars()
+        .map(|char| u8::try_from(char).unwrap_or(b'?'))
+        .collect()
+}
+
+fn from_latin1(text: &[u8]) -> Vec<u8> {
+    text.iter()
+        .map(|&byte| byte as char)
+        .collect::<String>()
+        .into_bytes()
+}
+
+impl Clipboard {
+    pub fn new(connection: &XConnection, screen: &Screen) -> Result<Self, RbError> {
+        let window = connection.generate_id()?;
+
+        connection.create_window(
+            COPY_DEPTH_FROM_PARENT,
+            window,
+            screen.root,
+            0,
+            0,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_ONLY,
+            0,
+            // Needed to know when the chunks of an INCR transfer arrive
+            &CreateWindowAux::new().event_mask(EventMask::PROPERTY_CHANGE),
+        )?;
+
+        // Well below the limit, which also has to fit the rest of the ChangeProperty request
+        let chunk_size = (connection.maximum_request_bytes() / 4).max(4096);
+
+        Ok(Self {
+            window,
+            owned: HashMap::new(),
+            transfers: Vec::new(),
+            chunk_size,
+        })
+    }
+
+    /// Takes ownership of a selection, or gives it up if `contents` is empty
+    fn set(
+        &mut self,
+        connection: &XConnection,
+        atoms: &Atoms,
+        selection: u32,
+        contents: SelectionContents,
+        time: u32,
+    ) -> Result<(), RbError> {
+        if contents.is_empty() {
+            if self.owned.remove(&selection).is_some() {
+                connection.set_selection_owner(NONE, selection, time)?;
+            }
+
+            return Ok(());
+        }
+
+        let cookies = contents
+            .iter()
+            .map(|(mime_type, _)| connection.intern_atom(false, mime_type.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut targets = Vec::new();
+
+        for ((mime_type, data), cookie) in contents.iter().zip(cookies) {
+            let atom = cookie.reply()?.atom;
+            let data: Rc<[u8]> = data.into();
+
+            targets.push(Target {
+                atom,
+                type_: atom,
+                data: data.clone(),
+            });
+
+            // Most programs ask for text with the older names
+            if mime_type == TEXT_MIME_TYPE {
+                for atom in [atoms.UTF8_STRING, atoms.TEXT, atoms.TEXT_PLAIN] {
+                    targets.push(Target {
+                        atom,
+                        type_: if atom == atoms.TEXT_PLAIN {
+                            atom
+                        } else {
+                            atoms.UTF8_STRING
+                        },
+                        data: data.clone(),
+                    });
+                }
+
+                targets.push(Target {
+                    atom: AtomEnum::STRING.into(),
+                    type_: AtomEnum::STRING.into(),
+                    data: to_latin1(&data).into(),
+                });
+            }
+        }
+
+        connection.set_selection_owner(self.window, selection, time)?;
+
+        // Another client may have taken the selection with a later timestamp
+        let owner = connection.get_selection_owner(selection)?.reply()?.owner;
+        if owner != self.window {
+            self.owned.remove(&selection);
+            return Ok(());
+        }
+
+        self.owned.insert(
+            selection,
+            OwnedSelection {
+                time,
+                contents,
+                targets,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Answers a client that wants one of the owned selections
+    fn handle_request(
+        &mut self,
+        connection: &XConnection,
+        atoms: &Atoms,
+        event: &SelectionRequestEvent,
+    ) -> Result<(), RbError> {
+        // Obsolete clients leave the property out and expect the target to be used instead
+        let property = if event.property == NONE {
+            event.target
+        } else {
+            event.property
+        };
+
+        let converted = self.convert(connection, atoms, event, property)?;
+
+        let notify = SelectionNotifyEvent {
+            response_type: SELECTION_NOTIFY_EVENT,
+            sequence: 0,
+            time: event.time,
+            requestor: event.requestor,
+            selection: event.selection,
+            target: event.target,
+            property: if converted { property } else { NONE },
+        };
+
+        connection.send_event(false, event.requestor, EventMask::NO_EVENT, notify)?;
+
+        Ok(())
+    }
+
+    /// Stores an owned selection in the requestor's property. Returns whether the selection
+    /// could be converted to the target.
+    fn convert(
+        &mut self,
+        connection: &XConnection,
+        atoms: &Atoms,
+        event: &SelectionRequestEvent,
+        property: u32,
+    ) -> Result<bool, RbError> {
+        let Some(owned) = self.owned.get(&event.selection) else {
+            return Ok(false);
+        };
+
+        if event.time != CURRENT_TIME && event.time < owned.time {
+            return Ok(false);
+        }
+
+        if event.target == atoms.TARGETS {
+            let targets: Vec<u32> = [atoms.TARGETS, atoms.TIMESTAMP]
+                .into_iter()
+                .chain(owned.targets.iter().map(|target| target.atom))
+                .collect();
+
+            connection.change_property32(
+                PropMode::REPLACE,
+                event.requestor,
+                property,
+                AtomEnum::ATOM,
+                &targets,
+            )?;
+
+            return Ok(true);
+        }
+
+        if event.target == atoms.TIMESTAMP {
+            connection.change_property32(
+                PropMode::REPLACE,
+                event.requestor,
+                property,
+                AtomEnum::INTEGER,
+                &[owned.time],
+            )?;
+
+            return Ok(true);
+        }
+
+        let Some(target) = owned
+            .targets
+            .iter()
+            .find(|target| target.atom == event.target)
+        else {
+            return Ok(false);
+        };
+
+        if target.data.len() <= self.chunk_size {
+            connection.change_property8(
+                PropMode::REPLACE,
+                event.requestor,
+                property,
+                target.type_,
+                &target.data,
+            )?;
+
+            return Ok(true);
+        }
+
+        // The requestor deletes the property to ask for each chunk
+        connection.change_window_attributes(
+            event.requestor,
+            &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE),
+        )?;
+        connection.change_property32(
+            PropMode::REPLACE,
+            event.requestor,
+            property,
+            atoms.INCR,
+            &[target.data.len().try_into().unwrap_or(u32::MAX)],
+        )?;
+
+        let transfer = Transfer {
+            requestor: event.requestor,
+            property,
+            type_: target.type_,
+            data: target.data.clone(),
+            offset: 0,
+        };
+
+        // A requestor that gave up on an earlier transfer may reuse the property
+        self.transfers
+            .retain(|old| (old.requestor, old.property) != (transfer.requestor, transfer.property));
+        self.transfers.push(transfer);
+
+        Ok(true)
+    }
+
+    /// Sends the next chunk of an INCR transfer once the requestor has read the last one.
+    /// Returns whether the event belonged to a transfer.
+    fn handle_property_notify(
+        &mut self,
+        connection: &XConnection,
+        event: &PropertyNotifyEvent,
+    ) -> Result<bool, RbError> {
+        let Some(index) = self.transfers.iter().position(|transfer| {
+            transfer.requestor == event.window && transfer.property == event.atom
+        }) else {
+            return Ok(false);
+        };
+
+        // The chunks that were just written are reported too
+        if event.state != Property::DELETE {
+            return Ok(true);
+        }
+
+        let transfer = &mut self.transfers[index];
+        let end = (transfer.offset + self.chunk_size).min(transfer.data.len());
+
+        connection.change_property8(
+            PropMode::REPLACE,
+            transfer.requestor,
+            transfer.property,
+            transfer.type_,
+            &transfer.data[transfer.offset..end],
+        )?;
+
+        // The transfer ends with an empty chunk
+        if transfer.offset == end {
+            let requestor = transfer.requestor;
+            self.transfers.remove(index);
+
+            if !self
+                .transfers
+                .iter()
+                .any(|transfer| transfer.requestor == requestor)
+            {
+                connection.change_window_attributes(
+                    requestor,
+                    &ChangeWindowAttributesAux::new().event_mask(EventMask::NO_EVENT),
+                )?;
+            }
+        } else {
+            transfer.offset = end;
+        }
+
+        Ok(true)
+    }
+
+    fn handle_clear(&mut self, event: &SelectionClearEvent) {
+        if event.owner == self.window {
+            self.owned.remove(&event.selection);
+        }
+    }
+}
+
+impl X11RbState {
+    fn get_selection_atom(&self, selection: Selection) -> u32 {
+        match selection {
+            Selection::Clipboard => self.atoms.CLIPBOARD,
+            Selection::Primary => AtomEnum::PRIMARY.into(),
+        }
+    }
+
+    /// Handles the events that are part of the selection protocols. Returns whether the event
+    /// was one of them.
+    pub(super) fn handle_clipboard_event(&mut self, event: &Event) -> bool {
+        let result = match event {
+            Event::SelectionRequest(event) => self
+                .clipboard
+                .handle_request(&self.connection, &self.atoms, event)
+                .map(|()| true),
+            Event::SelectionClear(event) => {
+                self.clipboard.handle_clear(event);
+                Ok(true)
+            }
+            // A reply that came in after we stopped waiting for it
+            Event::SelectionNotify(_) => Ok(true),
+            Event::PropertyNotify(event) if event.window == self.clipboard.window => Ok(true),
+            Event::PropertyNotify(event) => self
+                .clipboard
+                .handle_property_notify(&self.connection, event),
+            _ => Ok(false),
+        };
+
+        // The requestor may have been destroyed in the meantime. Connection errors are reported
+        // by the next wait_for_events call.
+        result.unwrap_or(true)
+    }
+
+    /// Waits for an event that `predicate` accepts, or returns `None` if it doesn't come before
+    /// [SELECTION_TIMEOUT]. Other clients are answered in the meantime, and the other events are
+    /// kept for [wait_for_events](super::CoreStateImplementation::wait_for_events).
+    fn wait_for_clipboard_event(
+        &mut self,
+        predicate: impl Fn(&Event) -> bool,
+    ) -> Result<Option<Event>, RbError> {
+        let deadline = Instant::now() + SELECTION_TIMEOUT;
+
+        loop {
+            let Some(event) = self.connection.poll_for_event()? else {
+                self.connection.flush()?;
+
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                if timeout.is_zero() || !self.wait_for_connection(timeout) {
+                    return Ok(None);
+                }
+
+                continue;
+            };
+
+            if predicate(&event) {
+                return Ok(Some(event));
+            }
+
+            if !self.handle_clipboard_event(&event) {
+                self.pending_events.push_back(event);
+            }
+        }
+    }
+
+    /// Blocks until the X server sends something or `timeout` runs out. Unlike
+    /// [wait_for_input](Self::wait_for_input), this doesn't consume wake-ups.
+    fn wait_for_connection(&self, timeout: Duration) -> bool {
+        let mut poll_fd = libc::pollfd {
+            #[cfg(not(raw_window_handle))]
+            fd: self.connection.stream().as_raw_fd(),
+            #[cfg(raw_window_handle)]
+            fd: self.connection.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        let timeout = timeout
+            .as_nanos()
+            .div_ceil(1_000_000)
+            .try_into()
+            .unwrap_or(i32::MAX);
+
+        unsafe { libc::poll(&mut poll_fd, 1, timeout) > 0 }
+    }
+
+    /// Asks the owner of a selection to convert it to `target`. Returns the type and data of
+    /// the result, or `None` if the owner refused or didn't answer.
+    fn convert_selection(
+        &mut self,
+        selection: u32,
+        target: u32,
+        time: u32,
+    ) -> Result<Option<(u32, Vec<u8>)>, RbError> {
+        let window = self.clipboard.window;
+        let property = self.atoms._WWIND_SELECTION;
+
+        self.connection
+            .convert_selection(window, selection, target, property, time)?;
+
+        let notify = self.wait_for_clipboard_event(|event| {
+            matches!(event, Event::SelectionNotify(event)
+                if event.requestor == window && event.selection == selection)
+        })?;
+
+        let Some(Event::SelectionNotify(notify)) = notify else {
+            return Ok(None);
+        };
+
+        if notify.property == NONE {
+            return Ok(None);
+        }
+
+        let reply = self
+            .connection
+            .get_property(true, window, property, AtomEnum::ANY, 0, u32::MAX)?
+            .reply()?;
+
+        if reply.type_ != self.atoms.INCR {
+            return Ok(Some((reply.type_, reply.value)));
+        }
+
+        // Deleting the INCR property asked for the first chunk. Each chunk is deleted after it
+        // is read to ask for the next one, until an empty chunk ends the transfer.
+        let mut data = Vec::new();
+
+        loop {
+            let new_value = self.wait_for_clipboard_event(|event| {
+                matches!(event, Event::PropertyNotify(event)
+                    if event.window == window
+                        && event.atom == property
+                        && event.state == Property::NEW_VALUE)
+            })?;
+
+            if new_value.is_none() {
+                return Ok(None);
+            }
+
+            let reply = self
+                .connection
+                .get_property(true, window, property, AtomEnum::ANY, 0, u32::MAX)?
+                .reply()?;
+
+            if reply.value.is_empty() {
+                return Ok(Some((reply.type_, data)));
+            }
+
+            data.extend(reply.value);
+        }
+    }
+
+    /// Whether a selection has an owner that can be asked for it
+    fn has_selection_owner(&self, selection: u32) -> Result<bool, RbError> {
+        let owner = self
+            .connection
+            .get_selection_owner(selection)?
+            .reply()?
+            .owner;
+
+        Ok(owner != NONE)
+    }
+
+    pub(super) fn set_selection_contents(
+        &mut self,
+        selection: Selection,
+        contents: SelectionContents,
+    ) -> Result<(), RbError> {
+        let selection = self.get_selection_atom(selection);
+
+        self.clipboard.set(
+            &self.connection,
+            &self.atoms,
+            selection,
+            contents,
+            self.last_event_time,
+        )
+    }
+
+    /// Takes ownership of a selection that isn't one of the [Selection]s, like `XdndSelection`.
+    /// Returns the targets that it is offered as, which is empty if another client took it with
+    /// a later time.
+    pub(super) fn own_selection(
+        &mut self,
+        selection: u32,
+        contents: SelectionContents,
+        time: u32,
+    ) -> Result<Vec<u32>, RbError> {
+        self.clipboard
+            .set(&self.connection, &self.atoms, selection, contents, time)?;
+
+        let targets = self
+            .clipboard
+            .owned
+            .get(&selection)
+            .map(|owned| owned.targets.iter().map(|target| target.atom).collect());
+
+        Ok(targets.unwrap_or_default())
+    }
+
+    pub(super) fn get_selection_data(
+        &mut self,
+        selection: Selection,
+        mime_type: &str,
+    ) -> Result<Option<Vec<u8>>, RbError> {
+        let selection = self.get_selection_atom(selection);
+
+        self.read_selection(selection, mime_type, self.last_event_time)
+    }
+
+    /// Gets the data in a selection as `mime_type`. Text is asked for with the older names too.
+    pub(super) fn read_selection(
+        &mut self,
+        selection: u32,
+        mime_type: &str,
+        time: u32,
+    ) -> Result<Option<Vec<u8>>, RbError> {
+        // Asking ourselves would wait until the timeout, since the request is only answered
+        // by the event loop
+        if let Some(owned) = self.clipboard.owned.get(&selection) {
+            return Ok(owned.contents.get(mime_type).map(<[u8]>::to_vec));
+        }
+
+        if !self.has_selection_owner(selection)? {
+            return Ok(None);
+        }
+
+        let mime_atom = self
+            .connection
+            .intern_atom(false, mime_type.as_bytes())?
+            .reply()?
+            .atom;
+
+        let targets: &[u32] = if mime_type == TEXT_MIME_TYPE {
+            &[self.atoms.UTF8_STRING, mime_atom, AtomEnum::STRING.into()]
+        } else {
+            &[mime_atom]
+        };
+
+        for &target in targets {
+            if let Some((type_, data)) = self.convert_selection(selection, target, time)? {
+                if type_ == u32::from(AtomEnum::STRING) && mime_type == TEXT_MIME_TYPE {
+                    return Ok(Some(from_latin1(&data)));
+                }
+
+                return Ok(Some(data));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub(super) fn get_selection_mime_types(
+        &mut self,
+        selection: Selection,
+    ) -> Result<Vec<String>, RbError> {
+        let selection = self.get_selection_atom(selection);
+
+        if let Some(owned) = self.clipboard.owned.get(&selection) {
+            return Ok(owned.contents.types());
+        }
+
+        if !self.has_selection_owner(selection)? {
+            return Ok(Vec::new());
+        }
+
+        let time = self.last_event_time;
+        let Some((_, data)) = self.convert_selection(selection, self.atoms.TARGETS, time)? else {
+            return Ok(Vec::new());
+        };
+
+        let targets: Vec<u32> = data
+            .chunks_exact(4)
+            .map(|atom| u32::from_ne_bytes(atom.try_into().unwrap()))
+            .collect();
+
+        let cookies = targets
+            .iter()
+            .map(|&atom| self.connection.get_atom_name(atom))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut types: Vec<String> = Vec::new();
+
+        for (&atom, cookie) in targets.iter().zip(cookies) {
+            let text_atoms = [
+                self.atoms.UTF8_STRING,
+                self.atoms.TEXT,
+                AtomEnum::STRING.into(),
+            ];
+
+            let mime_type = if text_atoms.contains(&atom) {
+                TEXT_MIME_TYPE.to_owned()
+            } else {
+                let name = String::from_utf8_lossy(&cookie.reply()?.name).into_owned();
+
+                // Leaves out TARGETS, TIMESTAMP and the other targets that aren't data
+                if !name.contains('/') {
+                    continue;
+                }
+
+                name
+            };
+
+            if !types.contains(&mime_type) {
+                types.push(mime_type);
+            }
+        }
+
+        Ok(types)
+    }
+}
@@ -0,0 +1,1041 @@
+//! Drag and drop through the XDND protocol. The dragged data is sent as the `XdndSelection`
+//! selection, which the clipboard code takes care of in both directions.
+
+use std::{
+    ffi::OsString,
+    os::unix::ffi::{OsStrExt, OsStringExt},
+    path::{self, Path, PathBuf},
+};
+
+use x11rb::{
+    errors::ReplyError,
+    protocol::{
+        xproto::{
+            AtomEnum, ClientMessageEvent, ConnectionExt as _, EventMask, GrabMode, GrabStatus,
+            KeyButMask, PropMode,
+        },
+        Event,
+    },
+    wrapper::ConnectionExt as _,
+    CURRENT_TIME, NONE,
+};
+
+use super::{get_wheel_delta, Atoms, RbError, X11RbState, XConnection};
+use crate::{
+    clipboard::{SelectionContents, TEXT_MIME_TYPE},
+    core::{core_state_implementation::WWindCoreEvent, keysym},
+    input::Key,
+    CursorIcon, DragAction, DropEvent, DropPayload, RgbaImage,
+};
+
+/// The newest version of XDND that is supported
+const XDND_VERSION: u32 = 5;
+
+const URI_LIST_MIME_TYPE: &str = "text/uri-list";
+
+/// Something that another client is dragging over one of our windows
+pub struct IncomingDrag {
+    source: u32,
+    window: u32,
+    version: u32,
+    /// The targets that the source offers
+    types: Vec<u32>,
+    /// Fetched when the drag first moves over the window. Holds `None` if it can't be used.
+    payload: Option<Option<DropPayload>>,
+    /// The action that the source asked for, like `XdndActionCopy`
+    action: u32,
+    position: (i16, i16),
+}
+
+/// A drag that one of the windows started
+pub struct OutgoingDrag {
+    /// The window that started the drag. Targets send their replies to it.
+    window: u32,
+    /// The targets that `XdndSelection` is offered as
+    types: Vec<u32>,
+    /// The cursor that shows the drag image. It is freed when the pointer is ungrabbed.
+    image_cursor: Option<u32>,
+    /// The window under the pointer, if it accepts drops
+    target: Option<DragTarget>,
+    /// When the button was released. The drag then waits for the target to answer the last
+    /// position if it hasn't yet, and then for `XdndFinished`.
+    released: Option<u32>,
+}
+
+struct DragTarget {
+    /// The window with `XdndAware` that the messages are about
+    window: u32,
+    /// The window that the messages are sent to, which is only different for proxies
+    proxy: u32,
+    version: u32,
+    /// Whether a position was sent that hasn't been answered with `XdndStatus` yet
+    waiting_for_status: bool,
+    /// The newest position that came in while waiting. It is sent once the target answers,
+    /// as root coordinates, action and time.
+    pending_position: Option<(i16, i16, u32, u32)>,
+    /// The action from the last `XdndStatus`, or `None` if the target refused the drop
+    action: Option<u32>,
+}
+
+/// Sends an XDND message about `window` to `destination`, which is only different for proxies
+fn send_xdnd_message(
+    connection: &XConnection,
+    destination: u32,
+    window: u32,
+    message_type: u32,
+    data: [u32; 5],
+) -> Result<(), RbError> {
+    let event = ClientMessageEvent::new(32, window, message_type, data);
+
+    connection.send_event(false, destination, EventMask::NO_EVENT, event)?;
+
+    Ok(())
+}
+
+fn send_xdnd_position(
+    connection: &XConnection,
+    atoms: &Atoms,
+    source: u32,
+    target: &DragTarget,
+    (root_x, root_y, action, time): (i16, i16, u32, u32),
+) -> Result<(), RbError> {
+    send_xdnd_message(
+        connection,
+        target.proxy,
+        target.window,
+        atoms.XdndPosition,
+        [
+            source,
+            0,
+            (root_x as u16 as u32) << 16 | root_y as u16 as u32,
+            time,
+            action,
+        ],
+    )
+}
+
+/// Makes a `file://` URI out of a path. Everything except unreserved characters and slashes is
+/// escaped.
+fn get_file_uri(path: &Path) -> Option<String> {
+    let path = path::absolute(path).ok()?;
+    let mut uri = String::from("file://");
+
+    for &byte in path.as_os_str().as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    Some(uri)
+}
+
+/// Gets the formats that the payloads of a drag are offered in. Only the first payload of each
+/// kind is used.
+fn get_drag_contents(payloads: &[DropPayload]) -> SelectionContents {
+    let mut formats: Vec<(&str, Vec<u8>)> = Vec::new();
+
+    for payload in payloads {
+        let (mime_type, data) = match payload {
+            DropPayload::Files(paths) => {
+                // The lines of a URI list end with CRLF
+                let list: String = paths
+                    .iter()
+                    .filter_map(|path| get_file_uri(path))
+                    .map(|uri| uri + "\r\n")
+                    .collect();
+
+                (URI_LIST_MIME_TYPE, list.into_bytes())
+            }
+            DropPayload::Text(text) => (TEXT_MIME_TYPE, text.as_bytes().to_vec()),
+        };
+
+        if formats.iter().all(|&(format, _)| format != mime_type) {
+            formats.push((mime_type, data));
+        }
+    }
+
+    let formats: Vec<(&str, &[u8])> = formats
+        .iter()
+        .map(|(mime_type, data)| (*mime_type, data.as_slice()))
+        .collect();
+
+    SelectionContents::new(&formats)
+}
+
+/// Decodes the `%XX` escapes of a URI
+fn percent_decode(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    decoded
+}
+
+/// Gets the URIs of a `text/uri-list`, which has one per line and comments that start with `#`
+fn get_uris(list: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(list)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Gets the paths of `file://` URIs, or `None` if there are other URIs
+fn get_file_paths(uris: &[String]) -> Option<Vec<PathBuf>> {
+    if uris.is_empty() {
+        return None;
+    }
+
+    uris.iter()
+        .map(|uri| {
+            // The host is usually left out, which leaves an empty one before the path
+            let path = uri.strip_prefix("file://")?;
+            let path = &path[path.find('/')?..];
+
+            Some(PathBuf::from(OsString::from_vec(percent_decode(path))))
+        })
+        .collect()
+}
+
+impl X11RbState {
+    /// Tells the clients that start drags that a window accepts drops
+    pub(super) fn set_xdnd_aware(&self, window: u32) -> Result<(), RbError> {
+        self.connection.change_property32(
+            PropMode::REPLACE,
+            window,
+            self.atoms.XdndAware,
+            AtomEnum::ATOM,
+            &[XDND_VERSION],
+        )?;
+
+        Ok(())
+    }
+
+    /// Handles the client messages that drag sources send to the window under the pointer, and
+    /// that drop targets send back to the windows that start drags. Other messages are ignored.
+    pub(super) unsafe fn handle_xdnd_message(
+        &mut self,
+        event: &ClientMessageEvent,
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) {
+        let data = event.data.as_data32();
+
+        let result = if event.type_ == self.atoms.XdndEnter {
+            self.handle_xdnd_enter(event.window, data)
+        } else if event.type_ == self.atoms.XdndPosition {
+            self.handle_xdnd_position(event.window, data, event_handler)
+        } else if event.type_ == self.atoms.XdndLeave {
+            self.handle_xdnd_leave(data, event_handler);
+            Ok(())
+        } else if event.type_ == self.atoms.XdndDrop {
+            self.handle_xdnd_drop(data, event_handler)
+        } else if event.type_ == self.atoms.XdndStatus {
+            self.handle_xdnd_status(data, event_handler)
+        } else if event.type_ == self.atoms.XdndFinished {
+            self.handle_xdnd_finished(data, event_handler);
+            Ok(())
+        } else {
+            return;
+        };
+
+        // The other client may have gone away in the meantime. Connection errors are reported
+        // by the next wait_for_events call.
+        let _ = result;
+    }
+
+    fn handle_xdnd_enter(&mut self, window: u32, data: [u32; 5]) -> Result<(), RbError> {
+        let source = data[0];
+
+        // Sources that offer more than three types list them in a property
+        let types = if data[1] & 1 != 0 {
+            self.get_atoms(source, self.atoms.XdndTypeList)?
+        } else {
+            data[2..]
+                .iter()
+                .copied()
+                .filter(|&atom| atom != NONE)
+                .collect()
+        };
+
+        self.incoming_drag = Some(IncomingDrag {
+            source,
+            window,
+            version: data[1] >> 24,
+            types,
+            payload: None,
+            action: self.atoms.XdndActionCopy,
+            position: (0, 0),
+        });
+
+        Ok(())
+    }
+
+    unsafe fn handle_xdnd_position(
+        &mut self,
+        window: u32,
+        data: [u32; 5],
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) -> Result<(), RbError> {
+        let Some(drag) = &self.incoming_drag else {
+            return Ok(());
+        };
+
+        if drag.source != data[0] || drag.window != window {
+            return Ok(());
+        }
+
+        let (root_x, root_y) = ((data[2] >> 16) as i16, data[2] as i16);
+        let time = if drag.version >= 1 {
+            data[3]
+        } else {
+            CURRENT_TIME
+        };
+        let action = if drag.version >= 2 && data[4] != NONE {
+            data[4]
+        } else {
+            self.atoms.XdndActionCopy
+        };
+
+        if drag.payload.is_none() {
+            let types = drag.types.clone();
+            let payload = self.get_drop_payload(&types, time)?;
+
+            let Some(drag) = &mut self.incoming_drag else {
+                return Ok(());
+            };
+            drag.payload = Some(payload);
+        }
+
+        let reply = self
+            .connection
+            .translate_coordinates(self.screen.root, window, root_x, root_y)?
+            .reply()?;
+
+        let Some(drag) = &mut self.incoming_drag else {
+            return Ok(());
+        };
+
+        drag.position = (reply.dst_x, reply.dst_y);
+        drag.action = action;
+
+        let payload = drag.payload.clone().flatten();
+        let source = drag.source;
+
+        // The empty rectangle asks for a position message whenever the pointer moves
+        let accepted = payload.is_some();
+        send_xdnd_message(
+            &self.connection,
+            source,
+            source,
+            self.atoms.XdndStatus,
+            [
+                window,
+                accepted as u32 | 1 << 1,
+                0,
+                0,
+                if accepted { action } else { NONE },
+            ],
+        )?;
+
+        if let Some(payload) = payload {
+            let event = DropEvent {
+                x: reply.dst_x,
+                y: reply.dst_y,
+                payload,
+            };
+
+            event_handler(WWindCoreEvent::DragOver(window.into(), event));
+        }
+
+        Ok(())
+    }
+
+    unsafe fn handle_xdnd_leave(
+        &mut self,
+        data: [u32; 5],
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) {
+        if self
+            .incoming_drag
+            .as_ref()
+            .is_none_or(|drag| drag.source != data[0])
+        {
+            return;
+        }
+
+        let Some(drag) = self.incoming_drag.take() else {
+            return;
+        };
+
+        // Only drags that were reported as being over the window are reported as leaving it
+        if let Some(Some(_)) = drag.payload {
+            event_handler(WWindCoreEvent::DragLeave(drag.window.into()));
+        }
+    }
+
+    unsafe fn handle_xdnd_drop(
+        &mut self,
+        data: [u32; 5],
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) -> Result<(), RbError> {
+        if self
+            .incoming_drag
+            .as_ref()
+            .is_none_or(|drag| drag.source != data[0])
+        {
+            return Ok(());
+        }
+
+        let Some(drag) = self.incoming_drag.take() else {
+            return Ok(());
+        };
+
+        let time = if drag.version >= 1 {
+            data[2]
+        } else {
+            CURRENT_TIME
+        };
+
+        let payload = match drag.payload {
+            Some(payload) => Ok(payload),
+            None => self.get_drop_payload(&drag.types, time),
+        };
+
+        // The source has to be told that the drag is over, even if the data couldn't be read
+        let accepted = matches!(payload, Ok(Some(_)));
+        send_xdnd_message(
+            &self.connection,
+            drag.source,
+            drag.source,
+            self.atoms.XdndFinished,
+            [
+                drag.window,
+                accepted as u32,
+                if accepted { drag.action } else { NONE },
+                0,
+                0,
+            ],
+        )?;
+
+        if let Some(payload) = payload? {
+            let event = DropEvent {
+                x: drag.position.0,
+                y: drag.position.1,
+                payload,
+            };
+
+            event_handler(WWindCoreEvent::DragDrop(drag.window.into(), event));
+        }
+
+        Ok(())
+    }
+
+    /// Reads what is being dragged from the source. Files are preferred over text. Returns
+    /// `None` if the source doesn't offer either of them.
+    fn get_drop_payload(
+        &mut self,
+        types: &[u32],
+        time: u32,
+    ) -> Result<Option<DropPayload>, RbError> {
+        let selection = self.atoms.XdndSelection;
+
+        let uris = if types.contains(&self.atoms.TEXT_URI_LIST) {
+            self.read_selection(selection, URI_LIST_MIME_TYPE, time)?
+                .map(|list| get_uris(&list))
+        } else {
+            None
+        };
+
+        if let Some(paths) = uris.as_deref().and_then(get_file_paths) {
+            return Ok(Some(DropPayload::Files(paths)));
+        }
+
+        let text_atoms = [
+            self.atoms.UTF8_STRING,
+            self.atoms.TEXT_PLAIN_UTF8,
+            self.atoms.TEXT_PLAIN,
+            self.atoms.TEXT,
+            AtomEnum::STRING.into(),
+        ];
+
+        if types.iter().any(|atom| text_atoms.contains(atom)) {
+            if let Some(text) = self.read_selection(selection, TEXT_MIME_TYPE, time)? {
+                return Ok(Some(DropPayload::Text(
+                    String::from_utf8_lossy(&text).into_owned(),
+                )));
+            }
+        }
+
+        // Links that aren't files, from sources that don't offer them as text too
+        Ok(uris
+            .filter(|uris| !uris.is_empty())
+            .map(|uris| DropPayload::Text(uris.join("\n"))))
+    }
+
+    /// Starts a drag from `window` if a mouse button is held. The drag lasts until the button
+    /// is released, with the pointer and keyboard grabbed.
+    pub(super) fn start_outgoing_drag(
+        &mut self,
+        window: u32,
+        payloads: &[DropPayload],
+        image: Option<(&RgbaImage, (u16, u16))>,
+    ) -> Result<(), RbError> {
+        let pointer = self.connection.query_pointer(self.screen.root)?.reply()?;
+
+        let buttons = KeyButMask::BUTTON1 | KeyButMask::BUTTON2 | KeyButMask::BUTTON3;
+        if u16::from(pointer.mask) & u16::from(buttons) == 0 {
+            return Ok(());
+        }
+
+        // A drag that is still waiting for its target to finish is given up on
+        if self.outgoing_drag.is_some() {
+            self.ungrab_outgoing_drag()?;
+            self.outgoing_drag = None;
+        }
+
+        let selection = self.atoms.XdndSelection;
+        let contents = get_drag_contents(payloads);
+        let types = self.own_selection(selection, contents, self.last_event_time)?;
+
+        // Nothing to drag, or another client took the selection with a later time
+        if types.is_empty() {
+            return Ok(());
+        }
+
+        // Only three types fit in XdndEnter
+        if types.len() > 3 {
+            self.connection.change_property32(
+                PropMode::REPLACE,
+                window,
+                self.atoms.XdndTypeList,
+                AtomEnum::ATOM,
+                &types,
+            )?;
+        }
+
+        let image_cursor = image
+            .map(|(image, hotspot)| self.create_argb_cursor(image, hotspot))
+            .transpose()?;
+        let cursor = match image_cursor {
+            Some(cursor) => cursor,
+            None => self.load_cursor(CursorIcon::Grabbing)?,
+        };
+
+        // Pressing the button grabbed the pointer already, but without the cursor
+        let grab = self
+            .connection
+            .grab_pointer(
+                false,
+                window,
+                EventMask::POINTER_MOTION | EventMask::BUTTON_RELEASE,
+                GrabMode::ASYNC,
+                GrabMode::ASYNC,
+                NONE,
+                cursor,
+                CURRENT_TIME,
+            )?
+            .reply()?;
+
+        if grab.status != GrabStatus::SUCCESS {
+            if let Some(cursor) = image_cursor {
+                self.connection.free_cursor(cursor)?;
+            }
+
+            return Ok(());
+        }
+
+        // Lets Escape cancel the drag even if the window doesn't have the keyboard focus. The
+        // drag still works if another client has the keyboard grabbed.
+        self.connection.grab_keyboard(
+            false,
+            window,
+            CURRENT_TIME,
+            GrabMode::ASYNC,
+            GrabMode::ASYNC,
+        )?;
+
+        self.outgoing_drag = Some(OutgoingDrag {
+            window,
+            types,
+            image_cursor,
+            target: None,
+            released: None,
+        });
+
+        // The pointer may already be over a target
+        self.move_outgoing_drag(
+            pointer.root_x,
+            pointer.root_y,
+            pointer.mask,
+            self.last_event_time,
+        )
+    }
+
+    /// Handles the pointer and keyboard events while a drag is in progress. Returns `true` if
+    /// the event was used up. The release of the button is still reported to the window, so
+    /// that it doesn't miss it.
+    pub(super) unsafe fn handle_drag_event(
+        &mut self,
+        event: &Event,
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) -> bool {
+        if self
+            .outgoing_drag
+            .as_ref()
+            .is_none_or(|drag| drag.released.is_some())
+        {
+            return false;
+        }
+
+        let result = match event {
+            Event::MotionNotify(event) => {
+                self.move_outgoing_drag(event.root_x, event.root_y, event.state, event.time)
+            }
+            Event::XinputMotion(event) => self.move_outgoing_drag(
+                (event.root_x >> 16) as i16,
+                (event.root_y >> 16) as i16,
+                KeyButMask::from(event.mods.effective as u16),
+                event.time,
+            ),
+            Event::ButtonRelease(event) if get_wheel_delta(event.detail).is_none() => {
+                self.drop_outgoing_drag(event.time, event_handler)
+            }
+            Event::KeyPress(event)
+                if self
+                    .keymap
+                    .get_base_keysym(event.detail)
+                    .map(keysym::keysym_to_key)
+                    == Some(Key::Escape) =>
+            {
+                self.cancel_outgoing_drag(event_handler)
+            }
+            _ => return false,
+        };
+
+        // The targets may have gone away in the meantime. Connection errors are reported by
+        // the next wait_for_events call.
+        let _ = result;
+
+        !matches!(event, Event::ButtonRelease(_))
+    }
+
+    /// Tells the targets that the pointer moved, and which one it is over now
+    fn move_outgoing_drag(
+        &mut self,
+        root_x: i16,
+        root_y: i16,
+        state: KeyButMask,
+        time: u32,
+    ) -> Result<(), RbError> {
+        let found = self.find_drag_target(root_x, root_y)?;
+        let action = if u16::from(state) & u16::from(KeyButMask::SHIFT) != 0 {
+            self.atoms.XdndActionMove
+        } else {
+            self.atoms.XdndActionCopy
+        };
+
+        let Some(drag) = &mut self.outgoing_drag else {
+            return Ok(());
+        };
+
+        if drag.target.as_ref().map(|target| target.window) != found.map(|(window, ..)| window) {
+            if let Some(target) = drag.target.take() {
+                send_xdnd_message(
+                    &self.connection,
+                    target.proxy,
+                    target.window,
+                    self.atoms.XdndLeave,
+                    [drag.window, 0, 0, 0, 0],
+                )?;
+            }
+
+            if let Some((window, proxy, version)) = found {
+                let mut data = [
+                    drag.window,
+                    version << 24 | (drag.types.len() > 3) as u32,
+                    NONE,
+                    NONE,
+                    NONE,
+                ];
+
+                for (slot, &atom) in data[2..].iter_mut().zip(&drag.types) {
+                    *slot = atom;
+                }
+
+                send_xdnd_message(&self.connection, proxy, window, self.atoms.XdndEnter, data)?;
+
+                drag.target = Some(DragTarget {
+                    window,
+                    proxy,
+                    version,
+                    waiting_for_status: false,
+                    pending_position: None,
+                    action: None,
+                });
+            }
+        }
+
+        let Some(target) = &mut drag.target else {
+            return Ok(());
+        };
+
+        let position = (root_x, root_y, action, time);
+
+        // Only one position is sent at a time, so that slow targets don't fall behind
+        if target.waiting_for_status {
+            target.pending_position = Some(position);
+            return Ok(());
+        }
+
+        target.waiting_for_status = true;
+        send_xdnd_position(&self.connection, &self.atoms, drag.window, target, position)
+    }
+
+    /// Finds the window under the pointer that accepts drops. Returns it with the window that
+    /// the messages are sent to and the version of XDND to use.
+    fn find_drag_target(
+        &self,
+        root_x: i16,
+        root_y: i16,
+    ) -> Result<Option<(u32, u32, u32)>, RbError> {
+        let mut window = self.screen.root;
+
+        loop {
+            let proxy = self.get_xdnd_proxy(window)?;
+
+            if let Some(version) =
+                self.get_first_value(proxy, self.atoms.XdndAware, AtomEnum::ATOM)?
+            {
+                return Ok(Some((window, proxy, version.min(XDND_VERSION))));
+            }
+
+            let reply = match self
+                .connection
+                .translate_coordinates(self.screen.root, window, root_x, root_y)?
+                .reply()
+            {
+                Ok(reply) => reply,
+                // The window was destroyed in the meantime
+                Err(ReplyError::X11Error(_)) => return Ok(None),
+                Err(err) => return Err(err.into()),
+            };
+
+            if reply.child == NONE {
+                return Ok(None);
+            }
+
+            window = reply.child;
+        }
+    }
+
+    /// Gets the window that XDND messages for `window` are sent to
+    fn get_xdnd_proxy(&self, window: u32) -> Result<u32, RbError> {
+        let property = self.atoms.XdndProxy;
+        let proxy = self.get_first_value(window, property, AtomEnum::WINDOW)?;
+
+        // The proxy only counts if it points to itself, so that stale ones are ignored
+        match proxy {
+            Some(proxy)
+                if self.get_first_value(proxy, property, AtomEnum::WINDOW)? == Some(proxy) =>
+            {
+                Ok(proxy)
+            }
+            _ => Ok(window),
+        }
+    }
+
+    /// Gets the first value of a 32-bit property, or `None` if the window doesn't have it or
+    /// was destroyed
+    fn get_first_value(
+        &self,
+        window: u32,
+        property: u32,
+        type_: AtomEnum,
+    ) -> Result<Option<u32>, RbError> {
+        let reply = match self
+            .connection
+            .get_property(false, window, property, type_, 0, 1)?
+            .reply()
+        {
+            Ok(reply) => reply,
+            Err(ReplyError::X11Error(_)) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(reply.value32().and_then(|mut values| values.next()))
+    }
+
+    unsafe fn drop_outgoing_drag(
+        &mut self,
+        time: u32,
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) -> Result<(), RbError> {
+        self.ungrab_outgoing_drag()?;
+
+        let Some(drag) = &mut self.outgoing_drag else {
+            return Ok(());
+        };
+
+        drag.released = Some(time);
+
+        // Otherwise the target is told once it answers the last position
+        match &drag.target {
+            Some(target) if target.waiting_for_status => Ok(()),
+            _ => self.send_outgoing_drop(event_handler),
+        }
+    }
+
+    /// Drops on the target if it accepted the last position, and ends the drag otherwise
+    unsafe fn send_outgoing_drop(
+        &mut self,
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) -> Result<(), RbError> {
+        let Some(drag) = &self.outgoing_drag else {
+            return Ok(());
+        };
+
+        if let Some(target) = &drag.target {
+            let message_type = if target.action.is_some() {
+                self.atoms.XdndDrop
+            } else {
+                self.atoms.XdndLeave
+            };
+
+            let time = drag.released.unwrap_or(CURRENT_TIME);
+
+            send_xdnd_message(
+                &self.connection,
+                target.proxy,
+                target.window,
+                message_type,
+                [drag.window, 0, time, 0, 0],
+            )?;
+
+            // The drag ends with XdndFinished
+            if target.action.is_some() {
+                return Ok(());
+            }
+        }
+
+        self.end_outgoing_drag(None, event_handler);
+
+        Ok(())
+    }
+
+    unsafe fn cancel_outgoing_drag(
+        &mut self,
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) -> Result<(), RbError> {
+        self.ungrab_outgoing_drag()?;
+
+        if let Some(drag) = &self.outgoing_drag {
+            if let Some(target) = &drag.target {
+                send_xdnd_message(
+                    &self.connection,
+                    target.proxy,
+                    target.window,
+                    self.atoms.XdndLeave,
+                    [drag.window, 0, 0, 0, 0],
+                )?;
+            }
+        }
+
+        self.end_outgoing_drag(None, event_handler);
+
+        Ok(())
+    }
+
+    fn ungrab_outgoing_drag(&mut self) -> Result<(), RbError> {
+        self.connection.ungrab_pointer(CURRENT_TIME)?;
+        self.connection.ungrab_keyboard(CURRENT_TIME)?;
+
+        if let Some(cursor) = self
+            .outgoing_drag
+            .as_mut()
+            .and_then(|drag| drag.image_cursor.take())
+        {
+            self.connection.free_cursor(cursor)?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn end_outgoing_drag(
+        &mut self,
+        action: Option<DragAction>,
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) {
+        if let Some(drag) = self.outgoing_drag.take() {
+            event_handler(WWindCoreEvent::DragEnd(drag.window.into(), action));
+        }
+    }
+
+    unsafe fn handle_xdnd_status(
+        &mut self,
+        data: [u32; 5],
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) -> Result<(), RbError> {
+        let Some(drag) = &mut self.outgoing_drag else {
+            return Ok(());
+        };
+
+        let Some(target) = drag
+            .target
+            .as_mut()
+            .filter(|target| target.window == data[0])
+        else {
+            return Ok(());
+        };
+
+        target.waiting_for_status = false;
+        // Targets before version 2 can only copy
+        target.action = (data[1] & 1 != 0).then_some(if target.version >= 2 && data[4] != NONE {
+            data[4]
+        } else {
+            self.atoms.XdndActionCopy
+        });
+
+        if drag.released.is_some() {
+            return self.send_outgoing_drop(event_handler);
+        }
+
+        if let Some(position) = target.pending_position.take() {
+            target.waiting_for_status = true;
+            send_xdnd_position(&self.connection, &self.atoms, drag.window, target, position)?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn handle_xdnd_finished(
+        &mut self,
+        data: [u32; 5],
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) {
+        let Some(drag) = &self.outgoing_drag else {
+            return;
+        };
+
+        let Some(target) = drag
+            .target
+            .as_ref()
+            .filter(|target| target.window == data[0] && !target.waiting_for_status)
+        else {
+            return;
+        };
+
+        if drag.released.is_none() {
+            return;
+        }
+
+        // Before version 5, the target doesn't say what it did, so the last status is used
+        let action = if target.version >= 5 {
+            (data[1] & 1 != 0).then_some(data[2])
+        } else {
+            target.action
+        };
+
+        let action = action.filter(|&action| action != NONE).map(|action| {
+            if action == self.atoms.XdndActionMove {
+                DragAction::Move
+            } else if action == self.atoms.XdndActionLink {
+                DragAction::Link
+            } else {
+                DragAction::Copy
+            }
+        });
+
+        self.end_outgoing_drag(action, event_handler);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{get_file_paths, get_file_uri, get_uris, percent_decode};
+
+    fn uris(list: &[&str]) -> Vec<String> {
+        list.iter().map(|&uri| uri.to_owned()).collect()
+    }
+
+    #[test]
+    fn percent_escapes() {
+        assert_eq!(percent_decode("/a%20b"), b"/a b");
+        assert_eq!(percent_decode("%2f%2F"), b"//");
+        assert_eq!(percent_decode("%C3%A9"), "é".as_bytes());
+        assert_eq!(percent_decode("%FF"), [0xFF]);
+    }
+
+    #[test]
+    fn invalid_percent_escapes_are_kept() {
+        assert_eq!(percent_decode("a%4"), b"a%4");
+        assert_eq!(percent_decode("%"), b"%");
+        assert_eq!(percent_decode("%zz%41"), b"%zzA");
+        assert_eq!(percent_decode("100%"), b"100%");
+    }
+
+    #[test]
+    fn uri_lists() {
+        let list = b"# a comment\r\nfile:///a\r\n\r\n  file:///b  \r\nhttps://example.com\n";
+
+        assert_eq!(
+            get_uris(list),
+            ["file:///a", "file:///b", "https://example.com"]
+        );
+        assert!(get_uris(b"# only a comment\r\n").is_empty());
+    }
+
+    #[test]
+    fn file_paths() {
+        let paths = get_file_paths(&uris(&["file:///a%20b", "file://localhost/x"]));
+
+        assert_eq!(
+            paths,
+            Some(vec![PathBuf::from("/a b"), PathBuf::from("/x")])
+        );
+    }
+
+    #[test]
+    fn other_uris_arent_file_paths() {
+        // These are dropped as text instead
+        assert_eq!(get_file_paths(&uris(&["https://example.com/a"])), None);
+        assert_eq!(
+            get_file_paths(&uris(&["file:///a", "https://example.com/a"])),
+            None
+        );
+        assert_eq!(get_file_paths(&uris(&["file:relative"])), None);
+        assert_eq!(get_file_paths(&[]), None);
+    }
+
+    #[test]
+    fn file_uris_round_trip() {
+        let path = Path::new("/tmp/a b/ü%.txt");
+        let uri = get_file_uri(path).unwrap();
+
+        assert_eq!(uri, "file:///tmp/a%20b/%C3%BC%25.txt");
+        assert_eq!(get_file_paths(&[uri]), Some(vec![path.to_owned()]));
+    }
+}
@@ -1,3 +1,5 @@
+use std::{fmt, io};
+
 use x11rb::{
     rust_connection::{ConnectError, ConnectionError, ParseError, ReplyError, ReplyOrIdError},
     x11_utils::X11Error,
@@ -10,7 +12,35 @@ pub enum RbError {
     ParseError(ParseError),
     ReplyError(ReplyError),
     IdsExausted(),
+    /// An X extension that wwind depends on is not supported by the server
+    MissingExtension(&'static str),
     X11Error(X11Error),
+    IoError(io::Error),
+    /// The root window uses a visual that wwind can't draw with
+    UnsupportedVisual,
+}
+
+impl fmt::Display for RbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RbError::ConnectError(err) => write!(f, "failed to connect to the X server: {err}"),
+            RbError::ConnectionError(err) => write!(f, "X11 connection error: {err}"),
+            RbError::ParseError(err) => write!(f, "failed to parse an X11 message: {err}"),
+            RbError::ReplyError(err) => write!(f, "X11 request failed: {err}"),
+            RbError::IdsExausted() => f.write_str("ran out of X11 resource IDs"),
+            RbError::MissingExtension(extension) => {
+                write!(f, "the X server does not support the {extension} extension")
+            }
+            RbError::X11Error(err) => write!(
+                f,
+                "X11 error {:?} in request {}",
+                err.error_kind,
+                err.request_name.unwrap_or("<unknown>")
+            ),
+            RbError::IoError(err) => write!(f, "IO error: {err}"),
+            RbError::UnsupportedVisual => f.write_str("the root window's visual is not supported"),
+        }
+    }
 }
 
 impl From<ConnectError> for RbError {
@@ -43,6 +73,12 @@ impl From<X11Error> for RbError {
     }
 }
 
+impl From<io::Error> for RbError {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
 impl From<ReplyOrIdError> for RbError {
     fn from(value: ReplyOrIdError) -> Self {
         match value {
@@ -0,0 +1,246 @@
+use x11rb::protocol::xkb::{self, ConnectionExt as _, KeySymMap, KeyType, MapPart};
+
+use super::{RbError, XConnection};
+
+/// The keyboard mapping of the XKB core keyboard
+pub struct Keymap {
+    types: Vec<KeyType>,
+    keys: Vec<KeySymMap>,
+    min_keycode: usize,
+}
+
+impl Keymap {
+    /// Fetches the current keyboard mapping. The XKB extension must already be enabled.
+    pub fn new(connection: &XConnection) -> Result<Self, RbError> {
+        let map = connection
+            .xkb_get_map(
+                xkb::ID::USE_CORE_KBD.into(),
+                MapPart::KEY_TYPES | MapPart::KEY_SYMS,
+                MapPart::from(0u16),
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                xkb::VMod::from(0u16),
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+            )?
+            .reply()?;
+
+        Ok(Self {
+            types: map.map.types_rtrn.unwrap_or_default(),
+            keys: map.map.syms_rtrn.unwrap_or_default(),
+            min_keycode: map.first_key_sym as usize,
+        })
+    }
+
+    /// Gets the keysym that a key produces. `state` is the state field of a core key event,
+    /// which contains both the modifiers and the XKB group.
+    pub fn get_keysym(&self, keycode: u8, state: u16) -> Option<u32> {
+        let key = self
+            .keys
+            .get((keycode as usize).checked_sub(self.min_keycode)?)?;
+
+        let group = self.get_effective_group(key, ((state >> 13) & 0b11) as usize)?;
+        let key_type = self.types.get(key.kt_index[group] as usize)?;
+
+        let modifiers = (state & 0xff) & u16::from(key_type.mods_mask);
+        let level = key_type
+            .map
+            .iter()
+            .find(|entry| entry.active && u16::from(entry.mods_mask) == modifiers)
+            .map_or(0, |entry| entry.level);
+
+        key.syms
+            .get(group * key.width as usize + level as usize)
+            .copied()
+            .filter(|&keysym| keysym != 0)
+    }
+
+    /// Gets the keysym that a key produces in the first group without any modifiers held
+    pub fn get_base_keysym(&self, keycode: u8) -> Option<u32> {
+        self.get_keysym(keycode, 0)
+    }
+
+    /// Brings a group that the key does not have back into range, the same way the server does
+    fn get_effective_group(&self, key: &KeySymMap, group: usize) -> Option<usize> {
+        let num_groups = (key.group_info & 0x0f) as usize;
+
+        if num_groups == 0 {
+            return None;
+        }
+
+        if group < num_groups {
+            return Some(group);
+        }
+
+        Some(match key.group_info & 0xc0 {
+            // Clamp into range
+            0x40 => num_groups - 1,
+            // Redirect into the group in bits 4-5
+            0x80 => ((key.group_info >> 4) & 0b11) as usize % num_groups,
+            // Wrap into range
+            _ => group % num_groups,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use x11rb::protocol::xkb::{KTMapEntry, KeySymMap, KeyType};
+
+    use super::Keymap;
+
+    const SHIFT: u16 = 1;
+    const LOCK: u16 = 1 << 1;
+    const CONTROL: u16 = 1 << 2;
+
+    const ONE_LEVEL: u8 = 0;
+    const TWO_LEVEL: u8 = 1;
+    const ALPHABETIC: u8 = 2;
+
+    /// The keycodes of the keys in [us_ru]
+    const KEY_A: u8 = 8;
+    const KEY_1: u8 = 9;
+    const KEY_ESCAPE: u8 = 10;
+    const KEY_NO_GROUPS: u8 = 11;
+
+    fn key_type(mods_mask: u16, map: &[(u16, u8)]) -> KeyType {
+        KeyType {
+            mods_mask: mods_mask.into(),
+            mods_mods: mods_mask.into(),
+            mods_vmods: 0u16.into(),
+            num_levels: map.len() as u8 + 1,
+            has_preserve: false,
+            map: map
+                .iter()
+                .map(|&(mods_mask, level)| KTMapEntry {
+                    active: true,
+                    mods_mask: mods_mask.into(),
+                    level,
+                    mods_mods: mods_mask.into(),
+                    mods_vmods: 0u16.into(),
+                })
+                .collect(),
+            preserve: Vec::new(),
+        }
+    }
+
+    fn key(key_type: u8, group_info: u8, width: u8, syms: &[u32]) -> KeySymMap {
+        KeySymMap {
+            kt_index: [key_type; 4],
+            group_info,
+            width,
+            syms: syms.to_vec(),
+        }
+    }
+
+    /// A keymap with a Latin and a Cyrillic group, like `us,ru`
+    fn us_ru(group_info: u8) -> Keymap {
+        Keymap {
+            types: vec![
+                key_type(0, &[]),
+                key_type(SHIFT, &[(SHIFT, 1)]),
+                key_type(SHIFT | LOCK, &[(SHIFT, 1), (LOCK, 1)]),
+            ],
+            keys: vec![
+                // a A ф Ф
+                key(ALPHABETIC, group_info, 2, &[0x61, 0x41, 0x6c6, 0x6e6]),
+                // 1 ! 1 !
+                key(TWO_LEVEL, group_info, 2, &[0x31, 0x21, 0x31, 0x21]),
+                key(ONE_LEVEL, 1, 1, &[0xff1b]),
+                key(ONE_LEVEL, 0, 0, &[]),
+            ],
+            min_keycode: 8,
+        }
+    }
+
+    fn group(group: u16) -> u16 {
+        group << 13
+    }
+
+    #[test]
+    fn shift_selects_the_second_level() {
+        let keymap = us_ru(2);
+
+        assert_eq!(keymap.get_keysym(KEY_A, 0), Some(0x61));
+        assert_eq!(keymap.get_keysym(KEY_A, SHIFT), Some(0x41));
+        assert_eq!(keymap.get_keysym(KEY_1, SHIFT), Some(0x21));
+        assert_eq!(keymap.get_keysym(KEY_ESCAPE, SHIFT), Some(0xff1b));
+    }
+
+    #[test]
+    fn lock_only_affects_alphabetic_keys() {
+        let keymap = us_ru(2);
+
+        assert_eq!(keymap.get_keysym(KEY_A, LOCK), Some(0x41));
+        assert_eq!(keymap.get_keysym(KEY_1, LOCK), Some(0x31));
+        assert_eq!(keymap.get_keysym(KEY_1, SHIFT | LOCK), Some(0x21));
+
+        // Shift cancels Lock, since there is no entry for both
+        assert_eq!(keymap.get_keysym(KEY_A, SHIFT | LOCK), Some(0x61));
+    }
+
+    #[test]
+    fn modifiers_that_the_type_ignores_are_ignored() {
+        let keymap = us_ru(2);
+
+        assert_eq!(keymap.get_keysym(KEY_A, CONTROL), Some(0x61));
+        assert_eq!(keymap.get_keysym(KEY_A, CONTROL | SHIFT), Some(0x41));
+        assert_eq!(keymap.get_base_keysym(KEY_A), Some(0x61));
+    }
+
+    #[test]
+    fn groups() {
+        let keymap = us_ru(2);
+
+        assert_eq!(keymap.get_keysym(KEY_A, group(1)), Some(0x6c6));
+        assert_eq!(keymap.get_keysym(KEY_A, group(1) | SHIFT), Some(0x6e6));
+
+        // Keys with one group ignore the group
+        assert_eq!(keymap.get_keysym(KEY_ESCAPE, group(1)), Some(0xff1b));
+    }
+
+    #[test]
+    fn out_of_range_groups_wrap_by_default() {
+        let keymap = us_ru(2);
+
+        assert_eq!(keymap.get_keysym(KEY_A, group(2)), Some(0x61));
+        assert_eq!(keymap.get_keysym(KEY_A, group(3)), Some(0x6c6));
+    }
+
+    #[test]
+    fn out_of_range_groups_can_be_clamped() {
+        let keymap = us_ru(0x40 | 2);
+
+        assert_eq!(keymap.get_keysym(KEY_A, group(2)), Some(0x6c6));
+        assert_eq!(keymap.get_keysym(KEY_A, group(3)), Some(0x6c6));
+    }
+
+    #[test]
+    fn out_of_range_groups_can_be_redirected() {
+        let keymap = us_ru(0x80 | 2);
+        assert_eq!(keymap.get_keysym(KEY_A, group(3)), Some(0x61));
+
+        let keymap = us_ru(0x80 | (1 << 4) | 2);
+        assert_eq!(keymap.get_keysym(KEY_A, group(2)), Some(0x6c6));
+        assert_eq!(keymap.get_keysym(KEY_A, group(3)), Some(0x6c6));
+    }
+
+    #[test]
+    fn missing_keys() {
+        let keymap = us_ru(2);
+
+        assert_eq!(keymap.get_keysym(KEY_NO_GROUPS, 0), None);
+        assert_eq!(keymap.get_keysym(7, 0), None);
+        assert_eq!(keymap.get_keysym(200, 0), None);
+    }
+}
@@ -1,49 +1,576 @@
-use crate::{Color, RectRegion};
+use crate::{
+    clipboard::{Selection, SelectionContents},
+    input::{Key, KeyEvent, Modifiers, MouseButton, MouseButtonEvent, MouseMoveEvent, ScrollEvent},
+    Color, CursorIcon, DropPayload, Monitor, RectRegion, ResizeEdge, RgbaImage, WindowBuilder,
+    WindowState, WindowStateFlags,
+};
 
 use super::{core_state_implementation::WWindCoreEvent, CoreStateImplementation};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    os::fd::AsRawFd,
+    time::Duration,
+};
+#[cfg(raw_window_handle)]
+use std::{num::NonZeroU32, ptr::NonNull};
 use x11rb::{
     atom_manager,
-    connection::Connection,
+    connection::{Connection, RequestConnection as _},
+    cursor,
+    properties::{WmHints, WmHintsState, WmSizeHints, WmSizeHintsSpecification},
     protocol::{
-        xkb::ConnectionExt as _,
+        render::{self, PictType, Pictformat},
+        xkb::{self, ConnectionExt as _},
         xproto::{
             self, change_property, create_window, destroy_window, map_window, send_event,
-            BackingStore, ChangeGCAux, ConnectionExt, CreateGCAux, CreateWindowAux, EventMask,
-            KeyButMask, PropMode, Rectangle, Screen, Segment, Visualtype, WindowClass,
+            unmap_window, AtomEnum, BackingStore, ButtonPressEvent, ChangeGCAux,
+            ChangeWindowAttributesAux, ClientMessageEvent, ConfigureWindowAux, ConnectionExt,
+            CreateGCAux, CreateWindowAux, EventMask, ImageFormat, ImageOrder, InputFocus,
+            KeyButMask, KeyPressEvent, MapState, NotifyDetail, NotifyMode, PropMode, Rectangle,
+            Screen, Segment, StackMode, WindowClass,
         },
         Event,
     },
-    rust_connection::RustConnection,
+    wrapper::ConnectionExt as _,
 };
 
+mod clipboard;
+mod dnd;
 mod error;
+mod keymap;
+mod monitor;
+mod scroll;
 pub use error::RbError;
 
-pub struct Keymap {
-    keysyms: Vec<u32>,
-    min_keycode: usize,
-    keysyms_per_keycode: u8,
-}
+use super::{
+    keysym::{self, Composer},
+    pipe_waker::{self, PipeReceiver, PipeWaker},
+};
+use clipboard::Clipboard;
+use dnd::{IncomingDrag, OutgoingDrag};
+use keymap::Keymap;
+#[cfg(raw_window_handle)]
+use raw_window_handle::{
+    HandleError, RawDisplayHandle, RawWindowHandle, XcbDisplayHandle, XcbWindowHandle,
+};
+use scroll::SmoothScroll;
 
-impl Keymap {
-    fn get_keysym(&self, keycode: usize, mut group: usize) -> Option<u32> {
-        group = group % self.keysyms_per_keycode as usize;
-        self.keysyms
-            .get(keycode.checked_sub(self.min_keycode)? * self.keysyms_per_keycode as usize + group)
-            .copied()
-    }
-}
+/// The connection to the X server. With `raw-window-handle`, it goes through libxcb so that
+/// other libraries can use it too.
+#[cfg(not(raw_window_handle))]
+type XConnection = x11rb::rust_connection::RustConnection;
+#[cfg(raw_window_handle)]
+type XConnection = x11rb::xcb_ffi::XCBConnection;
 
 pub struct X11RbState {
-    connection: RustConnection,
+    connection: XConnection,
+    screen_number: usize,
     graphics_context: u32,
     atoms: Atoms,
     screen: Screen,
-    visual: Visualtype,
+    /// Needed by other libraries that draw into the windows
+    #[cfg(raw_window_handle)]
+    visual: xproto::Visualtype,
     red_shift: u8,
     green_shift: u8,
     blue_shift: u8,
     keymap: Keymap,
+    composer: Composer,
+    /// Which keycodes are currently held down. Used to detect auto-repeat.
+    held_keys: [bool; 256],
+    /// `None` if the server doesn't support XInput 2.1. Only wheel steps can be reported then.
+    smooth_scroll: Option<SmoothScroll>,
+    /// Loads cursors from the cursor theme. Created when the first cursor is set.
+    cursor_handle: Option<cursor::Handle>,
+    /// The cursors that have been loaded, which are kept until the connection is closed
+    cursors: HashMap<CursorIcon, u32>,
+    /// The RENDER format for ARGB cursors. Looked up when the first custom cursor is set.
+    argb_format: Option<Pictformat>,
+    clipboard: Clipboard,
+    /// The time of the last key or button press. ICCCM says that selections should be taken
+    /// with the time of the event that caused it.
+    last_event_time: u32,
+    /// Events that came in while waiting for another client to send a selection
+    pending_events: VecDeque<Event>,
+    /// What another client is dragging over one of the windows
+    incoming_drag: Option<IncomingDrag>,
+    /// A drag that one of the windows started
+    outgoing_drag: Option<OutgoingDrag>,
+    /// Whether the server supports RandR 1.3. The whole screen is one monitor otherwise.
+    has_randr: bool,
+    /// The monitors from the last time that they changed
+    monitors: Vec<Monitor>,
+    /// Windows that the window manager has put into a frame. The positions in their real
+    /// ConfigureNotify events are relative to the frame instead of the root.
+    reparented_windows: HashSet<u32>,
+    wake_receiver: PipeReceiver,
+    waker: PipeWaker,
+}
+
+fn get_modifiers(state: KeyButMask) -> Modifiers {
+    const MODIFIER_MASKS: [(KeyButMask, Modifiers); 6] = [
+        (KeyButMask::SHIFT, Modifiers::SHIFT),
+        (KeyButMask::CONTROL, Modifiers::CONTROL),
+        (KeyButMask::MOD1, Modifiers::ALT),
+        (KeyButMask::MOD4, Modifiers::SUPER),
+        (KeyButMask::LOCK, Modifiers::CAPS_LOCK),
+        (KeyButMask::MOD2, Modifiers::NUM_LOCK),
+    ];
+
+    let state = u16::from(state);
+    let mut modifiers = Modifiers::NONE;
+
+    for (mask, modifier) in MODIFIER_MASKS {
+        if state & u16::from(mask) != 0 {
+            modifiers |= modifier;
+        }
+    }
+
+    modifiers
+}
+
+fn get_mouse_button(button: u8) -> MouseButton {
+    match button {
+        1 => MouseButton::Left,
+        2 => MouseButton::Middle,
+        3 => MouseButton::Right,
+        8 => MouseButton::Back,
+        9 => MouseButton::Forward,
+        button => MouseButton::Other(button),
+    }
+}
+
+/// ButtonRelease events have the same layout as ButtonPress events
+fn get_mouse_button_event(event: &ButtonPressEvent) -> MouseButtonEvent {
+    MouseButtonEvent {
+        x: event.event_x,
+        y: event.event_y,
+        button: get_mouse_button(event.detail),
+        modifiers: get_modifiers(event.state),
+        timestamp: event.time,
+    }
+}
+
+/// Gets the scroll amount of the core buttons that mouse wheels are mapped to
+fn get_wheel_delta(button: u8) -> Option<(f64, f64)> {
+    match button {
+        4 => Some((0.0, -1.0)),
+        5 => Some((0.0, 1.0)),
+        6 => Some((-1.0, 0.0)),
+        7 => Some((1.0, 0.0)),
+        _ => None,
+    }
+}
+
+impl X11RbState {
+    /// KeyRelease events have the same layout as KeyPress events
+    fn get_key_event(&self, event: &KeyPressEvent, repeat: bool) -> Option<KeyEvent> {
+        let state = u16::from(event.state);
+
+        let raw_key = self.keymap.get_keysym(event.detail, state)?;
+        let key = self
+            .keymap
+            .get_base_keysym(event.detail)
+            .map_or(Key::Unknown, keysym::keysym_to_key);
+
+        Some(KeyEvent {
+            key,
+            raw_key,
+            modifiers: get_modifiers(event.state),
+            repeat,
+            timestamp: event.time,
+        })
+    }
+
+    /// Sets the properties that the window manager reads when a window is first mapped
+    fn set_window_hints(&self, window: u32, builder: &WindowBuilder) -> Result<(), RbError> {
+        let (width, height) = builder.clamped_size();
+        let to_i32 = |(width, height): (u16, u16)| (width as i32, height as i32);
+
+        let mut size_hints = WmSizeHints::new();
+        size_hints.position = builder
+            .position
+            .map(|(x, y)| (WmSizeHintsSpecification::UserSpecified, x as i32, y as i32));
+        size_hints.size = Some((
+            WmSizeHintsSpecification::ProgramSpecified,
+            width as i32,
+            height as i32,
+        ));
+
+        // Window managers don't let windows with the same minimum and maximum size be resized
+        if builder.resizable {
+            size_hints.min_size = builder.min_size.map(to_i32);
+            size_hints.max_size = builder.max_size.map(to_i32);
+        } else {
+            size_hints.min_size = Some(to_i32((width, height)));
+            size_hints.max_size = Some(to_i32((width, height)));
+        }
+
+        size_hints.set_normal_hints(&self.connection, window)?;
+
+        if let Some(class) = &builder.class {
+            // The instance name and the class name, each null-terminated
+            let value = [class.as_bytes(), b"\0", class.as_bytes(), b"\0"].concat();
+
+            self.connection.change_property8(
+                PropMode::REPLACE,
+                window,
+                AtomEnum::WM_CLASS,
+                AtomEnum::STRING,
+                &value,
+            )?;
+        }
+
+        let net_wm_state: &[u32] = match builder.state {
+            WindowState::Normal => &[],
+            WindowState::Maximized => &[
+                self.atoms._NET_WM_STATE_MAXIMIZED_VERT,
+                self.atoms._NET_WM_STATE_MAXIMIZED_HORZ,
+            ],
+            WindowState::Minimized => {
+                let mut hints = WmHints::new();
+                hints.initial_state = Some(WmHintsState::Iconic);
+                hints.set(&self.connection, window)?;
+
+                &[]
+            }
+            WindowState::Fullscreen => &[self.atoms._NET_WM_STATE_FULLSCREEN],
+        };
+
+        if !net_wm_state.is_empty() {
+            self.connection.change_property32(
+                PropMode::REPLACE,
+                window,
+                self.atoms._NET_WM_STATE,
+                AtomEnum::ATOM,
+                net_wm_state,
+            )?;
+        }
+
+        if !builder.decorations {
+            self.set_motif_decorations(window, false)?;
+        }
+
+        Ok(())
+    }
+
+    /// Window managers read the decorations from the Motif hints, even though Motif is long gone
+    fn set_motif_decorations(&self, window: u32, decorations: bool) -> Result<(), RbError> {
+        // Flags, functions, decorations, input mode and status. Only the decorations field is
+        // used, and it is set to either none or all.
+        const MWM_HINTS_DECORATIONS: u32 = 1 << 1;
+        const MWM_DECOR_ALL: u32 = 1;
+
+        self.connection.change_property32(
+            PropMode::REPLACE,
+            window,
+            self.atoms._MOTIF_WM_HINTS,
+            self.atoms._MOTIF_WM_HINTS,
+            &[
+                MWM_HINTS_DECORATIONS,
+                0,
+                if decorations { MWM_DECOR_ALL } else { 0 },
+                0,
+                0,
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// The actions of a `_NET_WM_STATE` client message
+const NET_WM_STATE_REMOVE: u32 = 0;
+const NET_WM_STATE_ADD: u32 = 1;
+/// The directions of a `_NET_WM_MOVERESIZE` client message
+fn get_move_resize_direction(edge: Option<ResizeEdge>) -> u32 {
+    match edge {
+        Some(ResizeEdge::TopLeft) => 0,
+        Some(ResizeEdge::Top) => 1,
+        Some(ResizeEdge::TopRight) => 2,
+        Some(ResizeEdge::Right) => 3,
+        Some(ResizeEdge::BottomRight) => 4,
+        Some(ResizeEdge::Bottom) => 5,
+        Some(ResizeEdge::BottomLeft) => 6,
+        Some(ResizeEdge::Left) => 7,
+        None => 8,
+    }
+}
+
+/// The names of each cursor in cursor themes, in the order that they are tried. The first name
+/// is from the CSS cursor names that newer themes use, and the last is from the X cursor font,
+/// which is used when there is no theme.
+fn get_cursor_names(cursor: CursorIcon) -> &'static [&'static str] {
+    match cursor {
+        CursorIcon::Default => &["default", "left_ptr"],
+        CursorIcon::Text => &["text", "xterm"],
+        CursorIcon::Pointer => &["pointer", "hand2"],
+        CursorIcon::Crosshair => &["crosshair", "cross"],
+        CursorIcon::Move => &["move", "fleur"],
+        CursorIcon::Wait => &["wait", "watch"],
+        CursorIcon::Progress => &["progress", "left_ptr_watch", "watch"],
+        CursorIcon::Help => &["help", "question_arrow"],
+        CursorIcon::NotAllowed => &["not-allowed", "crossed_circle", "circle"],
+        CursorIcon::Grab => &["grab", "openhand", "hand1"],
+        CursorIcon::Grabbing => &["grabbing", "closedhand", "fleur"],
+        CursorIcon::ResizeHorizontal => &["ew-resize", "sb_h_double_arrow"],
+        CursorIcon::ResizeVertical => &["ns-resize", "sb_v_double_arrow"],
+        CursorIcon::ResizeTopLeftBottomRight => &["nwse-resize", "bottom_right_corner"],
+        CursorIcon::ResizeTopRightBottomLeft => &["nesw-resize", "bottom_left_corner"],
+    }
+}
+
+/// The values of the first field of `WM_STATE`
+const WITHDRAWN_STATE: u32 = 0;
+const ICONIC_STATE: u32 = 3;
+
+impl X11RbState {
+    /// Sends a client message to the root window, which is how requests are made to the window
+    /// manager
+    fn send_to_window_manager(
+        &self,
+        window: u32,
+        message_type: u32,
+        data: [u32; 5],
+    ) -> Result<(), RbError> {
+        let event = ClientMessageEvent::new(32, window, message_type, data);
+
+        send_event(
+            &self.connection,
+            false,
+            self.screen.root,
+            EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+            event,
+        )?;
+
+        Ok(())
+    }
+
+    /// Gets an atom list property, or an empty list if the window doesn't have it
+    fn get_atoms(&self, window: u32, property: u32) -> Result<Vec<u32>, RbError> {
+        let reply = self
+            .connection
+            .get_property(false, window, property, AtomEnum::ATOM, 0, u32::MAX)?
+            .reply()?;
+
+        Ok(reply.value32().into_iter().flatten().collect())
+    }
+
+    /// Gets the first field of `WM_STATE`, which the window manager sets on the windows that it
+    /// manages
+    fn get_wm_state(&self, window: u32) -> Result<u32, RbError> {
+        let reply = self
+            .connection
+            .get_property(
+                false,
+                window,
+                self.atoms.WM_STATE,
+                self.atoms.WM_STATE,
+                0,
+                1,
+            )?
+            .reply()?;
+
+        Ok(reply
+            .value32()
+            .and_then(|mut value| value.next())
+            .unwrap_or(WITHDRAWN_STATE))
+    }
+
+    fn get_window_state(&self, window: u32) -> Result<WindowStateFlags, RbError> {
+        let net_wm_state = self.get_atoms(window, self.atoms._NET_WM_STATE)?;
+        let has_atom = |atom| net_wm_state.contains(&atom);
+
+        let mut state = WindowStateFlags::NONE;
+        state.set(
+            WindowStateFlags::MAXIMIZED,
+            has_atom(self.atoms._NET_WM_STATE_MAXIMIZED_VERT)
+                && has_atom(self.atoms._NET_WM_STATE_MAXIMIZED_HORZ),
+        );
+        state.set(
+            WindowStateFlags::FULLSCREEN,
+            has_atom(self.atoms._NET_WM_STATE_FULLSCREEN),
+        );
+        state.set(
+            WindowStateFlags::MINIMIZED,
+            has_atom(self.atoms._NET_WM_STATE_HIDDEN) || self.get_wm_state(window)? == ICONIC_STATE,
+        );
+
+        Ok(state)
+    }
+
+    /// Gets a cursor from the cursor theme, or `x11rb::NONE` if the theme doesn't have it
+    fn load_cursor(&mut self, cursor: CursorIcon) -> Result<u32, RbError> {
+        if let Some(&loaded) = self.cursors.get(&cursor) {
+            return Ok(loaded);
+        }
+
+        let handle = match &self.cursor_handle {
+            Some(handle) => handle,
+            None => {
+                // The theme and size are read from the X resources, like Xlib does
+                let database = x11rb::resource_manager::new_from_default(&self.connection)?;
+                let handle = cursor::Handle::new(&self.connection, self.screen_number, &database)?
+                    .reply()?;
+
+                self.cursor_handle.insert(handle)
+            }
+        };
+
+        let mut loaded = x11rb::NONE;
+
+        for name in get_cursor_names(cursor) {
+            loaded = handle.load_cursor(&self.connection, name)?;
+
+            if loaded != x11rb::NONE {
+                break;
+            }
+        }
+
+        self.cursors.insert(cursor, loaded);
+
+        Ok(loaded)
+    }
+
+    /// Gets the RENDER picture format that has 8 bits of alpha, red, green and blue
+    fn get_argb_format(&mut self) -> Result<Pictformat, RbError> {
+        if let Some(format) = self.argb_format {
+            return Ok(format);
+        }
+
+        // Cursors were added in RENDER 0.5
+        let has_cursors = self
+            .connection
+            .extension_information(render::X11_EXTENSION_NAME)?
+            .is_some()
+            && render::query_version(&self.connection, 0, 5)?
+                .reply()
+                .is_ok_and(|version| version.major_version > 0 || version.minor_version >= 5);
+
+        if !has_cursors {
+            return Err(RbError::MissingExtension("RENDER"));
+        }
+
+        let formats = render::query_pict_formats(&self.connection)?.reply()?;
+
+        let format = formats
+            .formats
+            .iter()
+            .find(|format| {
+                let direct = format.direct;
+
+                format.type_ == PictType::DIRECT
+                    && format.depth == 32
+                    && (direct.alpha_shift, direct.alpha_mask) == (24, 0xff)
+                    && (direct.red_shift, direct.red_mask) == (16, 0xff)
+                    && (direct.green_shift, direct.green_mask) == (8, 0xff)
+                    && (direct.blue_shift, direct.blue_mask) == (0, 0xff)
+            })
+            .ok_or(RbError::MissingExtension("RENDER"))?
+            .id;
+
+        self.argb_format = Some(format);
+
+        Ok(format)
+    }
+
+    /// Creates a cursor from an image with RENDER
+    fn create_argb_cursor(
+        &mut self,
+        image: &RgbaImage,
+        (hotspot_x, hotspot_y): (u16, u16),
+    ) -> Result<u32, RbError> {
+        let format = self.get_argb_format()?;
+        let (width, height) = (image.width(), image.height());
+
+        let pixmap = self.connection.generate_id()?;
+        self.connection
+            .create_pixmap(32, pixmap, self.screen.root, width, height)?;
+
+        let gc = self.connection.generate_id()?;
+        self.connection
+            .create_gc(gc, pixmap, &CreateGCAux::default())?;
+
+        let big_endian = self.connection.setup().image_byte_order == ImageOrder::MSB_FIRST;
+        let data: Vec<u8> = image
+            .premultiplied_argb_pixels()
+            .flat_map(|pixel| {
+                if big_endian {
+                    pixel.to_be_bytes()
+                } else {
+                    pixel.to_le_bytes()
+                }
+            })
+            .collect();
+
+        self.connection.put_image(
+            ImageFormat::Z_PIXMAP,
+            pixmap,
+            gc,
+            width,
+            height,
+            0,
+            0,
+            0,
+            32,
+            &data,
+        )?;
+
+        let picture = self.connection.generate_id()?;
+        render::create_picture(
+            &self.connection,
+            picture,
+            pixmap,
+            format,
+            &Default::default(),
+        )?;
+
+        let cursor = self.connection.generate_id()?;
+        render::create_cursor(&self.connection, cursor, picture, hotspot_x, hotspot_y)?;
+
+        render::free_picture(&self.connection, picture)?;
+        self.connection.free_gc(gc)?;
+        self.connection.free_pixmap(pixmap)?;
+
+        Ok(cursor)
+    }
+
+    /// Adds or removes `_NET_WM_STATE` atoms. The window manager only listens for requests
+    /// about the windows that it manages, so the property of withdrawn windows is changed
+    /// directly and read when they are mapped.
+    fn change_net_wm_state(&self, window: u32, add: bool, atoms: [u32; 2]) -> Result<(), RbError> {
+        if self.get_wm_state(window)? != WITHDRAWN_STATE {
+            let action = if add {
+                NET_WM_STATE_ADD
+            } else {
+                NET_WM_STATE_REMOVE
+            };
+
+            // The last field is the source indication, where 1 means a normal application
+            return self.send_to_window_manager(
+                window,
+                self.atoms._NET_WM_STATE,
+                [action, atoms[0], atoms[1], 1, 0],
+            );
+        }
+
+        let mut net_wm_state = self.get_atoms(window, self.atoms._NET_WM_STATE)?;
+        net_wm_state.retain(|atom| !atoms.contains(atom));
+
+        if add {
+            net_wm_state.extend(atoms.into_iter().filter(|&atom| atom != 0));
+        }
+
+        self.connection.change_property32(
+            PropMode::REPLACE,
+            window,
+            self.atoms._NET_WM_STATE,
+            AtomEnum::ATOM,
+            &net_wm_state,
+        )?;
+
+        Ok(())
+    }
 }
 
 fn get_first_bit_pos(mut num: u32) -> u8 {
@@ -62,12 +589,351 @@ atom_manager! {
         WM_DELETE_WINDOW,
         _NET_WM_PING,
         _NET_WM_NAME,
+        _NET_WM_STATE,
+        _NET_WM_STATE_MAXIMIZED_VERT,
+        _NET_WM_STATE_MAXIMIZED_HORZ,
+        _NET_WM_STATE_FULLSCREEN,
+        _NET_WM_STATE_HIDDEN,
+        _NET_WM_MOVERESIZE,
+        _NET_WM_ICON,
+        _NET_ACTIVE_WINDOW,
+        _NET_SUPPORTED,
+        WM_STATE,
+        WM_CHANGE_STATE,
+        _MOTIF_WM_HINTS,
         UTF8_STRING,
         ATOM,
+        CLIPBOARD,
+        TARGETS,
+        TIMESTAMP,
+        INCR,
+        TEXT,
+        TEXT_PLAIN: b"text/plain",
+        TEXT_PLAIN_UTF8: b"text/plain;charset=utf-8",
+        TEXT_URI_LIST: b"text/uri-list",
+        _WWIND_SELECTION,
+        XdndAware,
+        XdndEnter,
+        XdndPosition,
+        XdndStatus,
+        XdndLeave,
+        XdndDrop,
+        XdndFinished,
+        XdndSelection,
+        XdndTypeList,
+        XdndActionCopy,
+        XdndActionMove,
+        XdndActionLink,
+        XdndProxy,
     }
 }
 
 impl X11RbState {
+    /// Blocks until the X server sends something, the event loop is woken up or `timeout` runs
+    /// out. Returns whether there is something to read from the X server.
+    fn wait_for_input(&self, timeout: Option<Duration>) -> bool {
+        let mut poll_fds = [
+            libc::pollfd {
+                #[cfg(not(raw_window_handle))]
+                fd: self.connection.stream().as_raw_fd(),
+                #[cfg(raw_window_handle)]
+                fd: self.connection.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: self.wake_receiver.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+
+        // Rounded up so that we don't wake up right before the timeout and spin
+        let timeout = timeout.map_or(-1, |timeout| {
+            timeout
+                .as_nanos()
+                .div_ceil(1_000_000)
+                .try_into()
+                .unwrap_or(i32::MAX)
+        });
+
+        if unsafe { libc::poll(poll_fds.as_mut_ptr(), poll_fds.len() as _, timeout) } <= 0 {
+            return false;
+        }
+
+        if poll_fds[1].revents != 0 {
+            self.wake_receiver.drain();
+        }
+
+        poll_fds[0].revents != 0
+    }
+
+    unsafe fn handle_event(
+        &mut self,
+        event: Event,
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) -> Result<(), RbError> {
+        if self.handle_clipboard_event(&event) || self.handle_drag_event(&event, event_handler) {
+            return Ok(());
+        }
+
+        match event {
+            Event::Expose(expose) => {
+                // XCB_EXPOSE
+                let region = RectRegion {
+                    x: expose.x,
+                    y: expose.y,
+                    width: expose.width,
+                    height: expose.height,
+                };
+
+                event_handler(WWindCoreEvent::Expose(expose.window.into(), region));
+            }
+            Event::KeyPress(keypress) => {
+                self.last_event_time = keypress.time;
+
+                let held = &mut self.held_keys[keypress.detail as usize];
+                let repeat = *held;
+                *held = true;
+
+                // Keys that aren't in the keymap are ignored
+                let Some(key_event) = self.get_key_event(&keypress, repeat) else {
+                    return Ok(());
+                };
+
+                event_handler(WWindCoreEvent::Keydown(keypress.event.into(), key_event));
+
+                // Shortcuts shouldn't type anything. AltGr is a separate modifier, so it
+                // is not affected by this.
+                if key_event.modifiers.contains(Modifiers::CONTROL)
+                    || key_event.modifiers.contains(Modifiers::ALT)
+                {
+                    return Ok(());
+                }
+
+                for character in self.composer.feed(key_event.raw_key) {
+                    event_handler(WWindCoreEvent::TextInput(keypress.event.into(), character));
+                }
+            }
+            Event::KeyRelease(keyrelease) => {
+                self.held_keys[keyrelease.detail as usize] = false;
+
+                if let Some(key_event) = self.get_key_event(&keyrelease, false) {
+                    event_handler(WWindCoreEvent::Keyup(keyrelease.event.into(), key_event));
+                }
+            }
+            Event::FocusIn(event) | Event::FocusOut(event) => {
+                // Focus changes caused by keyboard grabs and pointer-root focus don't change
+                // which window receives keyboard input
+                if event.mode == NotifyMode::GRAB
+                    || event.mode == NotifyMode::UNGRAB
+                    || event.detail == NotifyDetail::POINTER
+                {
+                    return Ok(());
+                }
+
+                let focused = event.response_type & 0x7f == xproto::FOCUS_IN_EVENT;
+
+                if !focused {
+                    // We won't get the releases of keys that are still held
+                    self.held_keys = [false; 256];
+                }
+
+                event_handler(WWindCoreEvent::FocusChange(event.event.into(), focused));
+            }
+            Event::EnterNotify(event) | Event::LeaveNotify(event) => {
+                if event.detail == NotifyDetail::INFERIOR {
+                    return Ok(());
+                }
+
+                let mouse_event = MouseMoveEvent {
+                    x: event.event_x,
+                    y: event.event_y,
+                    modifiers: get_modifiers(event.state),
+                    timestamp: event.time,
+                };
+
+                let window = event.event.into();
+
+                if event.response_type & 0x7f == xproto::ENTER_NOTIFY_EVENT {
+                    // The scroll valuators may have changed while the pointer was over
+                    // another window
+                    if let Some(smooth_scroll) = &mut self.smooth_scroll {
+                        smooth_scroll.refresh(&self.connection)?;
+                    }
+
+                    event_handler(WWindCoreEvent::PointerEnter(window, mouse_event));
+                } else {
+                    event_handler(WWindCoreEvent::PointerLeave(window, mouse_event));
+                }
+            }
+            Event::ConfigureNotify(event) => {
+                let window = event.window;
+
+                event_handler(WWindCoreEvent::Resize(
+                    window.into(),
+                    event.width,
+                    event.height,
+                ));
+
+                // Real ConfigureNotify events are relative to the parent, which is usually a
+                // window manager frame. Synthetic ones sent by the window manager are relative
+                // to the root. ICCCM says that the window manager sends a synthetic one whenever
+                // it moves a framed window, so the real ones are skipped for those.
+                let is_synthetic = event.response_type & 0x80 != 0;
+
+                if is_synthetic || !self.reparented_windows.contains(&window) {
+                    event_handler(WWindCoreEvent::Move(window.into(), event.x, event.y));
+                }
+            }
+            Event::PropertyNotify(event) => {
+                if event.atom != self.atoms._NET_WM_STATE && event.atom != self.atoms.WM_STATE {
+                    return Ok(());
+                }
+
+                // The window may have been destroyed in the meantime
+                if let Ok(state) = self.get_window_state(event.window) {
+                    event_handler(WWindCoreEvent::StateChange(event.window.into(), state));
+                }
+            }
+            Event::RandrScreenChangeNotify(_) | Event::RandrNotify(_) => {
+                self.handle_monitor_change(event_handler)?;
+            }
+            Event::ReparentNotify(event) => {
+                if event.parent == self.screen.root {
+                    self.reparented_windows.remove(&event.window);
+                } else {
+                    self.reparented_windows.insert(event.window);
+                }
+            }
+            Event::DestroyNotify(event) => {
+                self.reparented_windows.remove(&event.window);
+            }
+            // Also selected by STRUCTURE_NOTIFY, but not needed
+            Event::MapNotify(_) | Event::UnmapNotify(_) | Event::GravityNotify(_) => {}
+            Event::MappingNotify(_) => self.keymap = Keymap::new(&self.connection)?,
+            Event::ButtonPress(event) => {
+                self.last_event_time = event.time;
+
+                if let Some((delta_x, delta_y)) = get_wheel_delta(event.detail) {
+                    // With XInput 2.1 these are emulated from the scroll valuators, which are
+                    // handled separately
+                    if self.smooth_scroll.is_some() {
+                        return Ok(());
+                    }
+
+                    let scroll_event = ScrollEvent {
+                        x: event.event_x,
+                        y: event.event_y,
+                        delta_x,
+                        delta_y,
+                        precise: false,
+                        modifiers: get_modifiers(event.state),
+                        timestamp: event.time,
+                    };
+
+                    event_handler(WWindCoreEvent::Scroll(event.event.into(), scroll_event));
+                    return Ok(());
+                }
+
+                let mouse_event = get_mouse_button_event(&event);
+
+                event_handler(WWindCoreEvent::MouseDown(event.event.into(), mouse_event));
+            }
+            Event::ButtonRelease(event) => {
+                if get_wheel_delta(event.detail).is_some() {
+                    return Ok(());
+                }
+
+                let mouse_event = get_mouse_button_event(&event);
+
+                event_handler(WWindCoreEvent::MouseUp(event.event.into(), mouse_event));
+            }
+            Event::MotionNotify(event) => {
+                let mouse_event = MouseMoveEvent {
+                    x: event.event_x,
+                    y: event.event_y,
+                    modifiers: get_modifiers(event.state),
+                    timestamp: event.time,
+                };
+
+                event_handler(WWindCoreEvent::MouseMove(event.event.into(), mouse_event));
+            }
+            // Selecting XInput motion events replaces core MotionNotify events
+            Event::XinputMotion(event) => {
+                let x = (event.event_x >> 16) as i16;
+                let y = (event.event_y >> 16) as i16;
+                let modifiers = get_modifiers(KeyButMask::from(event.mods.effective as u16));
+
+                let scroll_delta = self
+                    .smooth_scroll
+                    .as_mut()
+                    .and_then(|smooth_scroll| smooth_scroll.handle_motion(&event));
+
+                if let Some((delta_x, delta_y)) = scroll_delta {
+                    let scroll_event = ScrollEvent {
+                        x,
+                        y,
+                        delta_x,
+                        delta_y,
+                        precise: true,
+                        modifiers,
+                        timestamp: event.time,
+                    };
+
+                    event_handler(WWindCoreEvent::Scroll(event.event.into(), scroll_event));
+                    return Ok(());
+                }
+
+                let mouse_event = MouseMoveEvent {
+                    x,
+                    y,
+                    modifiers,
+                    timestamp: event.time,
+                };
+
+                event_handler(WWindCoreEvent::MouseMove(event.event.into(), mouse_event));
+            }
+            Event::XinputDeviceChanged(event) => {
+                if let Some(smooth_scroll) = &mut self.smooth_scroll {
+                    smooth_scroll.device_changed(&event);
+                }
+            }
+            Event::ClientMessage(event) => {
+                // XCB_CLIENT_MESSAGE
+                if event.type_ == self.atoms.WM_PROTOCOLS {
+                    let protocol = event.data.as_data32()[0];
+
+                    if protocol == 0 {
+                        return Ok(());
+                    }
+
+                    if protocol == self.atoms.WM_DELETE_WINDOW {
+                        event_handler(WWindCoreEvent::CloseWindow(event.window.into()));
+                    } else if protocol == self.atoms._NET_WM_PING {
+                        let mut reply = event;
+
+                        reply.window = self.screen.root;
+
+                        // The reply is flushed after the event is handled
+                        send_event(
+                            &self.connection,
+                            false,
+                            self.screen.root,
+                            EventMask::SUBSTRUCTURE_NOTIFY | EventMask::RESIZE_REDIRECT,
+                            reply,
+                        )?;
+                    }
+                } else {
+                    self.handle_xdnd_message(&event, event_handler);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn get_color(&self, color: Color) -> u32 {
         (color.red as u32) << self.red_shift
@@ -80,9 +946,10 @@ impl CoreStateImplementation for X11RbState {
     type Error = RbError;
     type Window = u32;
     type DrawingContext = Self::Window;
+    type Waker = PipeWaker;
 
     unsafe fn new() -> Result<Self, Self::Error> {
-        let (connection, screen_number) = x11rb::connect(None)?;
+        let (connection, screen_number) = XConnection::connect(None)?;
 
         let screen = connection.setup().roots[screen_number].clone();
 
@@ -101,10 +968,8 @@ impl CoreStateImplementation for X11RbState {
             }
         }
 
-        let visual = if let Some(visual) = visual {
-            visual
-        } else {
-            todo!("Invalid root visual")
+        let Some(visual) = visual else {
+            return Err(RbError::UnsupportedVisual);
         };
 
         let red_shift = get_first_bit_pos(visual.red_mask);
@@ -112,21 +977,40 @@ impl CoreStateImplementation for X11RbState {
         let blue_shift = get_first_bit_pos(visual.blue_mask);
 
         // Keyboard information //
-        let min_keycode = connection.setup().min_keycode;
-        let max_keycode = connection.setup().max_keycode;
-        let map = connection
-            .get_keyboard_mapping(min_keycode, max_keycode - min_keycode + 1)?
-            .reply()?;
+        let xkb_supported = connection
+            .xkb_use_extension(1, 0)?
+            .reply()
+            .is_ok_and(|reply| reply.supported);
 
-        let keymap = Keymap {
-            keysyms: map.keysyms,
-            min_keycode: min_keycode as usize,
-            keysyms_per_keycode: map.keysyms_per_keycode,
-        };
+        if !xkb_supported {
+            return Err(RbError::MissingExtension("XKEYBOARD"));
+        }
+
+        // Without this, held keys send a KeyRelease before every repeated KeyPress.
+        // This is best-effort; if it fails, repeats are reported as separate presses.
+        let _ = connection.xkb_per_client_flags(
+            xkb::ID::USE_CORE_KBD.into(),
+            xkb::PerClientFlag::DETECTABLE_AUTO_REPEAT,
+            xkb::PerClientFlag::DETECTABLE_AUTO_REPEAT,
+            xkb::BoolCtrl::from(0u32),
+            xkb::BoolCtrl::from(0u32),
+            xkb::BoolCtrl::from(0u32),
+        );
+
+        let keymap = Keymap::new(&connection)?;
+
+        let smooth_scroll = SmoothScroll::new(&connection)?;
+
+        let (wake_receiver, waker) = pipe_waker::pipe()?;
 
         let atoms = Atoms::new(&connection)?;
         let atoms = atoms.reply()?;
 
+        let clipboard = Clipboard::new(&connection, &screen)?;
+
+        let has_randr = monitor::select_monitor_changes(&connection, &screen)?;
+        let monitors = monitor::query_monitors(&connection, &screen, has_randr)?;
+
         let graphics_context = connection.generate_id()?;
         connection.create_gc(
             graphics_context,
@@ -139,44 +1023,300 @@ impl CoreStateImplementation for X11RbState {
 
         Ok(Self {
             connection,
+            screen_number,
             atoms,
             screen,
             graphics_context,
+            #[cfg(raw_window_handle)]
             visual,
             red_shift,
             green_shift,
             blue_shift,
             keymap,
+            composer: Composer::default(),
+            held_keys: [false; 256],
+            smooth_scroll,
+            cursor_handle: None,
+            cursors: HashMap::new(),
+            argb_format: None,
+            clipboard,
+            last_event_time: x11rb::CURRENT_TIME,
+            pending_events: VecDeque::new(),
+            incoming_drag: None,
+            outgoing_drag: None,
+            has_randr,
+            monitors,
+            reparented_windows: HashSet::new(),
+            wake_receiver,
+            waker,
         })
     }
 
-    fn set_window_title(&mut self, window: Self::Window, title: &str) {
-        unsafe {
-            xproto::change_property(
-                &self.connection,
-                PropMode::REPLACE,
-                window,
-                self.atoms._NET_WM_NAME,
-                self.atoms.UTF8_STRING,
-                8,
-                title.len() as u32,
-                title.as_bytes(),
-            );
-        }
+    fn set_window_title(&mut self, window: Self::Window, title: &str) -> Result<(), Self::Error> {
+        xproto::change_property(
+            &self.connection,
+            PropMode::REPLACE,
+            window,
+            self.atoms._NET_WM_NAME,
+            self.atoms.UTF8_STRING,
+            8,
+            title.len() as u32,
+            title.as_bytes(),
+        )?;
+
+        Ok(())
+    }
+
+    fn accept_window_drops(&mut self, window: Self::Window) {
+        // Connection errors are reported by the next wait_for_events call
+        let _ = self.set_xdnd_aware(window);
     }
 
-    fn add_window(
+    fn set_window_position(
         &mut self,
+        window: Self::Window,
         x: i16,
         y: i16,
-        height: u16,
+    ) -> Result<(), Self::Error> {
+        let aux = ConfigureWindowAux::new().x(x as i32).y(y as i32);
+        self.connection.configure_window(window, &aux)?;
+
+        Ok(())
+    }
+
+    fn set_window_size(
+        &mut self,
+        window: Self::Window,
         width: u16,
-        title: &str,
-    ) -> Result<Self::Window, Self::Error> {
+        height: u16,
+    ) -> Result<(), Self::Error> {
+        let aux = ConfigureWindowAux::new()
+            .width(width as u32)
+            .height(height as u32);
+        self.connection.configure_window(window, &aux)?;
+
+        Ok(())
+    }
+
+    fn set_window_visible(
+        &mut self,
+        window: Self::Window,
+        visible: bool,
+    ) -> Result<(), Self::Error> {
+        if visible {
+            map_window(&self.connection, window)?;
+        } else {
+            unmap_window(&self.connection, window)?;
+        }
+
+        Ok(())
+    }
+
+    fn focus_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        let supported = self.get_atoms(self.screen.root, self.atoms._NET_SUPPORTED)?;
+
+        // EWMH window managers decide which window is active and ignore or undo SetInputFocus
+        if supported.contains(&self.atoms._NET_ACTIVE_WINDOW) {
+            // The first field is the source indication, where 1 means a normal application
+            return self.send_to_window_manager(
+                window,
+                self.atoms._NET_ACTIVE_WINDOW,
+                [1, self.last_event_time, 0, 0, 0],
+            );
+        }
+
+        // Focusing a window that isn't viewable is a BadMatch error
+        let attributes = self.connection.get_window_attributes(window)?.reply()?;
+
+        if attributes.map_state == MapState::VIEWABLE {
+            self.connection
+                .set_input_focus(InputFocus::PARENT, window, self.last_event_time)?;
+        }
+
+        Ok(())
+    }
+
+    fn raise_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        let aux = ConfigureWindowAux::new().stack_mode(StackMode::ABOVE);
+        self.connection.configure_window(window, &aux)?;
+
+        Ok(())
+    }
+
+    fn set_window_fullscreen(
+        &mut self,
+        window: Self::Window,
+        fullscreen: bool,
+    ) -> Result<(), Self::Error> {
+        self.change_net_wm_state(window, fullscreen, [self.atoms._NET_WM_STATE_FULLSCREEN, 0])
+    }
+
+    fn set_window_maximized(
+        &mut self,
+        window: Self::Window,
+        maximized: bool,
+    ) -> Result<(), Self::Error> {
+        self.change_net_wm_state(
+            window,
+            maximized,
+            [
+                self.atoms._NET_WM_STATE_MAXIMIZED_VERT,
+                self.atoms._NET_WM_STATE_MAXIMIZED_HORZ,
+            ],
+        )
+    }
+
+    fn minimize_window(&mut self, window: Self::Window) -> Result<(), Self::Error> {
+        if self.get_wm_state(window)? == WITHDRAWN_STATE {
+            let mut hints = WmHints::get(&self.connection, window)?.reply()?;
+            hints.initial_state = Some(WmHintsState::Iconic);
+            hints.set(&self.connection, window)?;
+
+            return Ok(());
+        }
+
+        self.send_to_window_manager(
+            window,
+            self.atoms.WM_CHANGE_STATE,
+            [ICONIC_STATE, 0, 0, 0, 0],
+        )
+    }
+
+    fn set_window_decorations(
+        &mut self,
+        window: Self::Window,
+        decorations: bool,
+    ) -> Result<(), Self::Error> {
+        self.set_motif_decorations(window, decorations)
+    }
+
+    fn set_window_icon(
+        &mut self,
+        window: Self::Window,
+        icons: &[RgbaImage],
+    ) -> Result<(), Self::Error> {
+        if icons.is_empty() {
+            self.connection
+                .delete_property(window, self.atoms._NET_WM_ICON)?;
+
+            return Ok(());
+        }
+
+        // Each icon is its width and height followed by its pixels
+        let value: Vec<u32> = icons
+            .iter()
+            .flat_map(|icon| {
+                [icon.width() as u32, icon.height() as u32]
+                    .into_iter()
+                    .chain(icon.argb_pixels())
+            })
+            .collect();
+
+        self.connection.change_property32(
+            PropMode::REPLACE,
+            window,
+            self.atoms._NET_WM_ICON,
+            AtomEnum::CARDINAL,
+            &value,
+        )?;
+
+        Ok(())
+    }
+
+    fn set_window_cursor(
+        &mut self,
+        window: Self::Window,
+        cursor: CursorIcon,
+    ) -> Result<(), Self::Error> {
+        let cursor = self.load_cursor(cursor)?;
+
+        let aux = ChangeWindowAttributesAux::new().cursor(cursor);
+        self.connection.change_window_attributes(window, &aux)?;
+
+        Ok(())
+    }
+
+    fn set_window_custom_cursor(
+        &mut self,
+        window: Self::Window,
+        image: &RgbaImage,
+        hotspot: (u16, u16),
+    ) -> Result<(), Self::Error> {
+        let cursor = self.create_argb_cursor(image, hotspot)?;
+
+        let aux = ChangeWindowAttributesAux::new().cursor(cursor);
+        self.connection.change_window_attributes(window, &aux)?;
+
+        // The window keeps its own reference to the cursor
+        self.connection.free_cursor(cursor)?;
+
+        Ok(())
+    }
+
+    fn start_window_drag(
+        &mut self,
+        window: Self::Window,
+        edge: Option<ResizeEdge>,
+    ) -> Result<(), Self::Error> {
+        let pointer = self.connection.query_pointer(self.screen.root)?.reply()?;
+
+        // The window manager waits for this button to be released. Zero means that no button
+        // is held.
+        let button = [
+            (KeyButMask::BUTTON1, 1),
+            (KeyButMask::BUTTON2, 2),
+            (KeyButMask::BUTTON3, 3),
+        ]
+        .into_iter()
+        .find(|&(mask, _)| u16::from(pointer.mask) & u16::from(mask) != 0)
+        .map_or(0, |(_, button)| button);
+
+        // Pressing a button grabs the pointer, and the window manager can't take it over until
+        // it is released
+        self.connection.ungrab_pointer(x11rb::CURRENT_TIME)?;
+
+        self.send_to_window_manager(
+            window,
+            self.atoms._NET_WM_MOVERESIZE,
+            [
+                pointer.root_x as u32,
+                pointer.root_y as u32,
+                get_move_resize_direction(edge),
+                button,
+                1,
+            ],
+        )
+    }
+
+    fn start_data_drag(
+        &mut self,
+        window: Self::Window,
+        payloads: &[DropPayload],
+        image: Option<(&RgbaImage, (u16, u16))>,
+    ) -> Result<(), Self::Error> {
+        self.start_outgoing_drag(window, payloads, image)
+    }
+
+    fn add_window(&mut self, builder: &WindowBuilder) -> Result<Self::Window, Self::Error> {
+        let (width, height) = builder.clamped_size();
+        // The window manager still decides where the window goes unless the position is set in
+        // the size hints
+        let (x, y) = builder.position.unwrap_or_default();
+
         unsafe {
             let window = self.connection.generate_id()?;
 
-            let event_mask = EventMask::EXPOSURE | EventMask::KEY_PRESS;
+            let event_mask = EventMask::EXPOSURE
+                | EventMask::KEY_PRESS
+                | EventMask::KEY_RELEASE
+                | EventMask::BUTTON_PRESS
+                | EventMask::BUTTON_RELEASE
+                | EventMask::POINTER_MOTION
+                | EventMask::STRUCTURE_NOTIFY
+                | EventMask::FOCUS_CHANGE
+                | EventMask::ENTER_WINDOW
+                | EventMask::LEAVE_WINDOW
+                | EventMask::PROPERTY_CHANGE;
             let window_aux = CreateWindowAux::new()
                 .event_mask(event_mask)
                 // .background_pixel(self.screen.white_pixel)
@@ -200,7 +1340,9 @@ impl CoreStateImplementation for X11RbState {
                 &window_aux,
             )?;
 
-            map_window(&self.connection, window).unwrap();
+            if let Some(smooth_scroll) = &self.smooth_scroll {
+                smooth_scroll.select_events(&self.connection, window)?;
+            }
 
             let protocols = [self.atoms.WM_DELETE_WINDOW, self.atoms._NET_WM_PING];
             let protocol_len = protocols.len() as u32;
@@ -215,10 +1357,14 @@ impl CoreStateImplementation for X11RbState {
                 32,
                 protocol_len,
                 protocols,
-            )
-            .unwrap();
+            )?;
+
+            self.set_window_title(window, &builder.title)?;
+            self.set_window_hints(window, builder)?;
 
-            self.set_window_title(window, title);
+            if builder.visible {
+                map_window(&self.connection, window)?;
+            }
 
             self.connection.flush()?;
 
@@ -266,78 +1412,66 @@ impl CoreStateImplementation for X11RbState {
     }
 
     unsafe fn destroy_window(&mut self, window: Self::Window) {
-        destroy_window(&self.connection, window).unwrap();
+        // Connection errors are reported by the next wait_for_events call
+        let _ = destroy_window(&self.connection, window);
     }
 
-    unsafe fn wait_for_events(&mut self, event_handler: &mut unsafe fn(WWindCoreEvent)) {
-        let event = self.connection.wait_for_event();
+    unsafe fn wait_for_events(
+        &mut self,
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+        timeout: Option<Duration>,
+    ) -> Result<bool, Self::Error> {
+        let event = match self.pending_events.pop_front() {
+            Some(event) => event,
+            None => match self.connection.poll_for_event()? {
+                Some(event) => event,
+                None => {
+                    self.connection.flush()?;
 
-        let event = if let Ok(event) = event {
-            event
-        } else {
-            return;
-        };
+                    if !self.wait_for_input(timeout) {
+                        return Ok(false);
+                    }
 
-        match event {
-            Event::Expose(expose) => {
-                // XCB_EXPOSE
-                let region = RectRegion {
-                    x: expose.x,
-                    y: expose.y,
-                    width: expose.width,
-                    height: expose.height,
-                };
+                    let Some(event) = self.connection.poll_for_event()? else {
+                        return Ok(false);
+                    };
 
-                event_handler(WWindCoreEvent::Expose(expose.window.into(), region));
-            }
-            Event::KeyPress(keypress) => {
-                let group: u16 = keypress.state.into();
-
-                if let Some(keysym) = self
-                    .keymap
-                    .get_keysym(keypress.detail as usize, group as usize)
-                {
-                    event_handler(WWindCoreEvent::Keydown(keypress.event.into(), keysym));
-                } else {
-                    eprintln!("Invalid keycode {}", keypress.detail);
+                    event
                 }
-            }
-            Event::ClientMessage(event) => {
-                // XCB_CLIENT_MESSAGE
-                if event.type_ == self.atoms.WM_PROTOCOLS {
-                    let protocol = event.data.as_data32()[0];
+            },
+        };
 
-                    if protocol == 0 {
-                        return;
-                    }
+        self.handle_event(event, event_handler)?;
 
-                    if protocol == self.atoms.WM_DELETE_WINDOW {
-                        event_handler(WWindCoreEvent::CloseWindow(event.window.into()));
-                    } else if protocol == self.atoms._NET_WM_PING {
-                        let mut reply = event;
+        Ok(true)
+    }
 
-                        reply.window = self.screen.root;
+    fn get_waker(&self) -> Self::Waker {
+        self.waker.clone()
+    }
 
-                        println!("pong");
-                        send_event(
-                            &self.connection,
-                            false,
-                            self.screen.root,
-                            EventMask::SUBSTRUCTURE_NOTIFY | EventMask::RESIZE_REDIRECT,
-                            reply,
-                        )
-                        .unwrap();
+    fn set_selection(
+        &mut self,
+        selection: Selection,
+        contents: SelectionContents,
+    ) -> Result<(), Self::Error> {
+        self.set_selection_contents(selection, contents)
+    }
 
-                        self.connection.flush().unwrap();
-                    } else {
-                        println!("unknown client event type {:?}", event.type_);
-                    }
-                }
-            }
-            event => {
-                println!("Unknown event: {event:?}");
-            }
-        }
+    fn get_selection(
+        &mut self,
+        selection: Selection,
+        mime_type: &str,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.get_selection_data(selection, mime_type)
+    }
+
+    fn get_selection_types(&mut self, selection: Selection) -> Result<Vec<String>, Self::Error> {
+        self.get_selection_mime_types(selection)
+    }
+
+    fn get_monitors(&self) -> Result<Vec<Monitor>, Self::Error> {
+        Ok(self.monitors.clone())
     }
 
     fn flush(&mut self) -> Result<(), Self::Error> {
@@ -358,11 +1492,21 @@ impl CoreStateImplementation for X11RbState {
         Ok(())
     }
 
-    fn get_size(&self, window: Self::Window) -> (u16, u16) {
-        let geometry = self.connection.get_geometry(window).unwrap();
-        let geometry = geometry.reply().unwrap();
+    #[cfg(raw_window_handle)]
+    fn raw_window_handle(&self, window: Self::Window) -> Result<RawWindowHandle, HandleError> {
+        let window = NonZeroU32::new(window).ok_or(HandleError::Unavailable)?;
+
+        let mut handle = XcbWindowHandle::new(window);
+        handle.visual_id = NonZeroU32::new(self.visual.visual_id);
+
+        Ok(handle.into())
+    }
+
+    #[cfg(raw_window_handle)]
+    fn raw_display_handle(&self) -> Result<RawDisplayHandle, HandleError> {
+        let connection = NonNull::new(self.connection.get_raw_xcb_connection());
 
-        (geometry.width, geometry.height)
+        Ok(XcbDisplayHandle::new(connection, self.screen_number as i32).into())
     }
 
     unsafe fn get_context(&mut self, window: Self::Window) -> Self::DrawingContext {
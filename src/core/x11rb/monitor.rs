@@ -0,0 +1,166 @@
+//! Monitors through RandR 1.3. Servers without it are treated as having one monitor that covers
+//! the whole screen.
+
+use x11rb::{
+    connection::RequestConnection as _,
+    protocol::{
+        randr::{self, ConnectionExt as _, ModeFlag, ModeInfo, NotifyMask, Rotation},
+        xproto::Screen,
+    },
+    NONE,
+};
+
+use super::{RbError, X11RbState, XConnection};
+use crate::{core::core_state_implementation::WWindCoreEvent, Monitor};
+
+/// Asks for the RandR events that are sent when monitors change. Returns `false` if the server
+/// doesn't support RandR 1.3.
+pub fn select_monitor_changes(connection: &XConnection, screen: &Screen) -> Result<bool, RbError> {
+    if connection
+        .extension_information(randr::X11_EXTENSION_NAME)?
+        .is_none()
+    {
+        return Ok(false);
+    }
+
+    let version = connection.randr_query_version(1, 3)?.reply()?;
+
+    if (version.major_version, version.minor_version) < (1, 3) {
+        return Ok(false);
+    }
+
+    connection.randr_select_input(
+        screen.root,
+        NotifyMask::SCREEN_CHANGE | NotifyMask::CRTC_CHANGE | NotifyMask::OUTPUT_CHANGE,
+    )?;
+
+    Ok(true)
+}
+
+/// Gets the refresh rate of a mode in millihertz
+fn get_refresh_rate(mode: &ModeInfo) -> Option<u32> {
+    let flags = u32::from(mode.mode_flags);
+    let mut vtotal = u64::from(mode.vtotal);
+
+    // Double scan draws every line twice, and interlacing draws half of them per refresh
+    if flags & u32::from(ModeFlag::DOUBLE_SCAN) != 0 {
+        vtotal *= 2;
+    }
+    if flags & u32::from(ModeFlag::INTERLACE) != 0 {
+        vtotal /= 2;
+    }
+
+    let pixels = u64::from(mode.htotal) * vtotal;
+
+    if pixels == 0 {
+        return None;
+    }
+
+    Some(((u64::from(mode.dot_clock) * 1000 + pixels / 2) / pixels) as u32)
+}
+
+/// Treats the whole screen as one monitor
+fn get_screen_monitor(screen: &Screen) -> Monitor {
+    Monitor {
+        name: String::new(),
+        x: 0,
+        y: 0,
+        width: screen.width_in_pixels,
+        height: screen.height_in_pixels,
+        refresh_rate: None,
+        physical_width: screen.width_in_millimeters.into(),
+        physical_height: screen.height_in_millimeters.into(),
+        primary: true,
+    }
+}
+
+/// Gets the outputs that are connected and showing part of the screen
+pub fn query_monitors(
+    connection: &XConnection,
+    screen: &Screen,
+    has_randr: bool,
+) -> Result<Vec<Monitor>, RbError> {
+    if !has_randr {
+        return Ok(vec![get_screen_monitor(screen)]);
+    }
+
+    let resources = connection
+        .randr_get_screen_resources_current(screen.root)?
+        .reply()?;
+    let primary = connection
+        .randr_get_output_primary(screen.root)?
+        .reply()?
+        .output;
+
+    let cookies = resources
+        .outputs
+        .iter()
+        .map(|&output| connection.randr_get_output_info(output, resources.config_timestamp))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut monitors = Vec::new();
+
+    for (&output, cookie) in resources.outputs.iter().zip(cookies) {
+        let info = cookie.reply()?;
+
+        if info.connection != randr::Connection::CONNECTED || info.crtc == NONE {
+            continue;
+        }
+
+        let crtc = connection
+            .randr_get_crtc_info(info.crtc, resources.config_timestamp)?
+            .reply()?;
+
+        let refresh_rate = resources
+            .modes
+            .iter()
+            .find(|mode| mode.id == crtc.mode)
+            .and_then(get_refresh_rate);
+
+        // The physical size is given for the unrotated output
+        let sideways = Rotation::ROTATE90 | Rotation::ROTATE270;
+        let (physical_width, physical_height) =
+            if u16::from(crtc.rotation) & u16::from(sideways) != 0 {
+                (info.mm_height, info.mm_width)
+            } else {
+                (info.mm_width, info.mm_height)
+            };
+
+        monitors.push(Monitor {
+            name: String::from_utf8_lossy(&info.name).into_owned(),
+            x: crtc.x,
+            y: crtc.y,
+            width: crtc.width,
+            height: crtc.height,
+            refresh_rate,
+            physical_width,
+            physical_height,
+            primary: output == primary,
+        });
+    }
+
+    // Some virtual servers have RandR without any outputs
+    if monitors.is_empty() {
+        monitors.push(get_screen_monitor(screen));
+    }
+
+    Ok(monitors)
+}
+
+impl X11RbState {
+    /// Checks the monitors again after a RandR event. Several events are sent for each change,
+    /// so the callback is only called if the monitors are different now.
+    pub(super) unsafe fn handle_monitor_change(
+        &mut self,
+        event_handler: &mut unsafe fn(WWindCoreEvent),
+    ) -> Result<(), RbError> {
+        let monitors = query_monitors(&self.connection, &self.screen, self.has_randr)?;
+
+        if monitors != self.monitors {
+            self.monitors = monitors;
+            event_handler(WWindCoreEvent::MonitorsChanged);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use x11rb::protocol::xinput::{
+    self, ConnectionExt as _, DeviceClass, DeviceClassData, DeviceId, Fp3232, ScrollType,
+    XIEventMask,
+};
+
+use super::{RbError, XConnection};
+
+/// `XIAllMasterDevices`
+const ALL_MASTER_DEVICES: DeviceId = 1;
+
+struct ScrollValuator {
+    number: u16,
+    vertical: bool,
+    /// How much the valuator changes for one wheel step
+    increment: f64,
+    /// The last known value of the valuator. Scroll valuators are absolute, so deltas are
+    /// calculated from this.
+    last_value: Option<f64>,
+}
+
+/// Smooth scrolling through the scroll valuators of XInput 2.1
+pub struct SmoothScroll {
+    /// The scroll valuators of each master pointer
+    devices: HashMap<DeviceId, Vec<ScrollValuator>>,
+}
+
+fn fp3232_to_f64(value: Fp3232) -> f64 {
+    value.integral as f64 + value.frac as f64 / (1u64 << 32) as f64
+}
+
+fn get_scroll_valuators(classes: &[DeviceClass]) -> Vec<ScrollValuator> {
+    let mut valuators: Vec<ScrollValuator> = classes
+        .iter()
+        .filter_map(|class| match &class.data {
+            DeviceClassData::Scroll(scroll) => Some(ScrollValuator {
+                number: scroll.number,
+                vertical: scroll.scroll_type == ScrollType::VERTICAL,
+                increment: fp3232_to_f64(scroll.increment),
+                last_value: None,
+            }),
+            _ => None,
+        })
+        .filter(|valuator| valuator.increment != 0.0)
+        .collect();
+
+    // Scroll valuators are also described by a valuator class, which has their current value
+    for class in classes {
+        if let DeviceClassData::Valuator(valuator_class) = &class.data {
+            if let Some(valuator) = valuators
+                .iter_mut()
+                .find(|valuator| valuator.number == valuator_class.number)
+            {
+                valuator.last_value = Some(fp3232_to_f64(valuator_class.value));
+            }
+        }
+    }
+
+    valuators
+}
+
+impl SmoothScroll {
+    /// Returns `None` if the server does not support XInput 2.1
+    pub fn new(connection: &XConnection) -> Result<Option<Self>, RbError> {
+        let Ok(version) = connection.xinput_xi_query_version(2, 1)?.reply() else {
+            return Ok(None);
+        };
+
+        if (version.major_version, version.minor_version) < (2, 1) {
+            return Ok(None);
+        }
+
+        let mut smooth_scroll = Self {
+            devices: HashMap::new(),
+        };
+
+        smooth_scroll.refresh(connection)?;
+
+        Ok(Some(smooth_scroll))
+    }
+
+    /// Re-reads the scroll valuators and their current values from the server
+    pub fn refresh(&mut self, connection: &XConnection) -> Result<(), RbError> {
+        let reply = connection
+            .xinput_xi_query_device(ALL_MASTER_DEVICES)?
+            .reply()?;
+
+        self.devices = reply
+            .infos
+            .iter()
+            .map(|info| (info.deviceid, get_scroll_valuators(&info.classes)))
+            .collect();
+
+        Ok(())
+    }
+
+    /// Selects the XInput events that are needed for smooth scrolling. Core MotionNotify
+    /// events are no longer sent to this window afterwards, so XI motion events have to be
+    /// handled instead.
+    pub fn select_events(&self, connection: &XConnection, window: u32) -> Result<(), RbError> {
+        connection.xinput_xi_select_events(
+            window,
+            &[xinput::EventMask {
+                deviceid: ALL_MASTER_DEVICES,
+                mask: vec![XIEventMask::MOTION | XIEventMask::DEVICE_CHANGED],
+            }],
+        )?;
+
+        Ok(())
+    }
+
+    /// Updates the scroll valuators of a device after the physical device behind it changed
+    pub fn device_changed(&mut self, event: &xinput::DeviceChangedEvent) {
+        self.devices
+            .insert(event.deviceid, get_scroll_valuators(&event.classes));
+    }
+
+    /// Returns the horizontal and vertical scroll amount of a motion event in wheel steps, or
+    /// `None` if the event did not scroll.
+    pub fn handle_motion(&mut self, event: &xinput::MotionEvent) -> Option<(f64, f64)> {
+        let valuators = self.devices.get_mut(&event.deviceid)?;
+
+        let mut values = event.axisvalues.iter();
+        let mut delta = None;
+
+        for number in 0..event.valuator_mask.len() * 32 {
+            if event.valuator_mask[number / 32] & (1 << (number % 32)) == 0 {
+                continue;
+            }
+
+            let Some(&value) = values.next() else {
+                break;
+            };
+
+            let Some(valuator) = valuators
+                .iter_mut()
+                .find(|valuator| valuator.number as usize == number)
+            else {
+                continue;
+            };
+
+            let value = fp3232_to_f64(value);
+
+            if let Some(last_value) = valuator.last_value.replace(value) {
+                let steps = (value - last_value) / valuator.increment;
+                let (delta_x, delta_y) = delta.get_or_insert((0.0, 0.0));
+
+                if valuator.vertical {
+                    *delta_y += steps;
+                } else {
+                    *delta_x += steps;
+                }
+            }
+        }
+
+        delta
+    }
+}
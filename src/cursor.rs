@@ -0,0 +1,34 @@
+/// A standard mouse cursor shape, set with [set_cursor](crate::Window::set_cursor). The look
+/// of each shape comes from the user's cursor theme.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CursorIcon {
+    /// The normal arrow
+    #[default]
+    Default,
+    /// A text beam, for text that can be selected or edited
+    Text,
+    /// A pointing hand, for links and buttons
+    Pointer,
+    Crosshair,
+    /// Something can be moved in any direction
+    Move,
+    /// The program is busy and can't be interacted with
+    Wait,
+    /// The program is busy, but can still be interacted with
+    Progress,
+    Help,
+    NotAllowed,
+    /// Something can be grabbed and dragged
+    Grab,
+    /// Something is being dragged
+    Grabbing,
+    /// Resizing to the left or right
+    ResizeHorizontal,
+    /// Resizing up or down
+    ResizeVertical,
+    /// Resizing from the top left or bottom right corner
+    ResizeTopLeftBottomRight,
+    /// Resizing from the top right or bottom left corner
+    ResizeTopRightBottomLeft,
+}
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+/// Something that is dragged over or dropped on a window
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DropPayload {
+    /// Files and directories, such as from a file manager. On X11, these come from a
+    /// `text/uri-list` of `file://` URIs.
+    Files(Vec<PathBuf>),
+    /// Text, such as a selection from a text editor. Links that aren't files are given as text
+    /// too, one per line.
+    Text(String),
+}
+
+/// Passed to [on_drag_over](crate::Window::on_drag_over) and [on_drop](crate::Window::on_drop)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DropEvent {
+    /// The position of the pointer, relative to the window's contents
+    pub x: i16,
+    pub y: i16,
+    pub payload: DropPayload,
+}
+
+/// What a drop target did with the data of a drag that was started with
+/// [start_drag](crate::Window::start_drag)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DragAction {
+    /// The target made a copy, so the data should be kept. Targets that do something other than
+    /// copying, moving or linking are reported as copying too.
+    Copy,
+    /// The target took the data, so it should be removed from where it was dragged from
+    Move,
+    /// The target made a link to the data, such as a shortcut to a file
+    Link,
+}
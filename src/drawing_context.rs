@@ -4,7 +4,7 @@ use crate::{
     core::{CoreStateImplementation, DrawingContextEnum},
     state::CoreStateData,
     util::PhantomUnsend,
-    Color, RectRegion,
+    Color, Error, RectRegion,
 };
 
 pub struct DrawingContext<'a> {
@@ -15,31 +15,34 @@ pub struct DrawingContext<'a> {
 }
 
 impl<'a> DrawingContext<'a> {
-    pub fn draw_line(&mut self, x1: u16, y1: u16, x2: u16, y2: u16) {
+    pub fn draw_line(&mut self, x1: u16, y1: u16, x2: u16, y2: u16) -> Result<(), Error> {
         let context = self.context;
 
         self.get_data_mut()
             .core_state
-            .draw_line(context, x1, y1, x2, y2)
-            .unwrap()
+            .draw_line(context, x1, y1, x2, y2)?;
+
+        Ok(())
     }
 
-    pub fn draw_rectangle(&mut self, rectangle: RectRegion) {
+    pub fn draw_rectangle(&mut self, rectangle: RectRegion) -> Result<(), Error> {
         let context = self.context;
 
         self.get_data_mut()
             .core_state
-            .draw_rectangle(context, rectangle)
-            .unwrap()
+            .draw_rectangle(context, rectangle)?;
+
+        Ok(())
     }
 
-    pub fn set_draw_color(&mut self, color: Color) {
+    pub fn set_draw_color(&mut self, color: Color) -> Result<(), Error> {
         let context = self.context;
 
         self.get_data_mut()
             .core_state
-            .set_draw_color(context, color)
-            .unwrap()
+            .set_draw_color(context, color)?;
+
+        Ok(())
     }
 }
 
@@ -0,0 +1,74 @@
+use std::fmt;
+
+use crate::core::CoreError;
+
+/// An error returned by wwind
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A [WWindInstance](crate::WWindInstance) already exists. Only one can exist at a time.
+    AlreadyCreated,
+    /// The window system returned an error, or the connection to it failed
+    Backend(BackendError),
+    /// A [Backend](crate::Backend) name, such as the value of `WWIND_BACKEND`, wasn't recognized
+    UnknownBackend(String),
+    /// The pixels given to an [RgbaImage](crate::RgbaImage) don't match its size, or an empty
+    /// image was used where that isn't possible
+    InvalidImage,
+    /// The backend in use can't do what was asked, such as dragging data out of a window on
+    /// Win32 and Wayland
+    Unsupported,
+}
+
+/// An error from the window system. The [Display](fmt::Display) implementation describes
+/// what went wrong.
+pub struct BackendError(CoreError);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::AlreadyCreated => f.write_str("a wwind instance already exists"),
+            Error::Backend(err) => err.fmt(f),
+            Error::UnknownBackend(name) => write!(f, "unknown backend `{name}`"),
+            Error::InvalidImage => {
+                f.write_str("the image is empty or its data doesn't match its size")
+            }
+            Error::Unsupported => f.write_str("the backend doesn't support this"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::AlreadyCreated
+            | Error::UnknownBackend(_)
+            | Error::InvalidImage
+            | Error::Unsupported => None,
+            Error::Backend(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Debug for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+impl From<CoreError> for Error {
+    fn from(value: CoreError) -> Self {
+        match value {
+            CoreError::Unsupported => Error::Unsupported,
+            value => Error::Backend(BackendError(value)),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::Error;
+
+/// An image made of 8-bit red, green, blue and alpha values, row by row from the top left.
+/// The color values are not premultiplied by the alpha.
+/// ```rust
+/// use wwind::RgbaImage;
+///
+/// // A 2x1 image with an opaque red and a transparent pixel
+/// let image = RgbaImage::new(2, 1, vec![255, 0, 0, 255, 0, 0, 0, 0]).unwrap();
+/// assert_eq!(image.get_pixel(0, 0), Some([255, 0, 0, 255]));
+///
+/// assert!(RgbaImage::new(2, 2, vec![0; 4]).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RgbaImage {
+    width: u16,
+    height: u16,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Fails with [Error::InvalidImage] if `pixels` isn't `width * height * 4` bytes long
+    pub fn new(width: u16, height: u16, pixels: Vec<u8>) -> Result<Self, Error> {
+        if pixels.len() != width as usize * height as usize * 4 {
+            return Err(Error::InvalidImage);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Gets the red, green, blue and alpha values of a pixel
+    pub fn get_pixel(&self, x: u16, y: u16) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+
+        Some(self.pixels[index..index + 4].try_into().unwrap())
+    }
+
+    /// Gets the pixels packed as `0xAARRGGBB`, which is the format that X11 and Win32 use
+    #[cfg(any(x11, win32, wayland))]
+    pub(crate) fn argb_pixels(&self) -> impl Iterator<Item = u32> + '_ {
+        self.pixels
+            .chunks_exact(4)
+            .map(|pixel| u32::from_be_bytes([pixel[3], pixel[0], pixel[1], pixel[2]]))
+    }
+
+    /// Like [argb_pixels](Self::argb_pixels), but with the colors multiplied by the alpha, which
+    /// is what RENDER and wl_shm expect
+    #[cfg(any(x11, wayland))]
+    pub(crate) fn premultiplied_argb_pixels(&self) -> impl Iterator<Item = u32> + '_ {
+        self.argb_pixels().map(premultiply)
+    }
+}
+
+/// Multiplies the color of an `0xAARRGGBB` pixel by its alpha
+#[cfg(any(x11, wayland))]
+fn premultiply(pixel: u32) -> u32 {
+    let alpha = pixel >> 24;
+    let multiply = |shift: u32| ((pixel >> shift & 0xff) * alpha / 255) << shift;
+
+    alpha << 24 | multiply(16) | multiply(8) | multiply(0)
+}
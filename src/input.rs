@@ -0,0 +1,263 @@
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+/// A set of keyboard modifiers that were held down when an event occurred.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub const NONE: Self = Self(0);
+    pub const SHIFT: Self = Self(1);
+    pub const CONTROL: Self = Self(1 << 1);
+    pub const ALT: Self = Self(1 << 2);
+    /// The "Windows" or "Command" key
+    pub const SUPER: Self = Self(1 << 3);
+    pub const CAPS_LOCK: Self = Self(1 << 4);
+    pub const NUM_LOCK: Self = Self(1 << 5);
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs)
+    }
+}
+
+impl BitAnd for Modifiers {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+    /// The "back" side button
+    Back,
+    /// The "forward" side button
+    Forward,
+    /// Any other button. The number is backend specific
+    Other(u8),
+}
+
+/// Sent when a mouse button is pressed or released over a window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MouseButtonEvent {
+    /// The x position of the pointer relative to the window
+    pub x: i16,
+    /// The y position of the pointer relative to the window
+    pub y: i16,
+    pub button: MouseButton,
+    pub modifiers: Modifiers,
+    /// The time of the event in milliseconds. The starting point is backend specific,
+    /// so this should only be compared with other timestamps.
+    pub timestamp: u32,
+}
+
+/// Sent when the pointer moves over a window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MouseMoveEvent {
+    /// The x position of the pointer relative to the window
+    pub x: i16,
+    /// The y position of the pointer relative to the window
+    pub y: i16,
+    pub modifiers: Modifiers,
+    /// The time of the event in milliseconds. The starting point is backend specific,
+    /// so this should only be compared with other timestamps.
+    pub timestamp: u32,
+}
+
+/// A key on the keyboard. Keys are identified by what they produce on a US QWERTY layout
+/// without any modifiers held.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+
+    Left,
+    Up,
+    Right,
+    Down,
+
+    Enter,
+    Escape,
+    Backspace,
+    Tab,
+    Space,
+    Insert,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+
+    LeftShift,
+    RightShift,
+    LeftControl,
+    RightControl,
+    LeftAlt,
+    RightAlt,
+    LeftSuper,
+    RightSuper,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    PrintScreen,
+    Pause,
+    Menu,
+
+    Minus,
+    Equals,
+    LeftBracket,
+    RightBracket,
+    Backslash,
+    Semicolon,
+    Apostrophe,
+    Grave,
+    Comma,
+    Period,
+    Slash,
+
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+
+    /// A key that wwind does not know about. [KeyEvent::raw_key] can still be used to identify it.
+    Unknown,
+}
+
+/// Sent when a key is pressed or released while a window has keyboard focus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub key: Key,
+    /// The backend specific code of the key. This is the keysym on X11 and the virtual-key
+    /// code on Win32.
+    pub raw_key: u32,
+    /// The modifiers that were held before this key was pressed or released
+    pub modifiers: Modifiers,
+    /// Whether this press was generated by the key being held down. Always `false` for
+    /// key releases.
+    pub repeat: bool,
+    /// The time of the event in milliseconds. The starting point is backend specific,
+    /// so this should only be compared with other timestamps.
+    pub timestamp: u32,
+}
+
+/// Sent when the user scrolls over a window with a mouse wheel or touchpad.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollEvent {
+    /// The x position of the pointer relative to the window
+    pub x: i16,
+    /// The y position of the pointer relative to the window
+    pub y: i16,
+    /// How far to scroll horizontally, in wheel steps. Positive values scroll to the right.
+    pub delta_x: f64,
+    /// How far to scroll vertically, in wheel steps. Positive values scroll down.
+    pub delta_y: f64,
+    /// Whether the deltas come from a device that scrolls smoothly, such as a touchpad. If
+    /// this is `false`, the deltas are always whole steps.
+    pub precise: bool,
+    pub modifiers: Modifiers,
+    /// The time of the event in milliseconds. The starting point is backend specific,
+    /// so this should only be compared with other timestamps.
+    pub timestamp: u32,
+}
@@ -4,40 +4,47 @@
 //! providing reasonable performance and platform consistency.
 //! # Example
 //! ```rust,no_run
-//! use wwind::{WWindInstance, WWindState, Window, Color, RectRegion};
+//! use wwind::{WWindInstance, WWindInitState, Window, WindowBuilder, Color, RectRegion};
 //!
-//! fn main() {
-//!     let instance = WWindInstance::new(|state: &mut WWindState| {
-//!         let mut window = state.add_window(100, 100, 500, 500, "test title");
+//! fn draw(window: &mut Window) -> Result<(), wwind::Error> {
+//!     let (w, h) = window.get_size();
+//!     let window_rect = RectRegion { x: 0, y: 0, width: w, height: h };
 //!
-//!         window.on_redraw(|_, window, _| {
-//!             let (w, h) = window.get_size();
-//!             let window_rect = RectRegion { x: 0, y: 0, width: w, height: h };
+//!     let mut context = window.get_drawing_context();
 //!
-//!             let mut context = window.get_drawing_context();
+//!     // Draw background //
 //!
-//!             // Draw background //
+//!     context.set_draw_color(Color::from_hex(0xffffff))?;
+//!     context.draw_rectangle(window_rect)?;
+//!     
+//!     // Draw lines //
 //!
-//!             context.set_draw_color(Color::from_hex(0xffffff));
-//!             context.draw_rectangle(window_rect);
-//!             
-//!             // Draw lines //
+//!     context.set_draw_color(Color::from_rgb(0, 255, 0))?;
+//!     context.draw_line(0, 0, w, h)?;
+//!     
+//!     context.set_draw_color(Color::from_rgb(255,0,0))?;
+//!     context.draw_line(w, 0, 0, h)?;
 //!
-//!             context.set_draw_color(Color::from_rgb(0, 255, 0));
-//!             context.draw_line(0, 0, w, h);
-//!             
-//!             context.set_draw_color(Color::from_rgb(255,0,0));
-//!             context.draw_line(w, 0, 0, h);
-//!         });
-//!     });
+//!     Ok(())
+//! }
+//!
+//! fn main() -> Result<(), wwind::Error> {
+//!     let instance = WWindInstance::new(|state: &mut WWindInitState| {
+//!         let mut window = WindowBuilder::new()
+//!             .title("test title")
+//!             .position(100, 100)
+//!             .size(500, 500)
+//!             .build(state)
+//!             .unwrap();
+//!
+//!         window.on_redraw(|_, window, _| draw(window).unwrap());
+//!     })?;
 //!
-//!     instance.unwrap().run();
+//!     instance.run()
 //! }
 //! ```
 //! ![Expected Output](https://github.com/wr7/wwind/raw/master/docs/example.png)
 
-use std::{marker::PhantomData, mem::MaybeUninit};
-
 //  TODO:
 // -  fix Win32Data leak
 // -  add support for color modes besides TrueColor
@@ -74,22 +81,56 @@ impl Color {
     }
 }
 
+use std::time::Duration;
+
 use util::PhantomUnsend;
 
+mod backend;
+mod clipboard;
 mod core;
+mod cursor;
+mod drag;
 mod drawing_context;
+mod error;
+mod image;
+mod input;
+mod monitor;
+mod proxy;
 mod state;
+mod timer;
 mod util;
 mod window;
+mod window_builder;
 
+#[cfg(headless)]
+pub use crate::core::{
+    Framebuffer, HeadlessCursor, HeadlessDrag, HeadlessEvent, HeadlessHandle, HeadlessWindowId,
+};
+pub use backend::{Backend, BACKEND_ENV_VAR};
+pub use clipboard::{Clipboard, Selection, TEXT_MIME_TYPE};
+pub use cursor::CursorIcon;
+pub use drag::{DragAction, DropEvent, DropPayload};
 pub use drawing_context::DrawingContext;
+pub use error::{BackendError, Error};
+pub use image::RgbaImage;
+pub use input::{
+    Key, KeyEvent, Modifiers, MouseButton, MouseButtonEvent, MouseMoveEvent, ScrollEvent,
+};
+pub use monitor::Monitor;
+pub use proxy::EventLoopProxy;
+pub use state::CoreStateType;
 pub use state::WWindInitState;
 pub use state::WWindState;
-pub use window::Window;
+pub use timer::TimerHandle;
+pub use window::{ResizeEdge, Window, WindowStateFlags};
+pub use window_builder::{WindowBuilder, WindowState};
+
+#[cfg(raw_window_handle)]
+pub use raw_window_handle;
 
 static mut SHOULD_EXIT: bool = false;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct RectRegion {
     pub x: u16,
     pub y: u16,
@@ -98,47 +139,280 @@ pub struct RectRegion {
 }
 
 impl RectRegion {
+    /// Regions that go past the edge of the coordinate space are cut off at `u16::MAX`
     pub fn get_bottom_y(&self) -> u16 {
-        self.height + self.y
+        self.y.saturating_add(self.height)
     }
     pub fn get_right_x(&self) -> u16 {
-        self.x + self.width
+        self.x.saturating_add(self.width)
+    }
+
+    /// Gets the smallest region that contains both regions
+    pub fn union(&self, other: &Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+
+        Self {
+            x,
+            y,
+            width: self.get_right_x().max(other.get_right_x()) - x,
+            height: self.get_bottom_y().max(other.get_bottom_y()) - y,
+        }
+    }
+
+    /// Gets the region that is in both regions, or `None` if they don't overlap
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right_x = self.get_right_x().min(other.get_right_x());
+        let bottom_y = self.get_bottom_y().min(other.get_bottom_y());
+
+        if right_x <= x || bottom_y <= y {
+            return None;
+        }
+
+        Some(Self {
+            x,
+            y,
+            width: right_x - x,
+            height: bottom_y - y,
+        })
     }
 }
 
+/// Returned by [WWindInstance::pump_events]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PumpStatus {
+    /// The event loop is still running
+    Continue,
+    /// The event loop exited, either because [WWindInitState::schedule_exit] was called or
+    /// because every window was closed. The instance has been torn down, so a new
+    /// [WWindInstance] can be created.
+    Exit,
+}
+
 pub struct WWindInstance<OnInit: FnOnce(&mut WWindInitState<UserData>) -> UserData, UserData = ()> {
     state: WWindInitState<UserData>,
-    on_init: OnInit,
+    /// `None` once the instance has been initialized
+    on_init: Option<OnInit>,
+    /// Whether the state has been destroyed
+    exited: bool,
     _unsend: PhantomUnsend,
 }
 
 impl<OnInit: FnOnce(&mut WWindInitState<UserData>) -> UserData, UserData>
     WWindInstance<OnInit, UserData>
 {
-    pub fn new(on_init: OnInit) -> Option<Self> {
-        let state = WWindInitState::new()?;
+    /// Connects to the window system. Fails if another [WWindInstance] exists or if the window
+    /// system can't be reached.
+    ///
+    /// The backend can be chosen with the `WWIND_BACKEND` environment variable. See [Backend].
+    pub fn new(on_init: OnInit) -> Result<Self, Error> {
+        Self::with_backend(Backend::Auto, on_init)
+    }
+
+    /// Like [new](Self::new), but tries `backend` first. If it fails, the other compiled-in
+    /// backends are tried.
+    pub fn with_backend(backend: Backend, on_init: OnInit) -> Result<Self, Error> {
+        let state = WWindInitState::new(backend)?;
         let _unsend = Default::default();
 
-        Some(Self {
-            on_init,
+        Ok(Self {
+            on_init: Some(on_init),
             state,
+            exited: false,
             _unsend,
         })
     }
 
-    pub fn run(mut self) {
-        let userdata = (self.on_init)(&mut self.state);
-        let userdata = Box::into_raw(Box::new(userdata));
+    /// Like [new](Self::new), but uses the in-memory headless backend instead of the window
+    /// system. Windows can then be inspected and sent events through a [HeadlessHandle].
+    #[cfg(headless)]
+    pub fn new_headless(on_init: OnInit) -> Result<Self, Error> {
+        Self::with_backend(Backend::Headless, on_init)
+    }
+
+    /// Gets the backend that is in use, or `None` if the instance has exited
+    pub fn backend_type(&self) -> Option<CoreStateType> {
+        (!self.exited).then(|| self.state.backend_type())
+    }
+
+    /// Gets a handle for controlling the headless backend. Returns `None` if another backend is
+    /// in use or if the instance has exited.
+    #[cfg(headless)]
+    pub fn headless_handle(&self) -> Option<HeadlessHandle> {
+        if self.exited {
+            return None;
+        }
+
+        self.state.headless_handle()
+    }
+
+    /// Runs the event loop until it exits
+    pub fn run(mut self) -> Result<(), Error> {
+        self.run_return()
+    }
+
+    /// Runs the event loop until it exits, then tears down the instance. Unlike [run](Self::run),
+    /// this returns control to the caller, and a new [WWindInstance] can be created afterwards.
+    pub fn run_return(&mut self) -> Result<(), Error> {
+        while self.pump_events(None)? == PumpStatus::Continue {}
+
+        Ok(())
+    }
+
+    /// Handles every pending event and then returns. If there are no pending events, this waits
+    /// for up to `timeout` for one to arrive. A `timeout` of `None` waits indefinitely.
+    ///
+    /// The first call runs the `on_init` closure. If the window system returns an error, the
+    /// instance is torn down and the error is returned.
+    pub fn pump_events(&mut self, timeout: Option<Duration>) -> Result<PumpStatus, Error> {
+        if self.exited {
+            return Ok(PumpStatus::Exit);
+        }
 
-        unsafe { state::USERDATA = userdata as *mut _ };
+        if let Some(on_init) = self.on_init.take() {
+            let userdata = on_init(&mut self.state);
+            let userdata = Box::into_raw(Box::new(userdata));
 
-        unsafe {
-            while !SHOULD_EXIT && self.state.do_windows_exist() {
-                self.state.wait_for_events();
-                self.state.destroy_pending_windows();
+            unsafe { state::USERDATA = userdata as *mut _ };
+        }
+
+        let result = unsafe { self.pump_events_inner(timeout) };
+
+        if !matches!(result, Ok(PumpStatus::Continue)) {
+            unsafe { self.destroy() };
+        }
+
+        result
+    }
+
+    unsafe fn pump_events_inner(&mut self, timeout: Option<Duration>) -> Result<PumpStatus, Error> {
+        let mut timeout = timeout;
+
+        while !SHOULD_EXIT && self.state.do_windows_exist() {
+            let handled = self.state.wait_for_events(timeout)?;
+            self.state.dispatch_user_events()?;
+            self.state.run_due_timers()?;
+            self.state.destroy_pending_windows();
+            self.state.redraw_damaged_windows()?;
+
+            if !handled {
+                return Ok(PumpStatus::Continue);
             }
 
-            self.state.destroy();
+            // Only the first event is waited for
+            timeout = Some(Duration::ZERO);
+        }
+
+        Ok(PumpStatus::Exit)
+    }
+
+    unsafe fn destroy(&mut self) {
+        if !self.exited {
+            self.exited = true;
+            self.state.clone().destroy();
+        }
+    }
+}
+
+impl<OnInit: FnOnce(&mut WWindInitState<UserData>) -> UserData, UserData> Drop
+    for WWindInstance<OnInit, UserData>
+{
+    fn drop(&mut self) {
+        unsafe { self.destroy() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RectRegion;
+
+    fn rect(x: u16, y: u16, width: u16, height: u16) -> RectRegion {
+        RectRegion {
+            x,
+            y,
+            width,
+            height,
         }
     }
+
+    #[test]
+    fn union_overlapping() {
+        let a = rect(10, 20, 30, 40);
+        let b = rect(20, 10, 50, 20);
+
+        assert_eq!(a.union(&b), rect(10, 10, 60, 50));
+        assert_eq!(b.union(&a), rect(10, 10, 60, 50));
+    }
+
+    #[test]
+    fn union_disjoint() {
+        let a = rect(0, 0, 10, 10);
+        let b = rect(100, 200, 5, 5);
+
+        assert_eq!(a.union(&b), rect(0, 0, 105, 205));
+    }
+
+    #[test]
+    fn union_contained() {
+        let outer = rect(0, 0, 100, 100);
+        let inner = rect(10, 10, 5, 5);
+
+        assert_eq!(outer.union(&inner), outer);
+        assert_eq!(inner.union(&outer), outer);
+    }
+
+    #[test]
+    fn union_at_edge() {
+        let a = rect(u16::MAX - 10, u16::MAX - 10, 100, 100);
+        let b = rect(u16::MAX - 20, u16::MAX - 5, 5, 5);
+
+        assert_eq!(a.get_right_x(), u16::MAX);
+        assert_eq!(a.get_bottom_y(), u16::MAX);
+        assert_eq!(a.union(&b), rect(u16::MAX - 20, u16::MAX - 10, 20, 10));
+    }
+
+    #[test]
+    fn intersection_overlapping() {
+        let a = rect(10, 20, 30, 40);
+        let b = rect(20, 10, 50, 20);
+
+        assert_eq!(a.intersection(&b), Some(rect(20, 20, 20, 10)));
+        assert_eq!(b.intersection(&a), Some(rect(20, 20, 20, 10)));
+    }
+
+    #[test]
+    fn intersection_disjoint() {
+        let a = rect(0, 0, 10, 10);
+        let b = rect(100, 200, 5, 5);
+
+        assert_eq!(a.intersection(&b), None);
+    }
+
+    #[test]
+    fn intersection_touching() {
+        let a = rect(0, 0, 10, 10);
+
+        assert_eq!(a.intersection(&rect(10, 0, 10, 10)), None);
+        assert_eq!(a.intersection(&rect(0, 10, 10, 10)), None);
+    }
+
+    #[test]
+    fn intersection_empty() {
+        let a = rect(0, 0, 10, 10);
+
+        assert_eq!(a.intersection(&rect(5, 5, 0, 0)), None);
+    }
+
+    #[test]
+    fn intersection_at_edge() {
+        let a = rect(u16::MAX - 10, u16::MAX - 10, 100, 100);
+        let b = rect(u16::MAX - 5, 0, 100, u16::MAX);
+
+        assert_eq!(
+            a.intersection(&b),
+            Some(rect(u16::MAX - 5, u16::MAX - 10, 5, 10))
+        );
+    }
 }
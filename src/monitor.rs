@@ -0,0 +1,23 @@
+use crate::WWindState;
+
+pub type OnMonitorsChanged<UserData> = dyn FnMut(&mut WWindState<UserData>) + 'static;
+
+/// A screen that is connected to the computer. Can be obtained with
+/// [monitors](crate::WWindInitState::monitors).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Monitor {
+    /// The name of the connector, like `HDMI-1` or `\\.\DISPLAY1`. Empty if it isn't known.
+    pub name: String,
+    /// The position of the top left corner, in the same coordinates as the positions of windows
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    /// In millihertz, so 60 Hz is 60000. `None` if it isn't known.
+    pub refresh_rate: Option<u32>,
+    /// The size of the picture in millimeters, or zero if it isn't known
+    pub physical_width: u32,
+    pub physical_height: u32,
+    /// Whether this is the main monitor, which taskbars and new windows usually go on
+    pub primary: bool,
+}
@@ -0,0 +1,94 @@
+use std::{
+    any::Any,
+    marker::PhantomData,
+    mem,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    core::{CoreWaker, WakerEnum},
+    WWindState,
+};
+
+/// A user event whose type has been erased. Handlers downcast it back.
+pub(crate) type UserEvent = Box<dyn Any + Send>;
+
+pub(crate) type OnUserEvent<UserData> = dyn FnMut(&mut WWindState<UserData>, UserEvent) + 'static;
+
+/// The events that were sent from other threads but not yet handled
+pub(crate) struct UserEventQueue {
+    /// `None` once the event loop has exited
+    events: Mutex<Option<Vec<UserEvent>>>,
+}
+
+impl UserEventQueue {
+    pub fn new() -> Self {
+        Self {
+            events: Mutex::new(Some(Vec::new())),
+        }
+    }
+
+    /// Takes all of the queued events
+    pub fn take(&self) -> Vec<UserEvent> {
+        let mut events = self.events.lock().unwrap_or_else(|err| err.into_inner());
+
+        events.as_mut().map(mem::take).unwrap_or_default()
+    }
+
+    /// Drops all queued events and makes future sends fail
+    pub fn close(&self) {
+        *self.events.lock().unwrap_or_else(|err| err.into_inner()) = None;
+    }
+}
+
+/// Sends events to the event loop from any thread. These are handled by the callback set with
+/// [on_user_event](crate::WWindInitState::on_user_event) for the same `T`.
+///
+/// Can be created with [create_proxy](crate::WWindInitState::create_proxy).
+pub struct EventLoopProxy<T> {
+    queue: Arc<UserEventQueue>,
+    waker: WakerEnum,
+    _phantom_data: PhantomData<fn(T)>,
+}
+
+impl<T: Send + 'static> EventLoopProxy<T> {
+    pub(crate) fn new(queue: Arc<UserEventQueue>, waker: WakerEnum) -> Self {
+        Self {
+            queue,
+            waker,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// Queues `event` and wakes up the event loop. If the event loop has already exited, the
+    /// event is given back.
+    pub fn send(&self, event: T) -> Result<(), T> {
+        {
+            let mut events = self
+                .queue
+                .events
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+
+            let Some(events) = events.as_mut() else {
+                return Err(event);
+            };
+
+            events.push(Box::new(event));
+        }
+
+        self.waker.wake();
+
+        Ok(())
+    }
+}
+
+impl<T> Clone for EventLoopProxy<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+            waker: self.waker.clone(),
+            _phantom_data: PhantomData,
+        }
+    }
+}
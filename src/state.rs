@@ -1,26 +1,63 @@
 use std::{
-    collections::HashMap,
+    any::TypeId,
+    collections::{hash_map::Entry, HashMap},
     ffi::c_void,
     marker::PhantomData,
     mem::{self, MaybeUninit},
     ops::{Deref, DerefMut},
-    ptr,
-    sync::atomic::{self, AtomicBool},
+    ptr::{self, addr_of_mut},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
 use crate::{
+    clipboard::Clipboard,
     core::{CoreStateEnum, CoreStateImplementation, CoreWindowRef, WWindCoreEvent},
+    monitor::{Monitor, OnMonitorsChanged},
+    proxy::{EventLoopProxy, OnUserEvent, UserEvent, UserEventQueue},
+    timer::{OnTimer, Timer, TimerHandle, Timers},
     util::PhantomUnsend,
-    window::{self, OnClose, OnKeydown, OnRedraw, WindowData},
-    Window, SHOULD_EXIT,
+    window::{
+        self, Binding, OnClose, OnDragEnd, OnDragLeave, OnDrop, OnFocusChange, OnKey,
+        OnMouseButton, OnMouseMove, OnMove, OnRedraw, OnResize, OnScroll, OnStateChange,
+        OnTextInput, WindowData,
+    },
+    Backend, Error, RectRegion, Window, WindowBuilder, SHOULD_EXIT,
 };
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg(headless)]
+use crate::HeadlessHandle;
+#[cfg(raw_window_handle)]
+use raw_window_handle::{DisplayHandle, HandleError, HasDisplayHandle};
+
+/// A backend that was compiled in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum CoreStateType {
     #[cfg(x11)]
     X11,
+    #[cfg(wayland)]
+    Wayland,
     #[cfg(windows)]
     Win32,
+    #[cfg(headless)]
+    Headless,
+}
+
+impl CoreStateType {
+    /// Every compiled-in backend, in the order that they are tried in
+    pub const ALL: &'static [CoreStateType] = &[
+        #[cfg(x11)]
+        CoreStateType::X11,
+        #[cfg(wayland)]
+        CoreStateType::Wayland,
+        #[cfg(windows)]
+        CoreStateType::Win32,
+        #[cfg(headless)]
+        CoreStateType::Headless,
+    ];
 }
 
 pub(super) static STATE_CREATED: AtomicBool = AtomicBool::new(false);
@@ -72,6 +109,13 @@ impl<UserData> AsMut<WWindInitState<UserData>> for WWindState<UserData> {
     }
 }
 
+#[cfg(raw_window_handle)]
+impl<UserData> HasDisplayHandle for WWindState<UserData> {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        self.0.display_handle()
+    }
+}
+
 impl<UserData> From<WWindState<UserData>> for WWindInitState<UserData> {
     fn from(value: WWindState<UserData>) -> Self {
         value.0
@@ -89,6 +133,22 @@ pub struct CoreStateData {
     pub(crate) core_state: CoreStateEnum,
     pub(crate) windows: HashMap<CoreWindowRef, WindowData>,
     pub(crate) windows_to_destroy: Vec<CoreWindowRef>,
+    pub(crate) timers: Timers,
+    pub(crate) user_events: Arc<UserEventQueue>,
+    /// The [OnUserEvent] callbacks for each event type
+    pub(crate) user_event_handlers: HashMap<TypeId, Binding>,
+    /// The [OnMonitorsChanged] callback
+    pub(crate) monitors_changed: Option<Binding>,
+}
+
+/// The connection to the display server, for libraries like softbuffer, wgpu or glutin
+#[cfg(raw_window_handle)]
+impl<UserData> HasDisplayHandle for WWindInitState<UserData> {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        let raw = self.get_core_data().core_state.raw_display_handle()?;
+
+        Ok(unsafe { DisplayHandle::borrow_raw(raw) })
+    }
 }
 
 impl<UserData> WWindInitState<UserData> {
@@ -96,6 +156,8 @@ impl<UserData> WWindInitState<UserData> {
         unsafe { SHOULD_EXIT = true };
     }
 
+    /// Creates a window. Note that the height comes before the width. [WindowBuilder] has
+    /// more options and can't mix the two up.
     pub fn add_window<'a>(
         &'a mut self,
         x: i16,
@@ -103,23 +165,154 @@ impl<UserData> WWindInitState<UserData> {
         height: u16,
         width: u16,
         title: &str,
-    ) -> Window<'a, UserData> {
-        let window_ref = self
-            .get_core_data_mut()
-            .core_state
-            .add_window(x, y, height, width, title)
-            .unwrap();
+    ) -> Result<Window<'a, UserData>, Error> {
+        WindowBuilder::new()
+            .position(x, y)
+            .size(width, height)
+            .title(title)
+            .build(self)
+    }
+
+    pub(crate) fn create_window<'a>(
+        &'a mut self,
+        builder: &WindowBuilder,
+    ) -> Result<Window<'a, UserData>, Error> {
+        let window_ref = self.get_core_data_mut().core_state.add_window(builder)?;
+
+        let (x, y) = builder.position.unwrap_or_default();
+        let (width, height) = builder.clamped_size();
 
         self.get_core_data_mut()
             .windows
-            .insert(window_ref, WindowData::new(width, height));
+            .insert(window_ref, WindowData::new(x, y, width, height));
 
-        self.get_window_from_ref(window_ref)
+        Ok(self.get_window_from_ref(window_ref))
     }
 
     pub fn do_windows_exist(&self) -> bool {
         !self.get_core_data().windows.is_empty()
     }
+
+    /// Gets the backend that is in use
+    pub fn backend_type(&self) -> CoreStateType {
+        self.get_core_data().core_state.get_type()
+    }
+
+    /// Gets a handle for controlling the headless backend, or `None` if another backend is in use
+    #[cfg(headless)]
+    pub fn headless_handle(&self) -> Option<HeadlessHandle> {
+        self.get_core_data().core_state.headless_handle()
+    }
+
+    /// Calls `closure` once after `delay` has passed
+    pub fn set_timeout<F: FnOnce(&mut WWindState<UserData>) + 'static>(
+        &mut self,
+        delay: Duration,
+        closure: F,
+    ) -> TimerHandle {
+        let mut closure = Some(closure);
+
+        let callback = Box::new(move |state: &mut WWindState<UserData>| {
+            if let Some(closure) = closure.take() {
+                closure(state)
+            }
+        }) as Box<OnTimer<UserData>>;
+
+        let callback = unsafe { window::into_binding(callback) };
+
+        self.get_core_data_mut()
+            .timers
+            .add(Instant::now() + delay, None, callback)
+    }
+
+    /// Calls `closure` every `interval` until the timer is cancelled
+    pub fn set_interval<F: FnMut(&mut WWindState<UserData>) + 'static>(
+        &mut self,
+        interval: Duration,
+        closure: F,
+    ) -> TimerHandle {
+        let callback = Box::new(closure) as Box<OnTimer<UserData>>;
+        let callback = unsafe { window::into_binding(callback) };
+
+        self.get_core_data_mut()
+            .timers
+            .add(Instant::now() + interval, Some(interval), callback)
+    }
+
+    /// Gets the clipboard and primary selection, which are shared with other programs
+    pub fn clipboard(&mut self) -> Clipboard<'_> {
+        Clipboard::from_parts(self.data)
+    }
+
+    /// Creates a proxy that can send events of type `T` to the event loop from other threads
+    pub fn create_proxy<T: Send + 'static>(&self) -> EventLoopProxy<T> {
+        let core_data = self.get_core_data();
+
+        EventLoopProxy::new(
+            core_data.user_events.clone(),
+            core_data.core_state.get_waker(),
+        )
+    }
+
+    /// Called when an event of type `T` is sent through an [EventLoopProxy]. Events that
+    /// don't have a callback are dropped.
+    pub fn on_user_event<T: Send + 'static, F: FnMut(&mut WWindState<UserData>, T) + 'static>(
+        &mut self,
+        mut closure: F,
+    ) {
+        let callback = Box::new(move |state: &mut WWindState<UserData>, event: UserEvent| {
+            if let Ok(event) = event.downcast::<T>() {
+                closure(state, *event)
+            }
+        }) as Box<OnUserEvent<UserData>>;
+
+        let callback = unsafe { window::into_binding(callback) };
+
+        let old_callback = self
+            .get_core_data_mut()
+            .user_event_handlers
+            .insert(TypeId::of::<T>(), callback);
+
+        if let Some(old_callback) = old_callback {
+            drop(unsafe { window::from_binding::<OnUserEvent<UserData>>(old_callback) })
+        }
+    }
+
+    /// Gets the monitors that are connected. None of them are primary on Wayland, which has no
+    /// primary monitor.
+    pub fn monitors(&self) -> Result<Vec<Monitor>, Error> {
+        let monitors = self.get_core_data().core_state.get_monitors()?;
+
+        Ok(monitors)
+    }
+
+    /// Called when a monitor is connected or disconnected, or when the size, position or
+    /// refresh rate of one changes. [monitors](Self::monitors) then returns the new list.
+    pub fn on_monitors_changed<F: FnMut(&mut WWindState<UserData>) + 'static>(
+        &mut self,
+        closure: F,
+    ) {
+        let callback = Box::new(closure) as Box<OnMonitorsChanged<UserData>>;
+        let callback = unsafe { window::into_binding(callback) };
+
+        let old_callback = self.get_core_data_mut().monitors_changed.replace(callback);
+
+        if let Some(old_callback) = old_callback {
+            drop(unsafe { window::from_binding::<OnMonitorsChanged<UserData>>(old_callback) })
+        }
+    }
+
+    /// Stops a timer. Does nothing if the timer already finished or was cancelled.
+    pub fn cancel_timer(&mut self, handle: TimerHandle) {
+        let Some(timer) = self.get_core_data_mut().timers.remove(handle) else {
+            return;
+        };
+
+        // If the timer is currently running, its callback is dropped once it returns
+        if let Some(callback) = timer.callback {
+            drop(unsafe { window::from_binding::<OnTimer<UserData>>(callback) });
+        }
+    }
 }
 
 impl<U> WWindInitState<U> {
@@ -135,27 +328,42 @@ impl<U> WWindInitState<U> {
         }
     }
 
-    pub(crate) fn new() -> Option<Self> {
+    pub(crate) fn new(backend: Backend) -> Result<Self, Error> {
+        let candidates = backend.candidates()?;
+
         if !STATE_CREATED.fetch_or(true, atomic::Ordering::Acquire) {
-            let core_state = unsafe { CoreStateEnum::new() }.unwrap();
+            let core_state = match unsafe { CoreStateEnum::load(&candidates) } {
+                Ok(core_state) => core_state,
+                Err(err) => {
+                    STATE_CREATED.store(false, atomic::Ordering::Release);
+                    return Err(err.into());
+                }
+            };
             let windows = HashMap::new();
             let windows_to_destroy = Vec::new();
+            let timers = Timers::default();
+            let user_events = Arc::new(UserEventQueue::new());
+            let user_event_handlers = HashMap::new();
 
             let data = CoreStateData {
                 core_state,
                 windows,
                 windows_to_destroy,
+                timers,
+                user_events,
+                user_event_handlers,
+                monitors_changed: None,
             };
 
             let state = Box::new(data);
 
-            Some(Self {
+            Ok(Self {
                 data: Box::into_raw(state),
                 _unsend: PhantomUnsend::default(),
                 _phantomdata: PhantomData,
             })
         } else {
-            None
+            Err(Error::AlreadyCreated)
         }
     }
 
@@ -185,12 +393,30 @@ impl<U> WWindInitState<U> {
         self.get_core_data_mut().windows.remove(&window);
     }
 
-    pub(crate) fn get_window_from_ref(&mut self, window_ref: CoreWindowRef) -> Window<U> {
+    pub(crate) fn get_window_from_ref(&mut self, window_ref: CoreWindowRef) -> Window<'_, U> {
         Window::from_parts(window_ref, self.data)
     }
 
-    pub(crate) unsafe fn destroy(self) {
-        CORE_STATE_TYPE.assume_init_drop();
+    pub(crate) unsafe fn destroy(mut self) {
+        SHOULD_EXIT = false;
+
+        for timer in self.get_core_data_mut().timers.drain() {
+            if let Some(callback) = timer.callback {
+                drop(window::from_binding::<OnTimer<U>>(callback));
+            }
+        }
+
+        self.get_core_data().user_events.close();
+
+        for (_, callback) in self.get_core_data_mut().user_event_handlers.drain() {
+            drop(window::from_binding::<OnUserEvent<U>>(callback));
+        }
+
+        if let Some(callback) = self.get_core_data_mut().monitors_changed.take() {
+            drop(window::from_binding::<OnMonitorsChanged<U>>(callback));
+        }
+
+        (*addr_of_mut!(CORE_STATE_TYPE)).assume_init_drop();
         drop(Box::from_raw(self.data));
 
         if !USERDATA.is_null() {
@@ -203,102 +429,417 @@ impl<U> WWindInitState<U> {
         STATE_CREATED.store(false, atomic::Ordering::Release);
     }
 
-    pub(crate) fn flush(&mut self) {
-        self.get_core_data_mut().core_state.flush().unwrap()
+    pub(crate) fn flush(&mut self) -> Result<(), Error> {
+        self.get_core_data_mut().core_state.flush()?;
+
+        Ok(())
+    }
+
+    /// Calls the redraw handlers of the windows that have damaged regions
+    pub(crate) unsafe fn redraw_damaged_windows(&mut self) -> Result<(), Error> {
+        let damaged_windows: Vec<(CoreWindowRef, RectRegion)> = self
+            .get_core_data_mut()
+            .windows
+            .iter_mut()
+            .filter_map(|(&window_ref, data)| Some((window_ref, data.damaged_region.take()?)))
+            .collect();
+
+        if damaged_windows.is_empty() {
+            return Ok(());
+        }
+
+        for (window_ref, region) in damaged_windows {
+            self.call_binding(
+                window_ref,
+                |data| &mut data.redraw,
+                |closure: &mut OnRedraw<U>, state, window| closure(state, window, region),
+            );
+        }
+
+        self.flush()
+    }
+
+    fn has_damaged_windows(&self) -> bool {
+        self.get_core_data()
+            .windows
+            .values()
+            .any(|data| data.damaged_region.is_some())
     }
 
-    pub(crate) unsafe fn wait_for_events(&mut self) {
+    /// Calls the callbacks of the events that were sent through an [EventLoopProxy]
+    pub(crate) unsafe fn dispatch_user_events(&mut self) -> Result<(), Error> {
+        let events = self.get_core_data().user_events.take();
+
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        for event in events {
+            let type_id = (*event).type_id();
+
+            let Some(callback) = self
+                .get_core_data_mut()
+                .user_event_handlers
+                .remove(&type_id)
+            else {
+                continue;
+            };
+
+            let mut callback = window::from_binding::<OnUserEvent<U>>(callback);
+
+            let mut state = WWindState::from_init(self.clone());
+            callback(&mut state, event);
+
+            // The callback may have been replaced while it was running
+            if let Entry::Vacant(entry) =
+                self.get_core_data_mut().user_event_handlers.entry(type_id)
+            {
+                entry.insert(window::into_binding(callback));
+            }
+        }
+
+        self.flush()
+    }
+
+    /// Calls the callbacks of the timers whose deadline has passed
+    pub(crate) unsafe fn run_due_timers(&mut self) -> Result<(), Error> {
+        let now = Instant::now();
+
+        for handle in self.get_core_data().timers.get_due(now) {
+            // The timer may have been cancelled by an earlier callback
+            let Some(timer) = self.get_core_data_mut().timers.get_mut(handle) else {
+                continue;
+            };
+
+            let Some(callback) = timer.callback.take() else {
+                continue;
+            };
+
+            let mut callback = window::from_binding::<OnTimer<U>>(callback);
+
+            let mut state = WWindState::from_init(self.clone());
+            callback(&mut state);
+
+            let timers = &mut self.get_core_data_mut().timers;
+
+            match timers.get_mut(handle) {
+                Some(Timer {
+                    interval: Some(interval),
+                    deadline,
+                    callback: callback_slot,
+                    ..
+                }) => {
+                    let next_deadline = *deadline + *interval;
+
+                    // Intervals that fell behind are skipped instead of running in a burst
+                    *deadline = if next_deadline <= now {
+                        now + *interval
+                    } else {
+                        next_deadline
+                    };
+                    *callback_slot = Some(window::into_binding(callback));
+                }
+                Some(_) => {
+                    timers.remove(handle);
+                }
+                // Cancelled while it was running
+                None => {}
+            }
+        }
+
+        self.flush()
+    }
+
+    /// Blocks until an event is handled, the next timer is due or `timeout` runs out. Returns
+    /// whether an event was handled.
+    pub(crate) unsafe fn wait_for_events(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<bool, Error> {
         static mut STATE: MaybeUninit<WWindInitState> = MaybeUninit::uninit();
 
         STATE = MaybeUninit::new(std::mem::transmute::<WWindInitState<U>, WWindInitState>(
             self.clone(),
         ));
 
+        let timer_timeout = self
+            .get_core_data()
+            .timers
+            .next_deadline()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+        // Damaged windows are redrawn right after this, so there's no time to wait
+        let timeout = if self.has_damaged_windows() {
+            Some(Duration::ZERO)
+        } else {
+            timeout
+        };
+
+        let timeout = match (timeout, timer_timeout) {
+            (Some(timeout), Some(timer_timeout)) => Some(timeout.min(timer_timeout)),
+            (timeout, timer_timeout) => timeout.or(timer_timeout),
+        };
+
         let core_state = &mut self.get_core_data_mut().core_state;
 
-        core_state.wait_for_events(&mut (on_event::<U> as unsafe fn(WWindCoreEvent)));
+        let handled =
+            core_state.wait_for_events(&mut (on_event::<U> as unsafe fn(WWindCoreEvent)), timeout);
 
         unsafe fn on_event<U>(event: WWindCoreEvent) {
-            let mut state = STATE.assume_init_mut().clone().with_data();
+            let mut state = (*addr_of_mut!(STATE)).assume_init_mut().clone().with_data();
 
             match event {
                 WWindCoreEvent::CloseWindow(window_ref) => {
                     if let Some(window_data) =
                         state.get_core_data_mut().windows.get_mut(&window_ref)
                     {
-                        let mut closure = if let Some(closure) = window_data.on_close.take() {
-                            mem::transmute::<[usize; 2], Box<OnClose<U>>>(closure)
-                        } else {
+                        if window_data.on_close.is_none() {
                             let mut window = state.get_window_from_ref(window_ref);
                             window.schedule_window_destruction();
                             return;
-                        };
-
-                        let mut state_clone = WWindState::from_init(state.clone());
-                        let mut window = state.get_window_from_ref(window_ref);
-
-                        closure(&mut state_clone, &mut window);
+                        }
 
-                        state
-                            .get_core_data_mut()
-                            .windows
-                            .get_mut(&window_ref)
-                            .map(|data| data.on_close.insert(mem::transmute(closure)));
+                        state.call_binding(
+                            window_ref,
+                            |data| &mut data.on_close,
+                            |closure: &mut OnClose<U>, state, window| closure(state, window),
+                        );
                     } else {
                         println!("CloseWindow called on non-existant window");
                     }
                 }
                 WWindCoreEvent::Expose(window_ref, region) => {
+                    // Merged with other damage and redrawn at the end of the loop iteration
                     if let Some(window_data) =
                         state.get_core_data_mut().windows.get_mut(&window_ref)
                     {
-                        let mut closure = if let Some(closure) = window_data.redraw.take() {
-                            mem::transmute::<[usize; 2], Box<OnRedraw<U>>>(closure)
-                        } else {
-                            return;
-                        };
-
-                        let mut state_clone = WWindState::from_init(state.clone());
-                        let mut window = state.get_window_from_ref(window_ref);
-
-                        closure(&mut state_clone, &mut window, region);
-
-                        state
-                            .get_core_data_mut()
-                            .windows
-                            .get_mut(&window_ref)
-                            .map(|data| data.redraw.insert(mem::transmute(closure)));
-                    } else {
-                        println!("CloseWindow called on non-existant window");
+                        window_data.invalidate(region);
                     }
                 }
-                WWindCoreEvent::Keydown(window_ref, keycode) => {
-                    if let Some(window_data) =
-                        state.get_core_data_mut().windows.get_mut(&window_ref)
-                    {
-                        let mut closure = if let Some(closure) = window_data.keydown.take() {
-                            mem::transmute::<[usize; 2], Box<OnKeydown<U>>>(closure)
-                        } else {
-                            return;
-                        };
+                WWindCoreEvent::Resize(window_ref, width, height) => {
+                    let changed = state
+                        .get_core_data_mut()
+                        .windows
+                        .get_mut(&window_ref)
+                        .is_some_and(|data| {
+                            let changed = (data.width, data.height) != (width, height);
+
+                            data.width = width;
+                            data.height = height;
+
+                            changed
+                        });
+
+                    if changed {
+                        state.call_binding(
+                            window_ref,
+                            |data| &mut data.resize,
+                            |closure: &mut OnResize<U>, state, window| {
+                                closure(state, window, width, height)
+                            },
+                        );
+                    }
+                }
+                WWindCoreEvent::Move(window_ref, x, y) => {
+                    let changed = state
+                        .get_core_data_mut()
+                        .windows
+                        .get_mut(&window_ref)
+                        .is_some_and(|data| {
+                            let changed = (data.x, data.y) != (x, y);
+
+                            data.x = x;
+                            data.y = y;
+
+                            changed
+                        });
+
+                    if changed {
+                        state.call_binding(
+                            window_ref,
+                            |data| &mut data.on_move,
+                            |closure: &mut OnMove<U>, state, window| closure(state, window, x, y),
+                        );
+                    }
+                }
+                WWindCoreEvent::Keydown(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.keydown,
+                        |closure: &mut OnKey<U>, state, window| closure(state, window, event),
+                    );
+                }
+                WWindCoreEvent::Keyup(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.keyup,
+                        |closure: &mut OnKey<U>, state, window| closure(state, window, event),
+                    );
+                }
+                WWindCoreEvent::TextInput(window_ref, character) => {
+                    let mut buffer = [0; 4];
+                    let text = character.encode_utf8(&mut buffer);
+
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.text_input,
+                        |closure: &mut OnTextInput<U>, state, window| closure(state, window, text),
+                    );
+                }
+                WWindCoreEvent::MouseDown(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.mouse_down,
+                        |closure: &mut OnMouseButton<U>, state, window| {
+                            closure(state, window, event)
+                        },
+                    );
+                }
+                WWindCoreEvent::MouseUp(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.mouse_up,
+                        |closure: &mut OnMouseButton<U>, state, window| {
+                            closure(state, window, event)
+                        },
+                    );
+                }
+                WWindCoreEvent::MouseMove(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.mouse_move,
+                        |closure: &mut OnMouseMove<U>, state, window| closure(state, window, event),
+                    );
+                }
+                WWindCoreEvent::Scroll(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.scroll,
+                        |closure: &mut OnScroll<U>, state, window| closure(state, window, event),
+                    );
+                }
+                WWindCoreEvent::FocusChange(window_ref, focused) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.focus_change,
+                        |closure: &mut OnFocusChange<U>, state, window| {
+                            closure(state, window, focused)
+                        },
+                    );
+                }
+                WWindCoreEvent::PointerEnter(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.pointer_enter,
+                        |closure: &mut OnMouseMove<U>, state, window| closure(state, window, event),
+                    );
+                }
+                WWindCoreEvent::PointerLeave(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.pointer_leave,
+                        |closure: &mut OnMouseMove<U>, state, window| closure(state, window, event),
+                    );
+                }
+                WWindCoreEvent::StateChange(window_ref, window_state) => {
+                    let changed = state
+                        .get_core_data_mut()
+                        .windows
+                        .get_mut(&window_ref)
+                        .is_some_and(|data| {
+                            mem::replace(&mut data.state, window_state) != window_state
+                        });
+
+                    if changed {
+                        state.call_binding(
+                            window_ref,
+                            |data| &mut data.state_change,
+                            |closure: &mut OnStateChange<U>, state, window| {
+                                closure(state, window, window_state)
+                            },
+                        );
+                    }
+                }
+                WWindCoreEvent::DragOver(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.drag_over,
+                        |closure: &mut OnDrop<U>, state, window| closure(state, window, &event),
+                    );
+                }
+                WWindCoreEvent::DragLeave(window_ref) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.drag_leave,
+                        |closure: &mut OnDragLeave<U>, state, window| closure(state, window),
+                    );
+                }
+                WWindCoreEvent::DragDrop(window_ref, event) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.on_drop,
+                        |closure: &mut OnDrop<U>, state, window| closure(state, window, &event),
+                    );
+                }
+                WWindCoreEvent::DragEnd(window_ref, action) => {
+                    state.call_binding(
+                        window_ref,
+                        |data| &mut data.drag_end,
+                        |closure: &mut OnDragEnd<U>, state, window| closure(state, window, action),
+                    );
+                }
+                WWindCoreEvent::MonitorsChanged => {
+                    if let Some(callback) = state.get_core_data_mut().monitors_changed.take() {
+                        let mut callback = window::from_binding::<OnMonitorsChanged<U>>(callback);
 
                         let mut state_clone = WWindState::from_init(state.clone());
-                        let mut window = state.get_window_from_ref(window_ref);
-
-                        closure(&mut state_clone, &mut window, keycode);
+                        callback(&mut state_clone);
 
-                        state
-                            .get_core_data_mut()
-                            .windows
-                            .get_mut(&window_ref)
-                            .map(|data| data.keydown.insert(mem::transmute(closure)));
-                    } else {
-                        println!("CloseWindow called on non-existant window");
+                        // The callback may have been replaced while it was running
+                        let slot = &mut state.get_core_data_mut().monitors_changed;
+                        if slot.is_none() {
+                            *slot = Some(window::into_binding(callback));
+                        }
                     }
                 }
             }
 
-            state.flush();
+            // Connection errors are reported by the next wait_for_events call
+            let _ = state.flush();
+        }
+
+        Ok(handled?)
+    }
+
+    /// Temporarily takes one of a window's event handlers and calls it. If the handler was
+    /// not replaced while it was running, it is put back afterwards.
+    unsafe fn call_binding<F: ?Sized>(
+        &mut self,
+        window_ref: CoreWindowRef,
+        binding: fn(&mut WindowData) -> &mut Option<Binding>,
+        call: impl FnOnce(&mut F, &mut WWindState<U>, &mut Window<U>),
+    ) {
+        let Some(window_data) = self.get_core_data_mut().windows.get_mut(&window_ref) else {
+            return;
+        };
+
+        let Some(raw_closure) = binding(window_data).take() else {
+            return;
+        };
+
+        let mut closure = window::from_binding::<F>(raw_closure);
+
+        let mut state_clone = WWindState::from_init(self.clone());
+        let mut window = self.get_window_from_ref(window_ref);
+
+        call(&mut closure, &mut state_clone, &mut window);
+
+        if let Some(window_data) = self.get_core_data_mut().windows.get_mut(&window_ref) {
+            let binding = binding(window_data);
+
+            if binding.is_none() {
+                *binding = Some(window::into_binding(closure));
+            }
         }
     }
 }
@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant};
+
+use crate::{window::Binding, WWindState};
+
+pub type OnTimer<UserData> = dyn FnMut(&mut WWindState<UserData>) + 'static;
+
+/// Identifies a timer created with [set_timeout](crate::WWindInitState::set_timeout) or
+/// [set_interval](crate::WWindInitState::set_interval). It can be passed to
+/// [cancel_timer](crate::WWindInitState::cancel_timer) to stop the timer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimerHandle(u64);
+
+pub(crate) struct Timer {
+    pub handle: TimerHandle,
+    pub deadline: Instant,
+    /// `None` if the timer only runs once
+    pub interval: Option<Duration>,
+    /// `None` while the callback is running
+    pub callback: Option<Binding>,
+}
+
+#[derive(Default)]
+pub(crate) struct Timers {
+    timers: Vec<Timer>,
+    next_id: u64,
+}
+
+impl Timers {
+    pub fn add(
+        &mut self,
+        deadline: Instant,
+        interval: Option<Duration>,
+        callback: Binding,
+    ) -> TimerHandle {
+        let handle = TimerHandle(self.next_id);
+        self.next_id += 1;
+
+        self.timers.push(Timer {
+            handle,
+            deadline,
+            interval,
+            callback: Some(callback),
+        });
+
+        handle
+    }
+
+    pub fn remove(&mut self, handle: TimerHandle) -> Option<Timer> {
+        let index = self
+            .timers
+            .iter()
+            .position(|timer| timer.handle == handle)?;
+
+        Some(self.timers.swap_remove(index))
+    }
+
+    pub fn get_mut(&mut self, handle: TimerHandle) -> Option<&mut Timer> {
+        self.timers.iter_mut().find(|timer| timer.handle == handle)
+    }
+
+    /// Gets the earliest deadline of a timer that isn't currently running
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.timers
+            .iter()
+            .filter(|timer| timer.callback.is_some())
+            .map(|timer| timer.deadline)
+            .min()
+    }
+
+    /// Gets the timers that should run now, in the order that they should run in
+    pub fn get_due(&self, now: Instant) -> Vec<TimerHandle> {
+        let mut due: Vec<&Timer> = self
+            .timers
+            .iter()
+            .filter(|timer| timer.callback.is_some() && timer.deadline <= now)
+            .collect();
+
+        due.sort_by_key(|timer| (timer.deadline, timer.handle.0));
+
+        due.into_iter().map(|timer| timer.handle).collect()
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = Timer> + '_ {
+        self.timers.drain(..)
+    }
+}
@@ -1,32 +1,226 @@
-use std::{ffi::c_void, marker::PhantomData, mem};
+use std::{
+    marker::PhantomData,
+    mem,
+    ops::{BitAnd, BitOr, BitOrAssign},
+};
+
+#[cfg(raw_window_handle)]
+use raw_window_handle::{
+    DisplayHandle, HandleError, HasDisplayHandle, HasWindowHandle, WindowHandle,
+};
 
 use crate::{
     core::{CoreStateImplementation, CoreWindowRef},
+    input::{KeyEvent, MouseButtonEvent, MouseMoveEvent, ScrollEvent},
     state::CoreStateData,
     util::PhantomUnsend,
-    DrawingContext, RectRegion, WWindInitState, WWindState,
+    CursorIcon, DragAction, DrawingContext, DropEvent, DropPayload, Error, RectRegion, RgbaImage,
+    WWindState,
 };
 
 pub type OnClose<UserData> = dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>) + 'static;
 pub type OnRedraw<UserData> =
     dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, RectRegion) + 'static;
-pub type OnKeydown<UserData> =
-    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, u32) + 'static;
+pub type OnResize<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, u16, u16) + 'static;
+pub type OnMove<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, i16, i16) + 'static;
+pub type OnFocusChange<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, bool) + 'static;
+pub type OnKey<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, KeyEvent) + 'static;
+pub type OnTextInput<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, &str) + 'static;
+pub type OnMouseButton<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, MouseButtonEvent) + 'static;
+pub type OnMouseMove<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, MouseMoveEvent) + 'static;
+pub type OnScroll<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, ScrollEvent) + 'static;
+pub type OnStateChange<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, WindowStateFlags) + 'static;
+pub type OnDrop<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, &DropEvent) + 'static;
+pub type OnDragLeave<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>) + 'static;
+pub type OnDragEnd<UserData> =
+    dyn FnMut(&mut WWindState<UserData>, &mut Window<UserData>, Option<DragAction>) + 'static;
+
+/// A type-erased `Box<dyn FnMut(..)>`
+pub type Binding = [usize; 2];
+
+/// The edge or corner of a window that is dragged in [Window::start_resize]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResizeEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// What the window manager is currently doing with a window. A window can be in several of
+/// these states at once, such as maximized and minimized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WindowStateFlags(u8);
+
+impl WindowStateFlags {
+    pub const NONE: Self = Self(0);
+    pub const MAXIMIZED: Self = Self(1);
+    /// Minimized, iconified or otherwise hidden by the window manager
+    pub const MINIMIZED: Self = Self(1 << 1);
+    pub const FULLSCREEN: Self = Self(1 << 2);
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0
+    }
+
+    /// Inserts or removes `other`
+    pub fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.insert(other)
+        } else {
+            self.remove(other)
+        }
+    }
+}
+
+impl BitOr for WindowStateFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for WindowStateFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs)
+    }
+}
+
+impl BitAnd for WindowStateFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
 
 pub struct WindowData {
-    pub on_close: Option<[usize; 2]>,
-    pub redraw: Option<[usize; 2]>,
-    pub keydown: Option<[usize; 2]>,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+
+    pub on_close: Option<Binding>,
+    pub redraw: Option<Binding>,
+    pub resize: Option<Binding>,
+    pub on_move: Option<Binding>,
+    pub keydown: Option<Binding>,
+    pub keyup: Option<Binding>,
+    pub text_input: Option<Binding>,
+    pub mouse_down: Option<Binding>,
+    pub mouse_up: Option<Binding>,
+    pub mouse_move: Option<Binding>,
+    pub scroll: Option<Binding>,
+    pub focus_change: Option<Binding>,
+    pub pointer_enter: Option<Binding>,
+    pub pointer_leave: Option<Binding>,
+    pub state_change: Option<Binding>,
+    pub drag_over: Option<Binding>,
+    pub drag_leave: Option<Binding>,
+    pub on_drop: Option<Binding>,
+    pub drag_end: Option<Binding>,
+
+    /// The last state that the window manager reported
+    pub state: WindowStateFlags,
+    /// The region that needs to be redrawn at the end of this loop iteration
+    pub damaged_region: Option<RectRegion>,
 }
 
 impl WindowData {
-    pub fn new(_width: u16, _height: u16) -> Self {
+    pub fn new(x: i16, y: i16, width: u16, height: u16) -> Self {
         Self {
+            x,
+            y,
+            width,
+            height,
             on_close: None,
             redraw: None,
+            resize: None,
+            on_move: None,
             keydown: None,
+            keyup: None,
+            text_input: None,
+            mouse_down: None,
+            mouse_up: None,
+            mouse_move: None,
+            scroll: None,
+            focus_change: None,
+            pointer_enter: None,
+            pointer_leave: None,
+            state_change: None,
+            drag_over: None,
+            drag_leave: None,
+            on_drop: None,
+            drag_end: None,
+            state: WindowStateFlags::NONE,
+            damaged_region: None,
         }
     }
+
+    /// Adds a region to the region that will be redrawn. Parts outside of the window are
+    /// ignored.
+    pub fn invalidate(&mut self, region: RectRegion) {
+        let window_region = RectRegion {
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+        };
+
+        let Some(region) = region.intersection(&window_region) else {
+            return;
+        };
+
+        self.damaged_region = Some(match self.damaged_region {
+            Some(damaged_region) => damaged_region.union(&region),
+            None => region,
+        });
+    }
+}
+
+/// Converts a boxed closure into a [Binding]
+/// ## Safety
+/// `F` must be a `dyn` type
+pub(crate) unsafe fn into_binding<F: ?Sized>(closure: Box<F>) -> Binding {
+    let binding = mem::transmute_copy::<Box<F>, Binding>(&closure);
+    mem::forget(closure);
+
+    binding
+}
+
+/// Converts a [Binding] back into the boxed closure it was created from
+/// ## Safety
+/// `binding` must have been created by [into_binding] with the same `F`
+pub(crate) unsafe fn from_binding<F: ?Sized>(binding: Binding) -> Box<F> {
+    mem::transmute_copy::<Binding, Box<F>>(&binding)
 }
 
 #[repr(C)]
@@ -38,8 +232,27 @@ pub struct Window<'a, UserData = ()> {
 }
 
 impl<'a, UserData> Window<'a, UserData> {
-    pub(crate) unsafe fn with_data<V>(self) -> Window<'a, V> {
-        mem::transmute(self)
+    /// Schedules the whole window to be redrawn. See [invalidate](Self::invalidate).
+    pub fn request_redraw(&mut self) {
+        let (width, height) = self.get_size();
+
+        self.invalidate(RectRegion {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        });
+    }
+
+    /// Schedules part of the window to be redrawn. The [on_redraw](Self::on_redraw) handler
+    /// is called at the end of the current loop iteration, once for all of the regions that
+    /// were invalidated, with a region that contains all of them.
+    pub fn invalidate(&mut self, region: RectRegion) {
+        let window_ref = self.window_ref;
+
+        if let Some(window_data) = self.get_core_data_mut().windows.get_mut(&window_ref) {
+            window_data.invalidate(region);
+        }
     }
 
     pub fn schedule_window_destruction(&mut self) {
@@ -55,51 +268,277 @@ impl<'a, UserData> Window<'a, UserData> {
         &mut self,
         closure: F,
     ) {
-        let window_ref = self.window_ref;
-
-        if let Some(window_data) = self.get_core_data_mut().windows.get_mut(&window_ref) {
-            if let Some(old_binding) = window_data.on_close {
-                drop(unsafe { mem::transmute::<[usize; 2], Box<OnClose<UserData>>>(old_binding) })
-            }
-
-            window_data.on_close =
-                Some(unsafe { mem::transmute(Box::new(closure) as Box<OnClose<UserData>>) });
-        }
+        self.set_binding(
+            |data| &mut data.on_close,
+            Box::new(closure) as Box<OnClose<UserData>>,
+        )
     }
 
+    /// Called when part of the window needs to be redrawn, either because the window system
+    /// asked for it or because of [request_redraw](Self::request_redraw) or
+    /// [invalidate](Self::invalidate)
     pub fn on_redraw<
         F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, RectRegion) + 'static,
     >(
         &mut self,
         closure: F,
     ) {
-        let window_ref = self.window_ref;
+        self.set_binding(
+            |data| &mut data.redraw,
+            Box::new(closure) as Box<OnRedraw<UserData>>,
+        )
+    }
 
-        if let Some(window_data) = self.get_core_data_mut().windows.get_mut(&window_ref) {
-            if let Some(old_binding) = window_data.redraw {
-                drop(unsafe { mem::transmute::<[usize; 2], Box<OnRedraw<UserData>>>(old_binding) })
-            }
+    /// Called when the window's size changes. The new width and height are passed in.
+    pub fn on_resize<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, u16, u16) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.resize,
+            Box::new(closure) as Box<OnResize<UserData>>,
+        )
+    }
 
-            window_data.redraw =
-                Some(unsafe { mem::transmute(Box::new(closure) as Box<OnRedraw<UserData>>) });
-        }
+    /// Called when the window is moved. The new position of the window on the screen is
+    /// passed in.
+    pub fn on_move<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, i16, i16) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.on_move,
+            Box::new(closure) as Box<OnMove<UserData>>,
+        )
     }
 
-    pub fn on_keydown<F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, u32) + 'static>(
+    /// Called when a key is pressed while the window has keyboard focus. This is also called
+    /// for auto-repeated presses, which can be told apart with [KeyEvent::repeat].
+    pub fn on_keydown<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, KeyEvent) + 'static,
+    >(
         &mut self,
         closure: F,
     ) {
-        let window_ref = self.window_ref;
+        self.set_binding(
+            |data| &mut data.keydown,
+            Box::new(closure) as Box<OnKey<UserData>>,
+        )
+    }
 
-        if let Some(window_data) = self.get_core_data_mut().windows.get_mut(&window_ref) {
-            if let Some(old_binding) = window_data.keydown {
-                drop(unsafe { mem::transmute::<[usize; 2], Box<OnKeydown<UserData>>>(old_binding) })
-            }
+    /// Called when a key is released while the window has keyboard focus
+    pub fn on_keyup<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, KeyEvent) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.keyup,
+            Box::new(closure) as Box<OnKey<UserData>>,
+        )
+    }
 
-            window_data.keydown =
-                Some(unsafe { mem::transmute(Box::new(closure) as Box<OnKeydown<UserData>>) });
-        }
+    /// Called when text is typed into the window. Unlike [Window::on_keydown], this takes the
+    /// keyboard layout, shift levels and dead keys into account. Control characters (such as
+    /// the ones produced by enter and backspace) are not reported here.
+    pub fn on_text_input<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, &str) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.text_input,
+            Box::new(closure) as Box<OnTextInput<UserData>>,
+        )
+    }
+
+    /// Called when a mouse button is pressed while the pointer is over the window
+    pub fn on_mouse_down<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, MouseButtonEvent) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.mouse_down,
+            Box::new(closure) as Box<OnMouseButton<UserData>>,
+        )
+    }
+
+    /// Called when a mouse button that was pressed over the window is released
+    pub fn on_mouse_up<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, MouseButtonEvent) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.mouse_up,
+            Box::new(closure) as Box<OnMouseButton<UserData>>,
+        )
+    }
+
+    /// Called when the pointer moves over the window
+    pub fn on_mouse_move<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, MouseMoveEvent) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.mouse_move,
+            Box::new(closure) as Box<OnMouseMove<UserData>>,
+        )
+    }
+
+    /// Called when the user scrolls over the window
+    pub fn on_scroll<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, ScrollEvent) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.scroll,
+            Box::new(closure) as Box<OnScroll<UserData>>,
+        )
     }
+
+    /// Called when the window gains (`true`) or loses (`false`) keyboard focus
+    pub fn on_focus_change<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, bool) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.focus_change,
+            Box::new(closure) as Box<OnFocusChange<UserData>>,
+        )
+    }
+
+    /// Called when the pointer enters the window
+    pub fn on_pointer_enter<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, MouseMoveEvent) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.pointer_enter,
+            Box::new(closure) as Box<OnMouseMove<UserData>>,
+        )
+    }
+
+    /// Called when the pointer leaves the window
+    pub fn on_pointer_leave<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, MouseMoveEvent) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.pointer_leave,
+            Box::new(closure) as Box<OnMouseMove<UserData>>,
+        )
+    }
+
+    /// Called when the window is maximized, minimized or made fullscreen, or when it stops
+    /// being one of those. This includes changes made by the user through the window manager.
+    pub fn on_state_change<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, WindowStateFlags) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.state_change,
+            Box::new(closure) as Box<OnStateChange<UserData>>,
+        )
+    }
+
+    /// Called when something is dropped on the window. Setting this, or any of the other drag
+    /// callbacks, lets things be dropped on the window.
+    ///
+    /// Only files can be dropped on Win32 windows, and nothing can be dropped on Wayland windows
+    /// yet.
+    pub fn on_drop<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, &DropEvent) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.accept_drops();
+        self.set_binding(
+            |data| &mut data.on_drop,
+            Box::new(closure) as Box<OnDrop<UserData>>,
+        )
+    }
+
+    /// Called when something is dragged into the window and whenever it moves inside of it.
+    ///
+    /// Never called on Win32, which only reports the drop, or on Wayland, where nothing can be
+    /// dropped yet.
+    pub fn on_drag_over<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, &DropEvent) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.accept_drops();
+        self.set_binding(
+            |data| &mut data.drag_over,
+            Box::new(closure) as Box<OnDrop<UserData>>,
+        )
+    }
+
+    /// Called when something that was dragged over the window leaves it without being dropped.
+    ///
+    /// Never called on Win32 or Wayland, for the same reasons as
+    /// [on_drag_over](Self::on_drag_over).
+    pub fn on_drag_leave<F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>) + 'static>(
+        &mut self,
+        closure: F,
+    ) {
+        self.accept_drops();
+        self.set_binding(
+            |data| &mut data.drag_leave,
+            Box::new(closure) as Box<OnDragLeave<UserData>>,
+        )
+    }
+
+    /// Called when a drag that was started with [start_drag](Self::start_drag) is over, with
+    /// what the drop target did with the data. `None` means that it wasn't dropped anywhere, or
+    /// that the target refused it.
+    ///
+    /// Never called on Win32 or Wayland, where [start_drag](Self::start_drag) fails with
+    /// [Error::Unsupported].
+    pub fn on_drag_end<
+        F: FnMut(&mut WWindState<UserData>, &mut Window<UserData>, Option<DragAction>) + 'static,
+    >(
+        &mut self,
+        closure: F,
+    ) {
+        self.set_binding(
+            |data| &mut data.drag_end,
+            Box::new(closure) as Box<OnDragEnd<UserData>>,
+        )
+    }
+
+    fn accept_drops(&mut self) {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .accept_window_drops(window_ref);
+    }
+
     pub fn get_drawing_context(&mut self) -> DrawingContext<'_> {
         let window_ref = self.window_ref;
 
@@ -108,10 +547,284 @@ impl<'a, UserData> Window<'a, UserData> {
         DrawingContext::from_parts(context, self.data)
     }
 
+    /// Gets the last known width and height of the window
     pub fn get_size(&self) -> (u16, u16) {
+        let window_data = self.get_window_data();
+
+        (window_data.width, window_data.height)
+    }
+
+    /// Gets the last known position of the window on the screen
+    pub fn get_position(&self) -> (i16, i16) {
+        let window_data = self.get_window_data();
+
+        (window_data.x, window_data.y)
+    }
+
+    pub fn set_title(&mut self, title: &str) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_title(window_ref, title)?;
+
+        Ok(())
+    }
+
+    /// Moves the window. [on_move](Self::on_move) is called once the window system has moved
+    /// it. This does nothing on Wayland, where windows can't position themselves.
+    pub fn set_position(&mut self, x: i16, y: i16) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_position(window_ref, x, y)?;
+
+        Ok(())
+    }
+
+    /// Resizes the window's contents. [on_resize](Self::on_resize) is called once the window
+    /// system has resized it, and [get_size](Self::get_size) changes then as well.
+    pub fn set_size(&mut self, width: u16, height: u16) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_size(window_ref, width, height)?;
+
+        Ok(())
+    }
+
+    /// Shows or hides the window. Hidden windows still exist and keep their event handlers.
+    pub fn set_visible(&mut self, visible: bool) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_visible(window_ref, visible)?;
+
+        Ok(())
+    }
+
+    /// Asks for the window to get keyboard focus. The window manager may refuse, and Wayland
+    /// doesn't allow it at all.
+    pub fn focus(&mut self) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .focus_window(window_ref)?;
+
+        Ok(())
+    }
+
+    /// Puts the window above the other windows. This does nothing on Wayland.
+    pub fn raise(&mut self) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .raise_window(window_ref)?;
+
+        Ok(())
+    }
+
+    /// Asks the window manager to make the window cover the whole screen, or to stop doing so.
+    /// [on_state_change](Self::on_state_change) is called once it has.
+    pub fn set_fullscreen(&mut self, fullscreen: bool) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_fullscreen(window_ref, fullscreen)?;
+
+        Ok(())
+    }
+
+    /// Asks the window manager to maximize or restore the window.
+    /// [on_state_change](Self::on_state_change) is called once it has.
+    pub fn set_maximized(&mut self, maximized: bool) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_maximized(window_ref, maximized)?;
+
+        Ok(())
+    }
+
+    /// Asks the window manager to minimize the window. It is restored by the user, or with
+    /// [set_visible](Self::set_visible) or [focus](Self::focus) on some window managers.
+    pub fn minimize(&mut self) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .minimize_window(window_ref)?;
+
+        Ok(())
+    }
+
+    /// Sets the icon that taskbars and window switchers show for the window. Several sizes of
+    /// the same icon can be given, and the window system picks the one that fits best. An
+    /// empty slice goes back to the default icon. This does nothing on Wayland, where the icon
+    /// is found through the [class](crate::WindowBuilder::class) instead.
+    pub fn set_icon(&mut self, icons: &[RgbaImage]) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_icon(window_ref, icons)?;
+
+        Ok(())
+    }
+
+    /// Sets the shape of the mouse cursor while it is over the window. On Wayland, this needs a
+    /// compositor that supports cursor-shape-v1.
+    pub fn set_cursor(&mut self, cursor: CursorIcon) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_cursor(window_ref, cursor)?;
+
+        Ok(())
+    }
+
+    /// Uses an image as the mouse cursor while it is over the window. The hotspot is the pixel
+    /// that the pointer points at, and it is moved inside of the image if it isn't already.
+    /// Fails with [Error::InvalidImage] if the image is empty.
+    pub fn set_custom_cursor(
+        &mut self,
+        image: &RgbaImage,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<(), Error> {
+        if image.width() == 0 || image.height() == 0 {
+            return Err(Error::InvalidImage);
+        }
+
+        let hotspot = (
+            hotspot_x.min(image.width() - 1),
+            hotspot_y.min(image.height() - 1),
+        );
+
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_custom_cursor(window_ref, image, hotspot)?;
+
+        Ok(())
+    }
+
+    /// Sets whether the window manager draws a title bar and border around the window. Windows
+    /// without them can draw their own and use [start_move](Self::start_move) and
+    /// [start_resize](Self::start_resize). Wayland windows never have decorations.
+    pub fn set_decorations(&mut self, decorations: bool) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .set_window_decorations(window_ref, decorations)?;
+
+        Ok(())
+    }
+
+    /// Lets the window manager move the window with the pointer until the mouse button is
+    /// released. This should be called from [on_mouse_down](Self::on_mouse_down), such as when
+    /// a custom title bar is pressed. On Wayland, this does nothing unless a mouse button was
+    /// pressed over the window.
+    pub fn start_move(&mut self) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .start_window_drag(window_ref, None)?;
+
+        Ok(())
+    }
+
+    /// Like [start_move](Self::start_move), but resizes the window by dragging `edge`
+    pub fn start_resize(&mut self, edge: ResizeEdge) -> Result<(), Error> {
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut()
+            .core_state
+            .start_window_drag(window_ref, Some(edge))?;
+
+        Ok(())
+    }
+
+    /// Starts dragging data out of the window, so that it can be dropped on another program or
+    /// on one of the windows of this one. This should be called from
+    /// [on_mouse_down](Self::on_mouse_down) or [on_mouse_move](Self::on_mouse_move), and
+    /// nothing happens if no mouse button is held. The data is dropped when the button is
+    /// released, and Escape cancels the drag.
+    ///
+    /// Each payload is a form that the data is offered in, and the target picks the one that
+    /// suits it. Only the first payload of each kind is used. The target is asked to copy the
+    /// data, or to move it while Shift is held, and [on_drag_end](Self::on_drag_end) is called
+    /// with what it did.
+    ///
+    /// Fails with [Error::Unsupported] on Win32 and Wayland, which can't drag data out of
+    /// windows yet.
+    pub fn start_drag(&mut self, payloads: &[DropPayload]) -> Result<(), Error> {
         let window_ref = self.window_ref;
 
-        self.get_core_data().core_state.get_size(window_ref)
+        self.get_core_data_mut()
+            .core_state
+            .start_data_drag(window_ref, payloads, None)?;
+
+        Ok(())
+    }
+
+    /// Like [start_drag](Self::start_drag), but shows an image under the pointer while
+    /// dragging, such as a picture of the dragged items. The hotspot is the pixel of the image
+    /// that the pointer is at. Fails with [Error::InvalidImage] if the image is empty.
+    pub fn start_drag_with_image(
+        &mut self,
+        payloads: &[DropPayload],
+        image: &RgbaImage,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<(), Error> {
+        if image.width() == 0 || image.height() == 0 {
+            return Err(Error::InvalidImage);
+        }
+
+        let hotspot = (
+            hotspot_x.min(image.width() - 1),
+            hotspot_y.min(image.height() - 1),
+        );
+
+        let window_ref = self.window_ref;
+
+        self.get_core_data_mut().core_state.start_data_drag(
+            window_ref,
+            payloads,
+            Some((image, hotspot)),
+        )?;
+
+        Ok(())
+    }
+
+    /// Gets the last state that the window manager reported
+    pub fn get_state(&self) -> WindowStateFlags {
+        self.get_window_data().state
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.get_state().contains(WindowStateFlags::FULLSCREEN)
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        self.get_state().contains(WindowStateFlags::MAXIMIZED)
+    }
+
+    /// Always `false` on Wayland, where compositors don't tell windows that they are minimized
+    pub fn is_minimized(&self) -> bool {
+        self.get_state().contains(WindowStateFlags::MINIMIZED)
     }
 }
 
@@ -130,4 +843,50 @@ impl<UserData> Window<'_, UserData> {
     pub(crate) fn get_core_data(&self) -> &CoreStateData {
         unsafe { &*self.data }
     }
+
+    fn get_window_data(&self) -> &WindowData {
+        &self.get_core_data().windows[&self.window_ref]
+    }
+
+    /// Replaces one of the window's event handlers, dropping the old one
+    fn set_binding<F: ?Sized>(
+        &mut self,
+        binding: fn(&mut WindowData) -> &mut Option<Binding>,
+        closure: Box<F>,
+    ) {
+        let window_ref = self.window_ref;
+
+        if let Some(window_data) = self.get_core_data_mut().windows.get_mut(&window_ref) {
+            let binding = binding(window_data);
+
+            if let Some(old_binding) = binding.take() {
+                drop(unsafe { from_binding::<F>(old_binding) })
+            }
+
+            *binding = Some(unsafe { into_binding(closure) });
+        }
+    }
+}
+
+/// Lets libraries like softbuffer, wgpu or glutin draw into the window. The handle stays valid
+/// until the window is destroyed.
+#[cfg(raw_window_handle)]
+impl<UserData> HasWindowHandle for Window<'_, UserData> {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        let raw = self
+            .get_core_data()
+            .core_state
+            .raw_window_handle(self.window_ref)?;
+
+        Ok(unsafe { WindowHandle::borrow_raw(raw) })
+    }
+}
+
+#[cfg(raw_window_handle)]
+impl<UserData> HasDisplayHandle for Window<'_, UserData> {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        let raw = self.get_core_data().core_state.raw_display_handle()?;
+
+        Ok(unsafe { DisplayHandle::borrow_raw(raw) })
+    }
 }
@@ -0,0 +1,156 @@
+use crate::{Error, WWindInitState, Window};
+
+/// How a window is shown when it is created
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WindowState {
+    #[default]
+    Normal,
+    Maximized,
+    Minimized,
+    Fullscreen,
+}
+
+/// Describes a window before it is created.
+///
+/// Every option has a default, so only the ones that matter need to be set. Options that the
+/// window system doesn't support are ignored; Wayland windows can't be positioned, for example.
+/// ```rust,no_run
+/// use wwind::{WWindInstance, WWindInitState, WindowBuilder};
+///
+/// let instance = WWindInstance::new(|state: &mut WWindInitState| {
+///     WindowBuilder::new()
+///         .title("Settings")
+///         .size(400, 300)
+///         .min_size(200, 150)
+///         .class("settings")
+///         .build(state)
+///         .unwrap();
+/// })
+/// .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct WindowBuilder {
+    pub(crate) title: String,
+    pub(crate) width: u16,
+    pub(crate) height: u16,
+    /// `None` lets the window manager choose
+    pub(crate) position: Option<(i16, i16)>,
+    pub(crate) min_size: Option<(u16, u16)>,
+    pub(crate) max_size: Option<(u16, u16)>,
+    pub(crate) resizable: bool,
+    pub(crate) decorations: bool,
+    pub(crate) visible: bool,
+    pub(crate) class: Option<String>,
+    pub(crate) state: WindowState,
+}
+
+impl Default for WindowBuilder {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            width: 640,
+            height: 480,
+            position: None,
+            min_size: None,
+            max_size: None,
+            resizable: true,
+            decorations: true,
+            visible: true,
+            class: None,
+            state: WindowState::Normal,
+        }
+    }
+}
+
+impl WindowBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the size of the window's contents. The default is 640x480.
+    pub fn size(mut self, width: u16, height: u16) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets where the window is placed on the screen. By default, the window manager decides.
+    pub fn position(mut self, x: i16, y: i16) -> Self {
+        self.position = Some((x, y));
+        self
+    }
+
+    /// Sets the smallest size that the user can resize the window to
+    pub fn min_size(mut self, width: u16, height: u16) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    /// Sets the largest size that the user can resize the window to
+    pub fn max_size(mut self, width: u16, height: u16) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    /// Sets whether the user can resize the window. Defaults to `true`.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Sets whether the window manager draws a title bar and border around the window.
+    /// Defaults to `true`.
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    /// Sets whether the window is shown once it is created. Defaults to `true`.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Sets the name that the desktop uses to group the application's windows and find its
+    /// icon and settings. This is `WM_CLASS` on X11 and the app id on Wayland.
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.class = Some(class.into());
+        self
+    }
+
+    /// Sets whether the window starts out maximized, minimized or fullscreen
+    pub fn initial_state(mut self, state: WindowState) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Creates the window
+    pub fn build<'a, UserData>(
+        &self,
+        state: &'a mut WWindInitState<UserData>,
+    ) -> Result<Window<'a, UserData>, Error> {
+        state.create_window(self)
+    }
+
+    /// The size of the window, clamped to the minimum and maximum sizes
+    pub(crate) fn clamped_size(&self) -> (u16, u16) {
+        let (mut width, mut height) = (self.width, self.height);
+
+        if let Some((max_width, max_height)) = self.max_size {
+            width = width.min(max_width);
+            height = height.min(max_height);
+        }
+
+        if let Some((min_width, min_height)) = self.min_size {
+            width = width.max(min_width);
+            height = height.max(min_height);
+        }
+
+        (width, height)
+    }
+}
@@ -0,0 +1,884 @@
+//! Drives the event dispatch through the headless backend
+
+#![cfg(feature = "headless")]
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::{Mutex, MutexGuard},
+    thread,
+    time::Duration,
+};
+
+use wwind::{
+    Color, DrawingContext, DropEvent, DropPayload, Framebuffer, HeadlessEvent, HeadlessHandle, Key,
+    KeyEvent, Modifiers, Monitor, PumpStatus, RectRegion, WWindInitState, WWindInstance,
+    WindowStateFlags,
+};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// Only one instance can exist at a time, but tests run on several threads
+fn lock() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    LOCK.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn pump<F: FnOnce(&mut WWindInitState)>(instance: &mut WWindInstance<F>) {
+    let status = instance.pump_events(Some(Duration::ZERO)).unwrap();
+
+    assert_eq!(status, PumpStatus::Continue);
+}
+
+/// Runs `on_init` and handles the events that creating the windows caused
+fn start<F: FnOnce(&mut WWindInitState)>(
+    instance: &mut WWindInstance<F>,
+    log: &Log,
+) -> HeadlessHandle {
+    pump(instance);
+    log.borrow_mut().clear();
+
+    instance.headless_handle().unwrap()
+}
+
+fn key_event(key: Key) -> KeyEvent {
+    KeyEvent {
+        key,
+        raw_key: 0,
+        modifiers: Modifiers::NONE,
+        repeat: false,
+        timestamp: 0,
+    }
+}
+
+fn drop_event(text: &str) -> DropEvent {
+    DropEvent {
+        x: 1,
+        y: 2,
+        payload: DropPayload::Text(text.to_owned()),
+    }
+}
+
+#[test]
+fn key_and_text_events() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, 10, 10, "test").unwrap();
+
+        let log = init_log.clone();
+        window
+            .on_keydown(move |_, _, event| log.borrow_mut().push(format!("down {:?}", event.key)));
+        let log = init_log.clone();
+        window.on_keyup(move |_, _, event| log.borrow_mut().push(format!("up {:?}", event.key)));
+        let log = init_log;
+        window.on_text_input(move |_, _, text| log.borrow_mut().push(format!("text {text}")));
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let window = handle.windows()[0];
+
+    handle.send_event(window, HeadlessEvent::Keydown(key_event(Key::A)));
+    handle.send_event(window, HeadlessEvent::TextInput('é'));
+    handle.send_event(window, HeadlessEvent::Keyup(key_event(Key::A)));
+    pump(&mut instance);
+
+    assert_eq!(*log.borrow(), ["down A", "text é", "up A"]);
+}
+
+#[test]
+fn focus_changes() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        for title in ["first", "second"] {
+            let mut window = state.add_window(0, 0, 10, 10, title).unwrap();
+
+            let log = init_log.clone();
+            window.on_focus_change(move |_, _, focused| {
+                log.borrow_mut().push(format!("{title} {focused}"))
+            });
+            window.on_keydown(|_, window, _| window.focus().unwrap());
+        }
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let [first, second] = handle.windows()[..] else {
+        panic!("expected two windows");
+    };
+
+    handle.send_event(first, HeadlessEvent::FocusChange(true));
+    pump(&mut instance);
+    assert_eq!(handle.focused_window(), Some(first));
+
+    // Focusing the other window takes the focus away from the first one
+    handle.send_event(second, HeadlessEvent::Keydown(key_event(Key::A)));
+    pump(&mut instance);
+    assert_eq!(handle.focused_window(), Some(second));
+
+    handle.send_event(second, HeadlessEvent::FocusChange(false));
+    pump(&mut instance);
+    assert_eq!(handle.focused_window(), None);
+
+    assert_eq!(
+        *log.borrow(),
+        ["first true", "first false", "second true", "second false"]
+    );
+}
+
+#[test]
+fn state_changes() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, 10, 10, "test").unwrap();
+
+        window.on_state_change(move |_, _, state| init_log.borrow_mut().push(format!("{state:?}")));
+        window.on_keydown(|_, window, event| match event.key {
+            Key::M => window.minimize().unwrap(),
+            Key::X => window.set_maximized(true).unwrap(),
+            Key::R => window.set_maximized(false).unwrap(),
+            Key::F => window.set_fullscreen(true).unwrap(),
+            _ => {}
+        });
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let window = handle.windows()[0];
+
+    let mut press = |key| {
+        handle.send_event(window, HeadlessEvent::Keydown(key_event(key)));
+        pump(&mut instance);
+        handle.state(window).unwrap()
+    };
+
+    assert_eq!(press(Key::X), WindowStateFlags::MAXIMIZED);
+    assert_eq!(
+        press(Key::M),
+        WindowStateFlags::MAXIMIZED | WindowStateFlags::MINIMIZED
+    );
+    // Restoring a minimized window brings it back on screen
+    assert_eq!(press(Key::R), WindowStateFlags::NONE);
+    assert_eq!(press(Key::M), WindowStateFlags::MINIMIZED);
+    assert_eq!(press(Key::F), WindowStateFlags::FULLSCREEN);
+
+    assert_eq!(log.borrow().len(), 5);
+
+    // The window manager can change the state on its own
+    handle.send_event(window, HeadlessEvent::StateChange(WindowStateFlags::NONE));
+    pump(&mut instance);
+    assert_eq!(handle.state(window), Some(WindowStateFlags::NONE));
+    assert_eq!(log.borrow().len(), 6);
+}
+
+#[test]
+fn drops_are_only_delivered_to_windows_that_accept_them() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, 10, 10, "ignores drops").unwrap();
+        let log = init_log.clone();
+        window.on_keydown(move |_, _, _| log.borrow_mut().push("key".to_owned()));
+
+        let mut window = state.add_window(0, 0, 10, 10, "accepts drops").unwrap();
+        let log = init_log.clone();
+        window.on_drag_over(move |_, _, event| {
+            log.borrow_mut().push(format!("over {:?}", event.payload))
+        });
+        let log = init_log;
+        window
+            .on_drop(move |_, _, event| log.borrow_mut().push(format!("drop {:?}", event.payload)));
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let [ignores, accepts] = handle.windows()[..] else {
+        panic!("expected two windows");
+    };
+
+    assert_eq!(handle.accepts_drops(ignores), Some(false));
+    assert_eq!(handle.accepts_drops(accepts), Some(true));
+
+    handle.send_event(ignores, HeadlessEvent::DragOver(drop_event("a")));
+    handle.send_event(ignores, HeadlessEvent::Drop(drop_event("a")));
+    handle.send_event(ignores, HeadlessEvent::Keydown(key_event(Key::A)));
+    handle.send_event(accepts, HeadlessEvent::DragOver(drop_event("b")));
+    handle.send_event(accepts, HeadlessEvent::Drop(drop_event("b")));
+    pump(&mut instance);
+
+    assert_eq!(
+        *log.borrow(),
+        ["key", "over Text(\"b\")", "drop Text(\"b\")"]
+    );
+}
+
+#[test]
+fn destroying_a_window_drops_its_queued_events() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        for title in ["destroyed", "kept"] {
+            let mut window = state.add_window(0, 0, 10, 10, title).unwrap();
+
+            let log = init_log.clone();
+            window.on_keydown(move |_, window, event| {
+                log.borrow_mut().push(format!("{title} {:?}", event.key));
+
+                if event.key == Key::Escape {
+                    window.schedule_window_destruction();
+                }
+            });
+        }
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let [destroyed, kept] = handle.windows()[..] else {
+        panic!("expected two windows");
+    };
+
+    handle.send_event(destroyed, HeadlessEvent::Keydown(key_event(Key::Escape)));
+    handle.send_event(destroyed, HeadlessEvent::Keydown(key_event(Key::A)));
+    handle.send_event(kept, HeadlessEvent::Keydown(key_event(Key::B)));
+    pump(&mut instance);
+
+    assert_eq!(handle.windows(), [kept]);
+    assert_eq!(handle.title(destroyed), None);
+    assert_eq!(*log.borrow(), ["destroyed Escape", "kept B"]);
+}
+
+#[test]
+fn monitor_changes() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        state.add_window(0, 0, 10, 10, "test").unwrap();
+
+        state.on_monitors_changed(move |state| {
+            let names = state
+                .monitors()
+                .unwrap()
+                .into_iter()
+                .map(|monitor| monitor.name)
+                .collect::<Vec<_>>();
+
+            init_log.borrow_mut().push(names.join(","));
+        });
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+
+    let monitor = Monitor {
+        name: "HDMI-1".to_owned(),
+        x: 0,
+        y: 0,
+        width: 1920,
+        height: 1080,
+        refresh_rate: Some(60000),
+        physical_width: 530,
+        physical_height: 300,
+        primary: true,
+    };
+    let second_monitor = Monitor {
+        name: "DP-1".to_owned(),
+        x: 1920,
+        primary: false,
+        ..monitor.clone()
+    };
+
+    handle.set_monitors(&[monitor.clone(), second_monitor]);
+    pump(&mut instance);
+
+    handle.set_monitors(&[monitor]);
+    pump(&mut instance);
+
+    // Nothing changed since the last pump
+    pump(&mut instance);
+
+    assert_eq!(*log.borrow(), ["HDMI-1,DP-1", "HDMI-1"]);
+}
+
+/// Creates a window, lets `draw` paint it in its first redraw and returns what was drawn
+fn render<F: FnMut(&mut DrawingContext) + 'static>(
+    width: u16,
+    height: u16,
+    mut draw: F,
+) -> Framebuffer {
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, height, width, "test").unwrap();
+
+        window.on_redraw(move |_, window, _| draw(&mut window.get_drawing_context()));
+    })
+    .unwrap();
+
+    pump(&mut instance);
+
+    let handle = instance.headless_handle().unwrap();
+    handle.framebuffer(handle.windows()[0]).unwrap()
+}
+
+/// Lists the pixels that were drawn to, row by row
+fn drawn_pixels(framebuffer: &Framebuffer) -> Vec<(u16, u16)> {
+    (0..framebuffer.height())
+        .flat_map(|y| (0..framebuffer.width()).map(move |x| (x, y)))
+        .filter(|&(x, y)| framebuffer.get_pixel(x, y).is_some())
+        .collect()
+}
+
+fn draw_line(width: u16, height: u16, (x1, y1): (u16, u16), (x2, y2): (u16, u16)) -> Framebuffer {
+    render(width, height, move |context| {
+        context.draw_line(x1, y1, x2, y2).unwrap();
+    })
+}
+
+#[test]
+fn shallow_and_steep_lines() {
+    let _lock = lock();
+
+    let shallow = draw_line(8, 8, (0, 0), (6, 2));
+    assert_eq!(
+        drawn_pixels(&shallow),
+        [(0, 0), (1, 0), (2, 1), (3, 1), (4, 1), (5, 2), (6, 2)]
+    );
+
+    let steep = draw_line(8, 8, (0, 0), (2, 5));
+    assert_eq!(
+        drawn_pixels(&steep),
+        [(0, 0), (0, 1), (1, 2), (1, 3), (2, 4), (2, 5)]
+    );
+
+    let point = draw_line(8, 8, (3, 4), (3, 4));
+    assert_eq!(drawn_pixels(&point), [(3, 4)]);
+}
+
+#[test]
+fn reversed_lines_cover_the_same_pixels() {
+    let _lock = lock();
+
+    for (start, end) in [((0, 0), (6, 2)), ((0, 0), (2, 5)), ((1, 6), (7, 1))] {
+        let forward = draw_line(8, 8, start, end);
+        let reversed = draw_line(8, 8, end, start);
+
+        assert_eq!(drawn_pixels(&forward), drawn_pixels(&reversed));
+    }
+}
+
+#[test]
+fn lines_are_clipped_at_the_edge() {
+    let _lock = lock();
+
+    let horizontal = draw_line(4, 4, (2, 1), (60, 1));
+    assert_eq!(drawn_pixels(&horizontal), [(2, 1), (3, 1)]);
+
+    let vertical = draw_line(4, 4, (1, u16::MAX), (1, 2));
+    assert_eq!(drawn_pixels(&vertical), [(1, 2), (1, 3)]);
+
+    let outside = draw_line(4, 4, (10, 10), (20, 0));
+    assert_eq!(drawn_pixels(&outside), []);
+}
+
+#[test]
+fn rectangles() {
+    let _lock = lock();
+
+    let framebuffer = render(4, 5, |context| {
+        context.set_draw_color(Color::from_hex(0x102030)).unwrap();
+        context
+            .draw_rectangle(RectRegion {
+                x: 1,
+                y: 1,
+                width: 2,
+                height: 3,
+            })
+            .unwrap();
+
+        // Clipped at the bottom right corner
+        context.set_draw_color(Color::from_hex(0xFF0000)).unwrap();
+        context
+            .draw_rectangle(RectRegion {
+                x: 3,
+                y: 4,
+                width: u16::MAX,
+                height: u16::MAX,
+            })
+            .unwrap();
+
+        // Empty
+        context
+            .draw_rectangle(RectRegion {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 5,
+            })
+            .unwrap();
+    });
+
+    assert_eq!(
+        drawn_pixels(&framebuffer),
+        [(1, 1), (2, 1), (1, 2), (2, 2), (1, 3), (2, 3), (3, 4)]
+    );
+
+    let color = |x, y| framebuffer.get_pixel(x, y).map(u32::from);
+    assert_eq!(color(2, 3), Some(Color::from_hex(0x102030).into()));
+    assert_eq!(color(3, 4), Some(Color::from_hex(0xFF0000).into()));
+    assert_eq!(framebuffer.data()[..4], [0, 0, 0, 0]);
+}
+
+#[test]
+fn resizing_keeps_the_overlapping_pixels() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, 4, 4, "test").unwrap();
+
+        let mut drawn = false;
+        window.on_redraw(move |_, window, _| {
+            if !drawn {
+                drawn = true;
+
+                let mut context = window.get_drawing_context();
+                context.draw_line(0, 0, 3, 3).unwrap();
+            }
+        });
+        window.on_resize(move |_, window, width, height| {
+            init_log
+                .borrow_mut()
+                .push(format!("{width}x{height} {:?}", window.get_size()))
+        });
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let window = handle.windows()[0];
+
+    handle.send_event(window, HeadlessEvent::Resize(2, 6));
+    pump(&mut instance);
+
+    let framebuffer = handle.framebuffer(window).unwrap();
+    assert_eq!((framebuffer.width(), framebuffer.height()), (2, 6));
+    assert_eq!(framebuffer.data().len(), 2 * 6 * 4);
+    assert_eq!(drawn_pixels(&framebuffer), [(0, 0), (1, 1)]);
+
+    handle.send_event(window, HeadlessEvent::Resize(5, 1));
+    pump(&mut instance);
+
+    let framebuffer = handle.framebuffer(window).unwrap();
+    assert_eq!(drawn_pixels(&framebuffer), [(0, 0)]);
+
+    // Resizing to the current size isn't reported
+    handle.send_event(window, HeadlessEvent::Resize(5, 1));
+    pump(&mut instance);
+
+    assert_eq!(*log.borrow(), ["2x6 (2, 6)", "5x1 (5, 1)"]);
+}
+
+#[test]
+fn close_and_expose_events() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, 10, 10, "handles close").unwrap();
+
+        let log = init_log.clone();
+        window.on_window_close(move |_, _| log.borrow_mut().push("close".to_owned()));
+        let log = init_log;
+        window.on_redraw(move |_, _, region| log.borrow_mut().push(format!("{region:?}")));
+
+        // Windows without a close handler are destroyed
+        state.add_window(0, 0, 10, 10, "closes").unwrap();
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let [handles_close, closes] = handle.windows()[..] else {
+        panic!("expected two windows");
+    };
+
+    let region = RectRegion {
+        x: 2,
+        y: 3,
+        width: 4,
+        height: 5,
+    };
+    handle.send_event(handles_close, HeadlessEvent::Expose(region));
+    pump(&mut instance);
+
+    // Parts outside of the window aren't redrawn
+    handle.send_event(
+        handles_close,
+        HeadlessEvent::Expose(RectRegion {
+            x: 8,
+            y: 0,
+            width: 100,
+            height: 1,
+        }),
+    );
+    pump(&mut instance);
+
+    handle.send_event(handles_close, HeadlessEvent::Close);
+    handle.send_event(closes, HeadlessEvent::Close);
+    pump(&mut instance);
+
+    assert_eq!(handle.windows(), [handles_close]);
+    assert_eq!(
+        *log.borrow(),
+        [
+            format!("{region:?}"),
+            format!(
+                "{:?}",
+                RectRegion {
+                    x: 8,
+                    y: 0,
+                    width: 2,
+                    height: 1,
+                }
+            ),
+            "close".to_owned(),
+        ]
+    );
+}
+
+#[test]
+fn invalidated_regions_are_merged_into_one_redraw() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, 100, 100, "test").unwrap();
+
+        window.on_redraw(move |_, _, region| init_log.borrow_mut().push(format!("{region:?}")));
+        window.on_keydown(|_, window, _| {
+            window.invalidate(RectRegion {
+                x: 10,
+                y: 20,
+                width: 5,
+                height: 5,
+            });
+            window.invalidate(RectRegion {
+                x: 30,
+                y: 5,
+                width: 10,
+                height: 10,
+            });
+        });
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let window = handle.windows()[0];
+
+    handle.send_event(window, HeadlessEvent::Keydown(key_event(Key::A)));
+    pump(&mut instance);
+
+    let union = RectRegion {
+        x: 10,
+        y: 5,
+        width: 30,
+        height: 20,
+    };
+    assert_eq!(*log.borrow(), [format!("{union:?}")]);
+}
+
+/// Creates an instance with one window and lets `add_timers` set up timers in `on_init`
+fn with_timers<T: FnOnce(&mut WWindInitState, &Log) + 'static>(
+    log: &Log,
+    add_timers: T,
+) -> WWindInstance<impl FnOnce(&mut WWindInitState)> {
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        state.add_window(0, 0, 10, 10, "test").unwrap();
+
+        add_timers(state, &init_log);
+    })
+    .unwrap();
+
+    start(&mut instance, log);
+
+    instance
+}
+
+fn push(log: &Log, entry: &str) {
+    log.borrow_mut().push(entry.to_owned());
+}
+
+#[test]
+fn due_timers_run_in_deadline_order() {
+    let _lock = lock();
+    let log = Log::default();
+
+    let mut instance = with_timers(&log, |state, log| {
+        for (delay, name) in [(30, "c"), (10, "a"), (20, "b")] {
+            let log = log.clone();
+            state.set_timeout(Duration::from_millis(delay), move |_| push(&log, name));
+        }
+    });
+
+    thread::sleep(Duration::from_millis(40));
+    pump(&mut instance);
+
+    assert_eq!(*log.borrow(), ["a", "b", "c"]);
+}
+
+#[test]
+fn timers_cancelled_by_an_earlier_callback_dont_run() {
+    let _lock = lock();
+    let log = Log::default();
+
+    let mut instance = with_timers(&log, |state, log| {
+        let later = Rc::new(Cell::new(None));
+
+        let first_log = log.clone();
+        let first_later = later.clone();
+        state.set_timeout(Duration::from_millis(5), move |state| {
+            push(&first_log, "first");
+            state.cancel_timer(first_later.get().unwrap());
+        });
+
+        let log = log.clone();
+        later.set(Some(
+            state.set_timeout(Duration::from_millis(10), move |_| push(&log, "cancelled")),
+        ));
+    });
+
+    // Both timers are due in the same batch
+    thread::sleep(Duration::from_millis(20));
+    pump(&mut instance);
+    pump(&mut instance);
+
+    assert_eq!(*log.borrow(), ["first"]);
+}
+
+#[test]
+fn intervals_can_cancel_themselves() {
+    let _lock = lock();
+    let log = Log::default();
+
+    let mut instance = with_timers(&log, |state, log| {
+        let handle = Rc::new(Cell::new(None));
+
+        let log = log.clone();
+        let own_handle = handle.clone();
+        handle.set(Some(state.set_interval(
+            Duration::from_millis(1),
+            move |state| {
+                push(&log, "tick");
+                state.cancel_timer(own_handle.get().unwrap());
+            },
+        )));
+    });
+
+    for _ in 0..3 {
+        thread::sleep(Duration::from_millis(5));
+        pump(&mut instance);
+    }
+
+    assert_eq!(*log.borrow(), ["tick"]);
+}
+
+#[test]
+fn intervals_are_rescheduled_and_skip_missed_runs() {
+    let _lock = lock();
+    let log = Log::default();
+
+    let mut instance = with_timers(&log, |state, log| {
+        let log = log.clone();
+        state.set_interval(Duration::from_millis(50), move |_| push(&log, "tick"));
+    });
+
+    thread::sleep(Duration::from_millis(60));
+    pump(&mut instance);
+    assert_eq!(log.borrow().len(), 1);
+
+    // The next run is a whole interval away
+    pump(&mut instance);
+    assert_eq!(log.borrow().len(), 1);
+
+    // Falling several intervals behind runs the timer once instead of catching up
+    thread::sleep(Duration::from_millis(250));
+    pump(&mut instance);
+    pump(&mut instance);
+    assert_eq!(log.borrow().len(), 2);
+}
+
+#[test]
+fn zero_intervals_run_once_per_pump() {
+    let _lock = lock();
+    let log = Log::default();
+
+    let mut instance = with_timers(&log, |state, log| {
+        let log = log.clone();
+        state.set_interval(Duration::ZERO, move |_| push(&log, "tick"));
+    });
+
+    log.borrow_mut().clear();
+
+    // Waiting without a timeout returns after the timer ran instead of spinning on it
+    for count in 1..=3 {
+        let status = instance.pump_events(None).unwrap();
+
+        assert_eq!(status, PumpStatus::Continue);
+        assert_eq!(log.borrow().len(), count);
+    }
+}
+
+#[test]
+fn pumping_without_waiting_drains_every_queued_event() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, 10, 10, "test").unwrap();
+
+        window
+            .on_keydown(move |_, _, event| init_log.borrow_mut().push(format!("{:?}", event.key)));
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let window = handle.windows()[0];
+
+    for key in [Key::A, Key::B, Key::C, Key::D] {
+        handle.send_event(window, HeadlessEvent::Keydown(key_event(key)));
+    }
+
+    let status = instance.pump_events(Some(Duration::ZERO)).unwrap();
+
+    assert_eq!(status, PumpStatus::Continue);
+    assert_eq!(*log.borrow(), ["A", "B", "C", "D"]);
+}
+
+#[test]
+fn pumping_exits_once_the_last_window_is_destroyed() {
+    let _lock = lock();
+    let log = Log::default();
+
+    let mut instance = WWindInstance::new_headless(|state: &mut WWindInitState| {
+        state.add_window(0, 0, 10, 10, "first").unwrap();
+        state.add_window(0, 0, 10, 10, "second").unwrap();
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let [first, second] = handle.windows()[..] else {
+        panic!("expected two windows");
+    };
+
+    handle.send_event(first, HeadlessEvent::Close);
+    pump(&mut instance);
+
+    handle.send_event(second, HeadlessEvent::Close);
+    let status = instance.pump_events(Some(Duration::ZERO)).unwrap();
+
+    assert_eq!(status, PumpStatus::Exit);
+    assert_eq!(instance.backend_type(), None);
+    assert!(instance.headless_handle().is_none());
+
+    // The instance stays torn down
+    let status = instance.pump_events(Some(Duration::ZERO)).unwrap();
+    assert_eq!(status, PumpStatus::Exit);
+}
+
+#[test]
+fn running_again_after_exiting() {
+    let _lock = lock();
+    let runs = Rc::new(Cell::new(0));
+
+    let new_instance = |runs: Rc<Cell<u32>>| {
+        WWindInstance::new_headless(move |state: &mut WWindInitState| {
+            state.add_window(0, 0, 10, 10, "test").unwrap();
+            state.set_timeout(Duration::ZERO, move |state| {
+                runs.set(runs.get() + 1);
+                state.schedule_exit();
+            });
+        })
+        .unwrap()
+    };
+
+    let mut instance = new_instance(runs.clone());
+    instance.run_return().unwrap();
+    assert_eq!(runs.get(), 1);
+
+    // Running the same instance again returns right away instead of running it twice
+    instance.run_return().unwrap();
+    assert_eq!(runs.get(), 1);
+
+    // A new instance doesn't see the previous exit, so it runs until its own
+    let mut instance = new_instance(runs.clone());
+    assert!(instance.backend_type().is_some());
+
+    instance.run_return().unwrap();
+    assert_eq!(runs.get(), 2);
+    assert_eq!(instance.backend_type(), None);
+}
+
+#[test]
+fn user_events_from_other_threads() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+    let proxy = Rc::new(RefCell::new(None));
+    let init_proxy = proxy.clone();
+
+    let mut instance = WWindInstance::new_headless(move |state: &mut WWindInitState| {
+        state.add_window(0, 0, 10, 10, "test").unwrap();
+
+        state.on_user_event(move |_, value: u32| init_log.borrow_mut().push(value.to_string()));
+        *init_proxy.borrow_mut() = Some(state.create_proxy::<u32>());
+    })
+    .unwrap();
+
+    let handle = start(&mut instance, &log);
+    let window = handle.windows()[0];
+    let proxy = proxy.borrow_mut().take().unwrap();
+
+    let sender = proxy.clone();
+    let thread = thread::spawn(move || {
+        // Gives the event loop time to start waiting
+        thread::sleep(Duration::from_millis(50));
+
+        for value in 1..=3 {
+            sender.send(value).unwrap();
+        }
+    });
+
+    // Without a timeout, this only returns because the proxy wakes it up
+    while log.borrow().len() < 3 {
+        let status = instance.pump_events(None).unwrap();
+        assert_eq!(status, PumpStatus::Continue);
+    }
+
+    thread.join().unwrap();
+    assert_eq!(*log.borrow(), ["1", "2", "3"]);
+
+    handle.send_event(window, HeadlessEvent::Close);
+    let status = instance.pump_events(Some(Duration::ZERO)).unwrap();
+    assert_eq!(status, PumpStatus::Exit);
+
+    // The value is given back once nothing can receive it
+    assert_eq!(proxy.send(4), Err(4));
+}
@@ -0,0 +1,148 @@
+//! Runs the Wayland backend against a real compositor. These tests are ignored by default
+//! because they need one. Weston's headless backend works without a display:
+//!
+//! ```sh
+//! weston --backend=headless --socket=wwind-test &
+//! WAYLAND_DISPLAY=wwind-test cargo test --features wayland --test wayland -- --ignored
+//! ```
+//!
+//! Weston versions before 10 name the backend `headless-backend.so` instead.
+
+#![cfg(feature = "wayland")]
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use wwind::{Backend, CoreStateType, PumpStatus, WWindInitState, WWindInstance};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// Only one instance can exist at a time, but tests run on several threads
+fn lock() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    LOCK.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Handles events until `done` returns `true`. The compositor answers asynchronously, so this
+/// gives it a few seconds.
+fn pump_until<F: FnOnce(&mut WWindInitState)>(
+    instance: &mut WWindInstance<F>,
+    mut done: impl FnMut() -> bool,
+) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    while !done() {
+        assert!(
+            Instant::now() < deadline,
+            "timed out waiting for the compositor"
+        );
+
+        let status = instance
+            .pump_events(Some(Duration::from_millis(100)))
+            .unwrap();
+
+        assert_eq!(status, PumpStatus::Continue);
+    }
+}
+
+/// Connects to the compositor without falling back to the other backends
+fn connect<F: FnOnce(&mut WWindInitState)>(on_init: F) -> WWindInstance<F> {
+    let instance = WWindInstance::with_backend(Backend::Wayland, on_init).unwrap();
+
+    assert_eq!(instance.backend_type(), Some(CoreStateType::Wayland));
+
+    instance
+}
+
+#[test]
+#[ignore = "needs a Wayland compositor"]
+fn windows_are_configured_and_redrawn() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = connect(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, 100, 200, "wwind test").unwrap();
+
+        let log = init_log.clone();
+        window.on_redraw(move |_, window, region| {
+            log.borrow_mut().push(format!("redraw {region:?}"));
+
+            let mut context = window.get_drawing_context();
+            context.draw_rectangle(region).unwrap();
+        });
+        let log = init_log;
+        window.on_resize(move |_, _, width, height| {
+            log.borrow_mut().push(format!("resize {width}x{height}"))
+        });
+    });
+
+    // Nothing is drawn until the compositor configures the window
+    pump_until(&mut instance, || !log.borrow().is_empty());
+    assert!(log.borrow()[0].starts_with("redraw"));
+}
+
+#[test]
+#[ignore = "needs a Wayland compositor"]
+fn resizing_redraws_the_window() {
+    let _lock = lock();
+    let log = Log::default();
+    let init_log = log.clone();
+
+    let mut instance = connect(move |state: &mut WWindInitState| {
+        let mut window = state.add_window(0, 0, 100, 200, "wwind test").unwrap();
+
+        let mut resized = false;
+        let log = init_log.clone();
+        window.on_redraw(move |_, window, _| {
+            log.borrow_mut()
+                .push(format!("redraw {:?}", window.get_size()));
+
+            if !resized {
+                resized = true;
+                window.set_size(300, 150).unwrap();
+            }
+        });
+        let log = init_log;
+        window.on_resize(move |_, _, width, height| {
+            log.borrow_mut().push(format!("resize {width}x{height}"))
+        });
+    });
+
+    pump_until(&mut instance, || log.borrow().len() >= 3);
+
+    assert_eq!(
+        log.borrow()[..3],
+        ["redraw (200, 100)", "resize 300x150", "redraw (300, 150)"]
+    );
+}
+
+#[test]
+#[ignore = "needs a Wayland compositor"]
+fn monitors_come_from_the_outputs() {
+    let _lock = lock();
+
+    let monitors = Rc::new(RefCell::new(None));
+    let init_monitors = monitors.clone();
+
+    let mut instance = connect(move |state: &mut WWindInitState| {
+        state.add_window(0, 0, 100, 100, "wwind test").unwrap();
+
+        *init_monitors.borrow_mut() = Some(state.monitors().unwrap());
+    });
+
+    pump_until(&mut instance, || monitors.borrow().is_some());
+
+    let monitors = monitors.borrow_mut().take().unwrap();
+    assert!(!monitors.is_empty());
+
+    for monitor in &monitors {
+        assert!(monitor.width > 0 && monitor.height > 0, "{monitor:?}");
+        assert!(!monitor.primary);
+    }
+}